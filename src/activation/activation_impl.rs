@@ -1,4 +1,27 @@
 pub trait Activation {
     fn function(&self, x: &[f64]) -> Vec<f64>;
     fn derivative(&self, x: &[f64]) -> Vec<f64>;
+
+    /// Full Jacobian at `x`, `jacobian[i][j]` is the derivative of output `i`
+    /// with respect to input `j`. Element-wise activations are diagonal.
+    fn jacobian(&self, x: &[f64]) -> Vec<Vec<f64>> {
+        let derivatives = self.derivative(x);
+        (0..x.len())
+            .map(|i| {
+                let mut row = vec![0.0; x.len()];
+                row[i] = derivatives[i];
+                row
+            })
+            .collect()
+    }
+
+    /// Product of the Jacobian at `x` with `output_grad`, i.e. the gradient
+    /// with respect to `x` given the gradient with respect to the outputs.
+    fn backward(&self, x: &[f64], output_grad: &[f64]) -> Vec<f64> {
+        self.derivative(x)
+            .iter()
+            .zip(output_grad.iter())
+            .map(|(d, g)| d * g)
+            .collect()
+    }
 }
@@ -19,7 +19,7 @@ impl Gaussian {
 impl Activation for Gaussian {
     fn function(&self, x: &[f64]) -> Vec<f64> {
         x.iter()
-            .map(|x| ((x - self.mu).powi(2) / (2.0 * self.sigma.powi(2))).exp())
+            .map(|x| (-(x - self.mu).powi(2) / (2.0 * self.sigma.powi(2))).exp())
             .collect()
     }
 
@@ -27,7 +27,7 @@ impl Activation for Gaussian {
         x.iter()
             .map(|x| {
                 -((x - self.mu) / self.sigma.powi(2))
-                    * ((x - self.mu).powi(2) / (2.0 * self.sigma.powi(2))).exp()
+                    * (-(x - self.mu).powi(2) / (2.0 * self.sigma.powi(2))).exp()
             })
             .collect()
     }
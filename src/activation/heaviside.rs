@@ -1,5 +1,7 @@
 use crate::activation::Activation;
 
+/// The derivative is a straight-through estimate (the derivative of ReLU),
+/// the step function itself has a zero derivative everywhere but at zero.
 pub struct Heaviside;
 
 impl Activation for Heaviside {
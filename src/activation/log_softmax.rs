@@ -0,0 +1,38 @@
+use crate::activation::{Activation, Softmax};
+
+pub struct LogSoftmax;
+
+impl Activation for LogSoftmax {
+    fn function(&self, x: &[f64]) -> Vec<f64> {
+        let max = x.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let log_sum = x.iter().map(|x| (x - max).exp()).sum::<f64>().ln() + max;
+        x.iter().map(|x| x - log_sum).collect()
+    }
+
+    fn derivative(&self, x: &[f64]) -> Vec<f64> {
+        Softmax {}.function(x).iter().map(|s| 1.0 - s).collect()
+    }
+
+    fn jacobian(&self, x: &[f64]) -> Vec<Vec<f64>> {
+        let softmax = Softmax {}.function(x);
+        (0..x.len())
+            .map(|i| {
+                softmax
+                    .iter()
+                    .enumerate()
+                    .map(|(j, sj)| if i == j { 1.0 - sj } else { -sj })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn backward(&self, x: &[f64], output_grad: &[f64]) -> Vec<f64> {
+        let softmax = Softmax {}.function(x);
+        let sum = output_grad.iter().sum::<f64>();
+        softmax
+            .iter()
+            .zip(output_grad.iter())
+            .map(|(s, g)| g - s * sum)
+            .collect()
+    }
+}
@@ -2,9 +2,12 @@ pub mod activation_impl;
 pub mod gaussian;
 pub mod heaviside;
 pub mod linear;
+pub mod log_softmax;
 pub mod multiquadratics;
 pub mod relu;
 pub mod sigmoid;
+pub mod softmax;
+pub mod sparsemax;
 pub mod tanh;
 pub mod utils;
 
@@ -12,8 +15,11 @@ pub use crate::activation::activation_impl::Activation;
 pub use crate::activation::gaussian::Gaussian;
 pub use crate::activation::heaviside::Heaviside;
 pub use crate::activation::linear::Linear;
+pub use crate::activation::log_softmax::LogSoftmax;
 pub use crate::activation::multiquadratics::Multiquadratics;
 pub use crate::activation::relu::ReLU;
 pub use crate::activation::sigmoid::Sigmoid;
+pub use crate::activation::softmax::Softmax;
+pub use crate::activation::sparsemax::Sparsemax;
 pub use crate::activation::tanh::Tanh;
-pub use crate::activation::utils::get_activation;
+pub use crate::activation::utils::{get_activation, ACTIVATIONS};
@@ -0,0 +1,46 @@
+use crate::activation::Activation;
+
+pub struct Softmax;
+
+impl Activation for Softmax {
+    fn function(&self, x: &[f64]) -> Vec<f64> {
+        let max = x.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let exps: Vec<f64> = x.iter().map(|x| (x - max).exp()).collect();
+        let sum = exps.iter().sum::<f64>();
+        exps.iter().map(|e| e / sum).collect()
+    }
+
+    fn derivative(&self, x: &[f64]) -> Vec<f64> {
+        let softmax = self.function(x);
+        softmax.iter().map(|s| s * (1.0 - s)).collect()
+    }
+
+    fn jacobian(&self, x: &[f64]) -> Vec<Vec<f64>> {
+        let softmax = self.function(x);
+        softmax
+            .iter()
+            .enumerate()
+            .map(|(i, si)| {
+                softmax
+                    .iter()
+                    .enumerate()
+                    .map(|(j, sj)| if i == j { si * (1.0 - sj) } else { -si * sj })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn backward(&self, x: &[f64], output_grad: &[f64]) -> Vec<f64> {
+        let softmax = self.function(x);
+        let dot = softmax
+            .iter()
+            .zip(output_grad.iter())
+            .map(|(s, g)| s * g)
+            .sum::<f64>();
+        softmax
+            .iter()
+            .zip(output_grad.iter())
+            .map(|(s, g)| s * (g - dot))
+            .collect()
+    }
+}
@@ -0,0 +1,75 @@
+use crate::activation::Activation;
+
+/// Sparsemax, see https://arxiv.org/abs/1602.02068
+pub struct Sparsemax;
+
+impl Sparsemax {
+    fn support(&self, x: &[f64]) -> Vec<bool> {
+        self.function(x).iter().map(|p| *p > 0.0).collect()
+    }
+}
+
+impl Activation for Sparsemax {
+    fn function(&self, x: &[f64]) -> Vec<f64> {
+        let mut sorted = x.to_vec();
+        sorted.sort_by(|a, b| b.total_cmp(a));
+
+        let mut sum = 0.0;
+        let mut threshold = 0.0;
+        for (k, z) in sorted.iter().enumerate() {
+            sum += z;
+            let k = (k + 1) as f64;
+            if 1.0 + k * z > sum {
+                threshold = (sum - 1.0) / k;
+            }
+        }
+
+        x.iter().map(|x| (x - threshold).max(0.0)).collect()
+    }
+
+    fn derivative(&self, x: &[f64]) -> Vec<f64> {
+        let support = self.support(x);
+        let size = support.iter().filter(|s| **s).count() as f64;
+        support
+            .iter()
+            .map(|s| if *s { 1.0 - 1.0 / size } else { 0.0 })
+            .collect()
+    }
+
+    fn jacobian(&self, x: &[f64]) -> Vec<Vec<f64>> {
+        let support = self.support(x);
+        let size = support.iter().filter(|s| **s).count() as f64;
+        support
+            .iter()
+            .enumerate()
+            .map(|(i, si)| {
+                support
+                    .iter()
+                    .enumerate()
+                    .map(|(j, sj)| match (si, sj) {
+                        (true, true) if i == j => 1.0 - 1.0 / size,
+                        (true, true) => -1.0 / size,
+                        _ => 0.0,
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn backward(&self, x: &[f64], output_grad: &[f64]) -> Vec<f64> {
+        let support = self.support(x);
+        let size = support.iter().filter(|s| **s).count() as f64;
+        let mean = support
+            .iter()
+            .zip(output_grad.iter())
+            .filter(|(s, _)| **s)
+            .map(|(_, g)| g)
+            .sum::<f64>()
+            / size;
+        support
+            .iter()
+            .zip(output_grad.iter())
+            .map(|(s, g)| if *s { g - mean } else { 0.0 })
+            .collect()
+    }
+}
@@ -1,16 +1,38 @@
 use std::collections::HashMap;
 
 use crate::activation::*;
+use crate::error::Error;
 
-pub fn get_activation(name: &str, params: HashMap<String, f64>) -> Box<dyn Activation> {
-    match name {
+/// Names accepted by `get_activation`.
+pub const ACTIVATIONS: [&str; 10] = [
+    "relu",
+    "tanh",
+    "linear",
+    "sigmoid",
+    "softmax",
+    "sparsemax",
+    "heaviside",
+    "log-softmax",
+    "gaussian",
+    "multiquadratics",
+];
+
+pub fn get_activation(
+    name: &str,
+    params: HashMap<String, f64>,
+) -> Result<Box<dyn Activation>, Error> {
+    let activation: Box<dyn Activation> = match name {
         "relu" => Box::new(ReLU {}),
         "tanh" => Box::new(Tanh {}),
         "linear" => Box::new(Linear {}),
         "sigmoid" => Box::new(Sigmoid {}),
+        "softmax" => Box::new(Softmax {}),
+        "sparsemax" => Box::new(Sparsemax {}),
         "heaviside" => Box::new(Heaviside {}),
+        "log-softmax" => Box::new(LogSoftmax {}),
         "gaussian" => Box::new(Gaussian::new(params)),
         "multiquadratics" => Box::new(Multiquadratics::new(params)),
-        _ => panic!("activation function should be sigmoid or relu"),
-    }
+        _ => return Err(Error::UnknownActivation(name.to_string())),
+    };
+    Ok(activation)
 }
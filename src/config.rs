@@ -1,78 +1,635 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
+use crate::error::Error;
+use crate::layer::{Window, LAYERS};
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct ConfigInitialization {
     pub method: String,
     pub seed: Option<u64>,
 }
 
+/// Learning-rate schedule, see `schedule::get_schedule` for the methods.
+/// `reduce-on-plateau` follows the validation loss and is only accepted by
+/// `Network::fit`; `train` returns a validation error for it.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ConfigSchedule {
+    pub method: String,
+    #[serde(default)]
+    pub params: HashMap<String, f64>,
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Config {
     pub lr: f64,
+    /// Schedule of the learning rate, constant at `lr` when unset.
+    #[serde(default)]
+    pub schedule: Option<ConfigSchedule>,
+    #[serde(default = "default_optimizer")]
+    pub optimizer: String,
+    /// Hyperparameters of the optimizer, e.g. `momentum`, `beta1`, `beta2`,
+    /// `epsilon` or `weight_decay`. Missing ones take their default values.
+    #[serde(default)]
+    pub optimizer_params: HashMap<String, f64>,
     pub epochs: usize,
+    /// Samples per optimization step, at least 2 with batch-norm layers since
+    /// the variance of a single sample is zero.
+    #[serde(default = "default_batch_size")]
+    pub batch_size: usize,
+    /// Visit the training samples in a different order every epoch.
+    #[serde(default = "default_shuffle")]
+    pub shuffle: bool,
     pub loss: String,
+    /// Shape of one input sample, e.g. `[channels, height, width]`. Flat
+    /// `[layers[0].input_size]` when empty.
+    #[serde(default)]
+    pub input_shape: Vec<usize>,
     pub layers: Vec<ConfigLayer>,
     pub initialization: ConfigInitialization,
+    #[serde(default)]
+    pub metrics: Vec<String>,
+    /// Epochs without improvement of the validation loss before `fit` stops.
+    #[serde(default)]
+    pub patience: Option<usize>,
+    /// Smallest decrease of the validation loss counted as an improvement.
+    #[serde(default)]
+    pub min_delta: f64,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct ConfigLayer {
     pub name: String,
+    /// Layer type, one of `layer::LAYERS`.
+    #[serde(default = "default_kind")]
+    pub kind: String,
     pub input_size: usize,
     pub output_size: usize,
     pub activation: String,
+    #[serde(default)]
+    pub l1: f64,
+    #[serde(default)]
+    pub l2: f64,
+    /// Decoupled weight decay, see `Regularization`. Not allowed along with
+    /// the decay of `adamw`, which must then be set to 0.
+    #[serde(default)]
+    pub weight_decay: f64,
+    /// Apply `l1`, `l2` and `weight_decay` to the biases too. Only layers
+    /// with weights are regularized, the coefficients must be 0 on the others.
+    #[serde(default)]
+    pub regularize_biases: bool,
+    /// Fraction of the inputs zeroed by a dropout layer during training.
+    #[serde(default)]
+    pub rate: f64,
+    /// Weight of the current batch in the running statistics of a batch
+    /// normalization layer.
+    #[serde(default = "default_momentum")]
+    pub momentum: f64,
+    /// Added to the variance by normalization layers for numerical stability.
+    #[serde(default = "default_epsilon")]
+    pub epsilon: f64,
+    /// Input channels of convolution and pooling layers, whose inputs hold
+    /// `channels` consecutive signals of `input_size / channels` values.
+    #[serde(default = "default_one")]
+    pub channels: usize,
+    /// Output channels of a convolution layer.
+    #[serde(default = "default_one")]
+    pub filters: usize,
+    #[serde(default = "default_one")]
+    pub kernel_size: usize,
+    #[serde(default = "default_one")]
+    pub stride: usize,
+    /// Zeros added on both sides of each signal.
+    #[serde(default)]
+    pub padding: usize,
+    /// Spacing between the kernel taps.
+    #[serde(default = "default_one")]
+    pub dilation: usize,
+    /// Image height of 2D convolution and pooling layers.
+    #[serde(default = "default_one")]
+    pub height: usize,
+    /// Image width of 2D convolution and pooling layers.
+    #[serde(default = "default_one")]
+    pub width: usize,
+    /// Sequence length of recurrent layers, whose inputs hold `steps`
+    /// consecutive vectors of `input_size / steps` values.
+    #[serde(default = "default_one")]
+    pub steps: usize,
+    /// Size of the hidden state of recurrent layers and of the feed-forward
+    /// network of transformer blocks.
+    #[serde(default = "default_one")]
+    pub hidden_size: usize,
+    /// Output the hidden state of every step instead of the last one.
+    #[serde(default)]
+    pub return_sequences: bool,
+    /// Length of the chunks of steps that gradients flow back through
+    /// (truncated backpropagation through time), zero for the whole sequence.
+    #[serde(default)]
+    pub truncation: usize,
+    /// Number of token indices of an embedding layer, whose inputs hold
+    /// `input_size` indices.
+    #[serde(default = "default_one")]
+    pub vocab_size: usize,
+    /// Size of the vector of each token of an embedding layer.
+    #[serde(default = "default_one")]
+    pub embedding_size: usize,
+    /// Token index embedded as zeros and never updated.
+    #[serde(default)]
+    pub padding_index: Option<usize>,
+    /// Text file of pre-trained embeddings, see `data::read_embeddings`.
+    #[serde(default)]
+    pub embeddings: Option<String>,
+    /// Whether each line of `embeddings` starts with its word, as in GloVe
+    /// files.
+    #[serde(default = "default_embedding_words")]
+    pub embedding_words: bool,
+    /// Attention heads, which split the `input_size / steps` features of
+    /// each step between them.
+    #[serde(default = "default_one")]
+    pub heads: usize,
+    /// Attend only to the current and earlier steps.
+    #[serde(default)]
+    pub causal: bool,
+}
+
+/// Layer kinds whose inputs are `channels` signals.
+const CHANNELED: [&str; 4] = ["conv1d", "max-pool1d", "avg-pool1d", "global-avg-pool1d"];
+
+/// Layer kinds whose inputs are `channels` images of `height × width`.
+const PLANAR: [&str; 3] = ["conv2d", "max-pool2d", "avg-pool2d"];
+
+/// Layer kinds whose inputs are sequences of `steps` vectors.
+const RECURRENT: [&str; 3] = ["rnn", "lstm", "gru"];
+
+/// Layer kinds mapping sequences of `steps` vectors to sequences of the same
+/// shape.
+const ATTENTION: [&str; 3] = ["positional-encoding", "attention", "transformer"];
+
+/// Layer kinds with weights that `l1`, `l2` and `weight_decay` apply to.
+const REGULARIZED: [&str; 9] = [
+    "dense",
+    "conv1d",
+    "conv2d",
+    "rnn",
+    "lstm",
+    "gru",
+    "embedding",
+    "attention",
+    "transformer",
+];
+
+fn default_optimizer() -> String {
+    "sgd".to_string()
+}
+
+fn default_kind() -> String {
+    "dense".to_string()
+}
+
+fn default_momentum() -> f64 {
+    0.1
+}
+
+fn default_epsilon() -> f64 {
+    1e-5
+}
+
+fn default_one() -> usize {
+    1
+}
+
+fn default_batch_size() -> usize {
+    1
+}
+
+fn default_shuffle() -> bool {
+    true
+}
+
+fn default_embedding_words() -> bool {
+    true
+}
+
+impl Default for ConfigInitialization {
+    fn default() -> Self {
+        Self {
+            method: "uniform-distribution".to_string(),
+            seed: None,
+        }
+    }
+}
+
+impl Default for ConfigLayer {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            kind: default_kind(),
+            input_size: 0,
+            output_size: 0,
+            activation: "linear".to_string(),
+            l1: 0.0,
+            l2: 0.0,
+            weight_decay: 0.0,
+            regularize_biases: false,
+            rate: 0.0,
+            momentum: default_momentum(),
+            epsilon: default_epsilon(),
+            channels: 1,
+            filters: 1,
+            kernel_size: 1,
+            stride: 1,
+            padding: 0,
+            dilation: 1,
+            height: 1,
+            width: 1,
+            steps: 1,
+            hidden_size: 1,
+            return_sequences: false,
+            truncation: 0,
+            vocab_size: 1,
+            embedding_size: 1,
+            padding_index: None,
+            embeddings: None,
+            embedding_words: true,
+            heads: 1,
+            causal: false,
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            lr: 0.01,
+            schedule: None,
+            epochs: 1,
+            batch_size: default_batch_size(),
+            shuffle: default_shuffle(),
+            input_shape: Vec::new(),
+            layers: Vec::new(),
+            loss: "mse".to_string(),
+            optimizer: default_optimizer(),
+            optimizer_params: HashMap::new(),
+            initialization: ConfigInitialization::default(),
+            metrics: Vec::new(),
+            patience: None,
+            min_delta: 0.0,
+        }
+    }
 }
 
 impl Config {
-    pub fn new(path: &str) -> Self {
-        let file = std::fs::File::open(path).expect("Unable to open file");
-        serde_json::from_reader(file).expect("Unable to parse JSON")
+    pub fn new(path: &str) -> Result<Self, Error> {
+        let file = std::fs::File::open(path)?;
+        let config: Self = serde_json::from_reader(std::io::BufReader::new(file))?;
+        config.validate()?;
+        Ok(config)
     }
 
-    pub fn validate(&self) -> Result<(), String> {
+    pub fn validate(&self) -> Result<(), Error> {
         if self.lr <= 0.0 {
-            return Err("learning rate must be positive".to_string());
+            return Err(Error::Validation(
+                "learning rate must be positive".to_string(),
+            ));
+        }
+
+        if self.batch_size == 0 {
+            return Err(Error::Validation("batch size must be positive".to_string()));
         }
 
         if self.loss.is_empty() {
-            return Err("loss function must be specified".to_string());
+            return Err(Error::Validation(
+                "loss function must be specified".to_string(),
+            ));
+        }
+
+        if self.optimizer.is_empty() {
+            return Err(Error::Validation("optimizer must be specified".to_string()));
+        }
+
+        if self.min_delta < 0.0 {
+            return Err(Error::Validation(
+                "min delta must not be negative".to_string(),
+            ));
+        }
+
+        if self.layers.is_empty() {
+            return Err(Error::Validation(
+                "at least one layer must be specified".to_string(),
+            ));
+        }
+
+        // adamw decays every parameter itself, 0.01 unless set otherwise
+        let optimizer_decay = self.optimizer == "adamw"
+            && self
+                .optimizer_params
+                .get("weight_decay")
+                .is_none_or(|decay| *decay != 0.0);
+        if optimizer_decay && self.layers.iter().any(|l| l.weight_decay != 0.0) {
+            return Err(Error::Validation(
+                "weight decay set on both adamw and layers".to_string(),
+            ));
         }
 
-        if self
-            .layers
-            .iter()
-            .zip(self.layers.iter().skip(1))
-            .any(|(l, n)| l.output_size != n.input_size)
-        {
-            return Err("input and output size must be the same".to_string());
+        if self.batch_size < 2 && self.layers.iter().any(|l| l.kind == "batch-norm") {
+            return Err(Error::Validation(
+                "batch-norm layers need a batch size of at least 2".to_string(),
+            ));
         }
 
-        if self.layers.iter().any(|l| l.validate().is_err()) {
-            return Err("layer validation failed".to_string());
+        self.infer_shapes()?;
+
+        self.initialization.validate()?;
+
+        if let Some(schedule) = &self.schedule {
+            schedule.validate()?;
         }
 
         Ok(())
     }
 }
 
+impl Config {
+    /// Validates each layer and follows the shape of a sample through the
+    /// stack, from `input_shape` to the output shape of each layer.
+    pub fn infer_shapes(&self) -> Result<Vec<Vec<usize>>, Error> {
+        let mut shape = match (self.input_shape.is_empty(), self.layers.first()) {
+            (false, _) => self.input_shape.clone(),
+            (true, Some(layer)) => vec![layer.input_size],
+            (true, None) => Vec::new(),
+        };
+        let mut shapes = Vec::with_capacity(self.layers.len());
+        for layer in self.layers.iter() {
+            let size = shape.iter().product();
+            if layer.input_size != size {
+                return Err(Error::ShapeMismatch {
+                    expected: size,
+                    actual: layer.input_size,
+                });
+            }
+            let input = layer.input_shape();
+            if input.len() > 1 && shape.len() > 1 && input != shape {
+                return Err(Error::Validation(format!(
+                    "layer {} expects inputs of shape {:?}, got {:?}",
+                    layer.name, input, shape
+                )));
+            }
+            layer.validate()?;
+
+            if !layer.keeps_shape() {
+                shape = layer.output_shape();
+            }
+            shapes.push(shape.clone());
+        }
+        Ok(shapes)
+    }
+}
+
 impl ConfigLayer {
-    pub fn validate(&self) -> Result<(), String> {
+    pub fn validate(&self) -> Result<(), Error> {
+        if !LAYERS.contains(&self.kind.as_str()) {
+            return Err(Error::UnknownLayer(self.kind.clone()));
+        }
+
         if self.input_size == 0 || self.output_size == 0 {
-            return Err("input and output size must be positive".to_string());
+            return Err(Error::Validation(
+                "input and output size must be positive".to_string(),
+            ));
         }
 
         if self.activation.is_empty() {
-            return Err("activation function must be specified".to_string());
+            return Err(Error::Validation(
+                "activation function must be specified".to_string(),
+            ));
+        }
+
+        if self.l1 < 0.0 || self.l2 < 0.0 || self.weight_decay < 0.0 {
+            return Err(Error::Validation(
+                "regularization coefficients must not be negative".to_string(),
+            ));
+        }
+
+        let regularized = self.l1 != 0.0 || self.l2 != 0.0 || self.weight_decay != 0.0;
+        if regularized && !REGULARIZED.contains(&self.kind.as_str()) {
+            return Err(Error::Validation(format!(
+                "{} layers are not regularized",
+                self.kind
+            )));
+        }
+
+        if self.kind == "dropout" && !(0.0..1.0).contains(&self.rate) {
+            return Err(Error::Validation(
+                "dropout rate must be in [0, 1)".to_string(),
+            ));
+        }
+
+        if self.kind == "batch-norm" && !(0.0..=1.0).contains(&self.momentum) {
+            return Err(Error::Validation(
+                "batch norm momentum must be in [0, 1]".to_string(),
+            ));
+        }
+
+        let normalization = ["batch-norm", "layer-norm"].contains(&self.kind.as_str());
+        if normalization && self.epsilon <= 0.0 {
+            return Err(Error::Validation(
+                "normalization epsilon must be positive".to_string(),
+            ));
+        }
+
+        if CHANNELED.contains(&self.kind.as_str()) {
+            if self.channels == 0
+                || self.filters == 0
+                || self.kernel_size == 0
+                || self.stride == 0
+                || self.dilation == 0
+            {
+                return Err(Error::Validation(
+                    "channels, filters, kernel size, stride and dilation must be positive"
+                        .to_string(),
+                ));
+            }
+            if !self.input_size.is_multiple_of(self.channels) {
+                return Err(Error::Validation(
+                    "input size must be a multiple of channels".to_string(),
+                ));
+            }
+            let length = self.input_size / self.channels;
+            if self.kind != "global-avg-pool1d" && Window::new(self, length).outputs() == 0 {
+                return Err(Error::Validation(
+                    "kernel must fit in the padded input".to_string(),
+                ));
+            }
+        }
+
+        if PLANAR.contains(&self.kind.as_str()) {
+            if self.channels == 0
+                || self.filters == 0
+                || self.kernel_size == 0
+                || self.stride == 0
+                || self.dilation == 0
+                || self.height == 0
+                || self.width == 0
+            {
+                return Err(Error::Validation(
+                    "channels, filters, kernel size, stride, dilation, height and width must be positive"
+                        .to_string(),
+                ));
+            }
+            let expected = self.channels * self.height * self.width;
+            if self.input_size != expected {
+                return Err(Error::ShapeMismatch {
+                    expected,
+                    actual: self.input_size,
+                });
+            }
+            let (rows, cols) = (
+                Window::new(self, self.height),
+                Window::new(self, self.width),
+            );
+            if rows.outputs() == 0 || cols.outputs() == 0 {
+                return Err(Error::Validation(
+                    "kernel must fit in the padded input".to_string(),
+                ));
+            }
+        }
+
+        if RECURRENT.contains(&self.kind.as_str()) {
+            if self.steps == 0 || self.hidden_size == 0 {
+                return Err(Error::Validation(
+                    "steps and hidden size must be positive".to_string(),
+                ));
+            }
+            if !self.input_size.is_multiple_of(self.steps) {
+                return Err(Error::Validation(
+                    "input size must be a multiple of steps".to_string(),
+                ));
+            }
+        }
+
+        if ATTENTION.contains(&self.kind.as_str()) {
+            if self.steps == 0 || self.heads == 0 {
+                return Err(Error::Validation(
+                    "steps and heads must be positive".to_string(),
+                ));
+            }
+            if !self.input_size.is_multiple_of(self.steps) {
+                return Err(Error::Validation(
+                    "input size must be a multiple of steps".to_string(),
+                ));
+            }
+            if !(self.input_size / self.steps).is_multiple_of(self.heads) {
+                return Err(Error::Validation(
+                    "features of each step must be a multiple of heads".to_string(),
+                ));
+            }
+        }
+
+        if self.kind == "embedding" {
+            if self.vocab_size == 0 || self.embedding_size == 0 {
+                return Err(Error::Validation(
+                    "vocabulary and embedding size must be positive".to_string(),
+                ));
+            }
+            if self.padding_index.is_some_and(|p| p >= self.vocab_size) {
+                return Err(Error::Validation(
+                    "padding index must be in the vocabulary".to_string(),
+                ));
+            }
+        }
+
+        let expected = self.infer_output_size();
+        if self.output_size != expected {
+            return Err(Error::ShapeMismatch {
+                expected,
+                actual: self.output_size,
+            });
         }
 
         Ok(())
     }
+
+    /// Shape of a sample as seen by the layer: `[channels, length]` for 1D
+    /// convolution and pooling, `[channels, height, width]` for 2D ones,
+    /// `[steps, features]` for recurrent and attention layers and flat
+    /// otherwise.
+    pub fn input_shape(&self) -> Vec<usize> {
+        let kind = self.kind.as_str();
+        if CHANNELED.contains(&kind) {
+            vec![self.channels, self.input_size / self.channels.max(1)]
+        } else if PLANAR.contains(&kind) {
+            vec![self.channels, self.height, self.width]
+        } else if RECURRENT.contains(&kind) || ATTENTION.contains(&kind) {
+            vec![self.steps, self.input_size / self.steps.max(1)]
+        } else {
+            vec![self.input_size]
+        }
+    }
+
+    /// Output shape implied by the kind and the input shape. Only dense
+    /// layers choose their output size freely.
+    pub fn output_shape(&self) -> Vec<usize> {
+        let channels = self.channels.max(1);
+        let length = || Window::new(self, self.input_size / channels).outputs();
+        let plane = || {
+            let (rows, cols) = (
+                Window::new(self, self.height),
+                Window::new(self, self.width),
+            );
+            (rows.outputs(), cols.outputs())
+        };
+        match self.kind.as_str() {
+            "dense" => vec![self.output_size],
+            "conv1d" => vec![self.filters, length()],
+            "max-pool1d" | "avg-pool1d" => vec![channels, length()],
+            "global-avg-pool1d" => vec![channels],
+            "conv2d" => {
+                let (h, w) = plane();
+                vec![self.filters, h, w]
+            }
+            "max-pool2d" | "avg-pool2d" => {
+                let (h, w) = plane();
+                vec![channels, h, w]
+            }
+            "rnn" | "lstm" | "gru" if self.return_sequences => {
+                vec![self.steps, self.hidden_size]
+            }
+            "rnn" | "lstm" | "gru" => vec![self.hidden_size],
+            "embedding" => vec![self.input_size, self.embedding_size],
+            "positional-encoding" | "attention" | "transformer" => self.input_shape(),
+            _ => vec![self.input_size],
+        }
+    }
+
+    pub fn infer_output_size(&self) -> usize {
+        self.output_shape().iter().product()
+    }
+
+    /// Layers without weights, except `flatten`, keep the shape of their
+    /// inputs.
+    pub fn keeps_shape(&self) -> bool {
+        ["activation", "dropout", "batch-norm", "layer-norm"].contains(&self.kind.as_str())
+    }
 }
 
 impl ConfigInitialization {
-    pub fn validate(&self) -> Result<(), String> {
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.method.is_empty() {
+            return Err(Error::Validation(
+                "initialization method must be specified".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl ConfigSchedule {
+    pub fn validate(&self) -> Result<(), Error> {
         if self.method.is_empty() {
-            return Err("initialization method must be specified".to_string());
+            return Err(Error::Validation(
+                "schedule method must be specified".to_string(),
+            ));
         }
 
         Ok(())
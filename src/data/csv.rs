@@ -0,0 +1,167 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::fs::File;
+use std::io::BufReader;
+
+use crate::dataset::Dataset;
+use crate::error::Error;
+use crate::matrix::Matrix;
+
+/// CSV column, by header name or by 0-based index.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Column {
+    Name(String),
+    Index(usize),
+}
+
+impl From<&str> for Column {
+    fn from(name: &str) -> Self {
+        Column::Name(name.to_string())
+    }
+}
+
+impl From<usize> for Column {
+    fn from(index: usize) -> Self {
+        Column::Index(index)
+    }
+}
+
+impl fmt::Display for Column {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Column::Name(name) => write!(f, "{}", name),
+            Column::Index(index) => write!(f, "{}", index),
+        }
+    }
+}
+
+pub struct CsvOptions {
+    /// Input columns. Every column that is not a target when empty.
+    pub features: Vec<Column>,
+    pub targets: Vec<Column>,
+    /// Categorical target columns, one-hot encoded over their distinct
+    /// values in sorted order.
+    pub one_hot: Vec<Column>,
+    pub has_headers: bool,
+    pub delimiter: u8,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            features: Vec::new(),
+            targets: Vec::new(),
+            one_hot: Vec::new(),
+            has_headers: true,
+            delimiter: b',',
+        }
+    }
+}
+
+/// Reads the CSV file at `path` into a dataset.
+pub fn read_csv(path: &str, options: &CsvOptions) -> Result<Dataset, Error> {
+    let mut reader = ::csv::ReaderBuilder::new()
+        .has_headers(options.has_headers)
+        .delimiter(options.delimiter)
+        .from_reader(BufReader::new(File::open(path)?));
+
+    let headers = match options.has_headers {
+        true => Some(reader.headers().map_err(csv_error)?.clone()),
+        false => None,
+    };
+    let records = reader
+        .records()
+        .map(|r| r.map_err(csv_error))
+        .collect::<Result<Vec<_>, Error>>()?;
+    let width = headers.as_ref().or(records.first()).map_or(0, |r| r.len());
+
+    let resolve = |column: &Column| -> Result<usize, Error> {
+        let index = match column {
+            Column::Name(name) => headers
+                .as_ref()
+                .and_then(|h| h.iter().position(|n| n.trim() == name)),
+            Column::Index(index) => Some(*index).filter(|i| *i < width),
+        };
+        index.ok_or_else(|| Error::Validation(format!("unknown column: {}", column)))
+    };
+
+    if options.targets.is_empty() {
+        return Err(Error::Validation(
+            "at least one target column must be specified".to_string(),
+        ));
+    }
+    let targets = options
+        .targets
+        .iter()
+        .map(resolve)
+        .collect::<Result<Vec<usize>, Error>>()?;
+    let features = match options.features.is_empty() {
+        true => (0..width).filter(|i| !targets.contains(i)).collect(),
+        false => options
+            .features
+            .iter()
+            .map(resolve)
+            .collect::<Result<Vec<usize>, Error>>()?,
+    };
+
+    let mut categories: HashMap<usize, Vec<&str>> = HashMap::new();
+    for column in options.one_hot.iter() {
+        let index = resolve(column)?;
+        if !targets.contains(&index) {
+            return Err(Error::Validation(format!(
+                "one-hot column is not a target: {}",
+                column
+            )));
+        }
+        let values: BTreeSet<&str> = records.iter().map(|r| r[index].trim()).collect();
+        categories.insert(index, values.into_iter().collect());
+    }
+
+    let mut inputs = Vec::with_capacity(records.len());
+    let mut outputs = Vec::with_capacity(records.len());
+    for record in records.iter() {
+        let line = record.position().map_or(0, |p| p.line());
+        let number = |index: usize| -> Result<f64, Error> {
+            let value = record[index].trim();
+            value.parse().map_err(|_| Error::Malformed {
+                line,
+                message: format!("invalid number in column {}: {:?}", index, value),
+            })
+        };
+
+        inputs.push(
+            features
+                .iter()
+                .map(|&i| number(i))
+                .collect::<Result<Vec<f64>, Error>>()?,
+        );
+        let mut output = Vec::new();
+        for &index in targets.iter() {
+            match categories.get(&index) {
+                Some(values) => output.extend(values.iter().map(|v| {
+                    if *v == record[index].trim() {
+                        1.0
+                    } else {
+                        0.0
+                    }
+                })),
+                None => output.push(number(index)?),
+            }
+        }
+        outputs.push(output);
+    }
+
+    Dataset::new(
+        Matrix::from_rows(inputs.iter().map(|r| r.as_slice())),
+        Matrix::from_rows(outputs.iter().map(|r| r.as_slice())),
+    )
+}
+
+fn csv_error(e: ::csv::Error) -> Error {
+    let line = e.position().map_or(0, |p| p.line());
+    let message = e.to_string();
+    match e.into_kind() {
+        ::csv::ErrorKind::Io(e) => Error::Io(e),
+        _ => Error::Malformed { line, message },
+    }
+}
@@ -0,0 +1,42 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+
+use crate::error::Error;
+use crate::matrix::Matrix;
+
+/// Reads a text embedding matrix, one row of whitespace-separated values per
+/// line: row `i` is the embedding of index `i`. With `words`, every line
+/// starts with a word, as in GloVe files, which is ignored even if it reads
+/// as a number. Blank lines are skipped.
+pub fn read_embeddings(path: &str, words: bool) -> Result<Matrix, Error> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut rows: Vec<Vec<f64>> = Vec::new();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        let mut fields = line.split_whitespace().peekable();
+        if fields.peek().is_none() {
+            continue;
+        }
+        if words {
+            fields.next();
+        }
+        let row = fields
+            .map(|v| {
+                v.parse().map_err(|_| Error::Malformed {
+                    line: i as u64 + 1,
+                    message: format!("invalid number: {:?}", v),
+                })
+            })
+            .collect::<Result<Vec<f64>, Error>>()?;
+        if let Some(first) = rows.first() {
+            if row.len() != first.len() {
+                return Err(Error::Malformed {
+                    line: i as u64 + 1,
+                    message: format!("expected {} values, got {}", first.len(), row.len()),
+                });
+            }
+        }
+        rows.push(row);
+    }
+    Ok(Matrix::from_rows(rows.iter().map(|r| r.as_slice())))
+}
@@ -0,0 +1,119 @@
+use std::fs::File;
+use std::io::{BufReader, Read};
+
+use flate2::read::GzDecoder;
+
+use crate::dataset::Dataset;
+use crate::error::Error;
+use crate::matrix::Matrix;
+
+/// Contents of an IDX file: the dimensions and the values in row-major order.
+pub struct Idx {
+    pub dims: Vec<usize>,
+    pub data: Vec<f64>,
+}
+
+/// Reads the IDX file at `path`, gzip-compressed or not.
+pub fn read_idx(path: &str) -> Result<Idx, Error> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+    if bytes.starts_with(&[0x1f, 0x8b]) {
+        let mut decompressed = Vec::new();
+        GzDecoder::new(bytes.as_slice()).read_to_end(&mut decompressed)?;
+        bytes = decompressed;
+    }
+    parse_idx(&bytes)
+}
+
+fn parse_idx(bytes: &[u8]) -> Result<Idx, Error> {
+    // https://yann.lecun.com/exdb/mnist/: two zero bytes, the data type,
+    // the number of dimensions, then the big-endian u32 dimensions
+    let invalid = |message: &str| Error::Validation(format!("invalid IDX file: {}", message));
+    let [0, 0, kind, ndims, ..] = *bytes else {
+        return Err(invalid("bad magic number"));
+    };
+    let header = 4 + 4 * ndims as usize;
+    if bytes.len() < header {
+        return Err(invalid("truncated header"));
+    }
+    let dims: Vec<usize> = bytes[4..header]
+        .chunks_exact(4)
+        .map(|d| u32::from_be_bytes([d[0], d[1], d[2], d[3]]) as usize)
+        .collect();
+
+    let size = match kind {
+        0x08 | 0x09 => 1,
+        0x0b => 2,
+        0x0c | 0x0d => 4,
+        0x0e => 8,
+        _ => return Err(invalid("unknown data type")),
+    };
+    let body = &bytes[header..];
+    let overflow = || Error::Malformed {
+        line: 1,
+        message: format!("IDX dimensions {:?} overflow", dims),
+    };
+    let count = dims
+        .iter()
+        .try_fold(1usize, |count, d| count.checked_mul(*d))
+        .ok_or_else(overflow)?;
+    let expected = count.checked_mul(size).ok_or_else(overflow)?;
+    if body.len() != expected {
+        return Err(Error::ShapeMismatch {
+            expected,
+            actual: body.len(),
+        });
+    }
+
+    let data = body
+        .chunks_exact(size)
+        .map(|v| match kind {
+            0x08 => v[0] as f64,
+            0x09 => v[0] as i8 as f64,
+            0x0b => i16::from_be_bytes([v[0], v[1]]) as f64,
+            0x0c => i32::from_be_bytes([v[0], v[1], v[2], v[3]]) as f64,
+            0x0d => f32::from_be_bytes([v[0], v[1], v[2], v[3]]) as f64,
+            _ => f64::from_be_bytes([v[0], v[1], v[2], v[3], v[4], v[5], v[6], v[7]]),
+        })
+        .collect();
+    Ok(Idx { dims, data })
+}
+
+/// Reads MNIST-style IDX image and label files into a dataset. Each image is
+/// flattened and its pixels scaled from `0..=255` to `[0, 1]`, each label is
+/// one-hot encoded over `classes`.
+pub fn read_mnist(images: &str, labels: &str, classes: usize) -> Result<Dataset, Error> {
+    let images = read_idx(images)?;
+    let labels = read_idx(labels)?;
+    if images.dims.is_empty() || labels.dims.is_empty() {
+        return Err(Error::Validation(
+            "MNIST images and labels need at least one dimension".to_string(),
+        ));
+    }
+    let count = images.dims.first().copied().unwrap_or(0);
+    if labels.data.len() != count {
+        return Err(Error::ShapeMismatch {
+            expected: count,
+            actual: labels.data.len(),
+        });
+    }
+
+    let size = images.dims.iter().skip(1).product();
+    let inputs = Matrix {
+        rows: count,
+        cols: size,
+        data: images.data.iter().map(|p| p / 255.0).collect(),
+    };
+    let mut targets = Matrix::zeros(count, classes);
+    for (i, label) in labels.data.iter().enumerate() {
+        if *label < 0.0 || *label >= classes as f64 {
+            return Err(Error::Validation(format!(
+                "label {} out of range for {} classes",
+                label, classes
+            )));
+        }
+        targets.row_mut(i)[*label as usize] = 1.0;
+    }
+    Dataset::new(inputs, targets)
+}
@@ -0,0 +1,7 @@
+pub mod csv;
+pub mod embeddings;
+pub mod idx;
+
+pub use crate::data::csv::{read_csv, Column, CsvOptions};
+pub use crate::data::embeddings::read_embeddings;
+pub use crate::data::idx::{read_idx, read_mnist, Idx};
@@ -0,0 +1,88 @@
+use crate::error::Error;
+use crate::matrix::Matrix;
+
+/// Input vectors of each step of a sequence, and its target vectors.
+pub type Sequence = (Vec<Vec<f64>>, Vec<Vec<f64>>);
+
+/// Inputs and targets of a dataset, one sample per row.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Dataset {
+    pub inputs: Matrix,
+    pub targets: Matrix,
+}
+
+impl Dataset {
+    pub fn new(inputs: Matrix, targets: Matrix) -> Result<Self, Error> {
+        if inputs.rows != targets.rows {
+            return Err(Error::ShapeMismatch {
+                expected: inputs.rows,
+                actual: targets.rows,
+            });
+        }
+        Ok(Self { inputs, targets })
+    }
+
+    pub fn from_samples(samples: &[(Vec<f64>, Vec<f64>)]) -> Self {
+        Self::try_from_samples(samples).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `from_samples`, returning an error if the inputs or the targets
+    /// of the samples differ in length.
+    pub fn try_from_samples(samples: &[(Vec<f64>, Vec<f64>)]) -> Result<Self, Error> {
+        if let Some((x, y)) = samples.first() {
+            for (inputs, targets) in samples {
+                if inputs.len() != x.len() {
+                    return Err(Error::ShapeMismatch {
+                        expected: x.len(),
+                        actual: inputs.len(),
+                    });
+                }
+                if targets.len() != y.len() {
+                    return Err(Error::ShapeMismatch {
+                        expected: y.len(),
+                        actual: targets.len(),
+                    });
+                }
+            }
+        }
+        Ok(Self {
+            inputs: Matrix::from_rows(samples.iter().map(|(x, _)| x.as_slice())),
+            targets: Matrix::from_rows(samples.iter().map(|(_, y)| y.as_slice())),
+        })
+    }
+
+    /// Flattens sequences step by step, the layout of recurrent layers.
+    /// Targets hold one vector per output step, or a single one for layers
+    /// that only output their last state.
+    pub fn from_sequences(sequences: &[Sequence]) -> Self {
+        let samples: Vec<(Vec<f64>, Vec<f64>)> = sequences
+            .iter()
+            .map(|(x, y)| (x.concat(), y.concat()))
+            .collect();
+        Self::from_samples(&samples)
+    }
+
+    pub fn len(&self) -> usize {
+        self.inputs.rows
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn sample(&self, i: usize) -> (&[f64], &[f64]) {
+        (self.inputs.row(i), self.targets.row(i))
+    }
+
+    pub fn samples(&self) -> impl Iterator<Item = (&[f64], &[f64])> {
+        (0..self.len()).map(|i| self.sample(i))
+    }
+
+    /// Inputs and targets of the samples at `indices`, in that order.
+    pub fn batch(&self, indices: &[usize]) -> (Matrix, Matrix) {
+        (
+            Matrix::from_rows(indices.iter().map(|&i| self.inputs.row(i))),
+            Matrix::from_rows(indices.iter().map(|&i| self.targets.row(i))),
+        )
+    }
+}
@@ -0,0 +1,74 @@
+use std::fmt;
+
+#[derive(Debug)]
+pub enum Error {
+    UnknownActivation(String),
+    UnknownLoss(String),
+    UnknownInitialization(String),
+    UnknownOptimizer(String),
+    UnknownMetric(String),
+    UnknownPreprocessor(String),
+    UnknownSchedule(String),
+    UnknownLayer(String),
+    Io(std::io::Error),
+    Parse(serde_json::Error),
+    /// Malformed record of a data file, `line` is 1-based and 1 for the
+    /// header of binary files.
+    Malformed {
+        line: u64,
+        message: String,
+    },
+    ShapeMismatch {
+        expected: usize,
+        actual: usize,
+    },
+    Validation(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::UnknownActivation(name) => write!(f, "unknown activation function: {}", name),
+            Error::UnknownLoss(name) => write!(f, "unknown loss function: {}", name),
+            Error::UnknownInitialization(name) => {
+                write!(f, "unknown initialization method: {}", name)
+            }
+            Error::UnknownOptimizer(name) => write!(f, "unknown optimizer: {}", name),
+            Error::UnknownMetric(name) => write!(f, "unknown metric: {}", name),
+            Error::UnknownPreprocessor(name) => write!(f, "unknown preprocessor: {}", name),
+            Error::UnknownSchedule(name) => write!(f, "unknown learning rate schedule: {}", name),
+            Error::UnknownLayer(name) => write!(f, "unknown layer kind: {}", name),
+            Error::Io(e) => write!(f, "i/o error: {}", e),
+            Error::Parse(e) => write!(f, "parse error: {}", e),
+            Error::Malformed { line, message } => {
+                write!(f, "malformed record at line {}: {}", line, message)
+            }
+            Error::ShapeMismatch { expected, actual } => {
+                write!(f, "shape mismatch: expected {}, got {}", expected, actual)
+            }
+            Error::Validation(message) => write!(f, "validation error: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            Error::Parse(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Error::Parse(e)
+    }
+}
@@ -0,0 +1,67 @@
+use crate::config::Config;
+use crate::error::Error;
+use crate::layer::{Layer, Mode};
+use crate::network::Network;
+
+/// Largest relative error between analytic and numerical gradients of the
+/// parameters of one layer.
+pub struct GradCheck {
+    pub layer: String,
+    pub max_relative_error: f64,
+}
+
+fn relative_error(analytic: f64, numerical: f64) -> f64 {
+    (analytic - numerical).abs() / (analytic.abs() + numerical.abs()).max(1e-6)
+}
+
+fn parameter(layer: &mut dyn Layer, group: usize, k: usize) -> &mut f64 {
+    &mut layer.parameters_mut().swap_remove(group).0[k]
+}
+
+fn loss(network: &Network, inputs: &[f64], targets: &[f64]) -> f64 {
+    network.loss.function(&network.forward(inputs), targets)
+}
+
+/// Compares the gradients computed by `Network::accumulate` for one sample
+/// against central finite differences of the loss with step `epsilon`.
+pub fn gradcheck(
+    config: &Config,
+    inputs: &[f64],
+    targets: &[f64],
+    epsilon: f64,
+) -> Result<Vec<GradCheck>, Error> {
+    let mut network = Network::try_new(config.clone())?;
+    // dropout masks would make the loss differ between passes
+    network.set_mode(Mode::Eval);
+    network.accumulate(inputs, targets);
+
+    let mut reports = Vec::new();
+    for i in 0..network.layers.len() {
+        let mut max_relative_error: f64 = 0.0;
+        let gradients: Vec<Vec<f64>> = network.layers[i]
+            .gradients()
+            .iter()
+            .map(|g| g.to_vec())
+            .collect();
+        for (group, analytic) in gradients.iter().enumerate() {
+            for (k, analytic) in analytic.iter().enumerate() {
+                let value = *parameter(network.layers[i].as_mut(), group, k);
+
+                *parameter(network.layers[i].as_mut(), group, k) = value + epsilon;
+                let plus = loss(&network, inputs, targets);
+                *parameter(network.layers[i].as_mut(), group, k) = value - epsilon;
+                let minus = loss(&network, inputs, targets);
+                *parameter(network.layers[i].as_mut(), group, k) = value;
+
+                let numerical = (plus - minus) / (2.0 * epsilon);
+                max_relative_error = max_relative_error.max(relative_error(*analytic, numerical));
+            }
+        }
+        reports.push(GradCheck {
+            layer: network.layers[i].name().to_string(),
+            max_relative_error,
+        });
+    }
+
+    Ok(reports)
+}
@@ -0,0 +1,34 @@
+use rand::{Rng, RngCore};
+
+use crate::initialization::{get_rng, sample_normal, Initialization};
+
+/// He et al., see https://arxiv.org/abs/1502.01852
+pub struct He {
+    pub normal: bool,
+    rng: Box<dyn RngCore>,
+}
+
+impl He {
+    pub fn new(seed: Option<u64>, normal: bool) -> Self {
+        Self {
+            normal,
+            rng: get_rng(seed),
+        }
+    }
+}
+
+impl Initialization for He {
+    fn init(&mut self, fan_in: usize, _: usize) -> f64 {
+        let fan = fan_in as f64;
+        if self.normal {
+            sample_normal(self.rng.as_mut(), (2.0 / fan).sqrt())
+        } else {
+            let limit = (6.0 / fan).sqrt();
+            self.rng.gen_range(-limit..limit)
+        }
+    }
+
+    fn bias(&mut self, _: usize, _: usize) -> f64 {
+        0.0
+    }
+}
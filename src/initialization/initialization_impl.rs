@@ -1,3 +1,10 @@
 pub trait Initialization {
-    fn init(&mut self) -> f64;
+    /// Draws one parameter of a layer with `fan_in` inputs and `fan_out` outputs.
+    fn init(&mut self, fan_in: usize, fan_out: usize) -> f64;
+
+    /// Draws one bias of a layer with `fan_in` inputs and `fan_out` outputs.
+    /// Fan-aware schemes only scale the weights and start biases at zero.
+    fn bias(&mut self, fan_in: usize, fan_out: usize) -> f64 {
+        self.init(fan_in, fan_out)
+    }
 }
@@ -0,0 +1,24 @@
+use rand::RngCore;
+
+use crate::initialization::{get_rng, sample_normal, Initialization};
+
+/// LeCun et al., see https://doi.org/10.1007/3-540-49430-8_2
+pub struct Lecun {
+    rng: Box<dyn RngCore>,
+}
+
+impl Lecun {
+    pub fn new(seed: Option<u64>) -> Self {
+        Self { rng: get_rng(seed) }
+    }
+}
+
+impl Initialization for Lecun {
+    fn init(&mut self, fan_in: usize, _: usize) -> f64 {
+        sample_normal(self.rng.as_mut(), (1.0 / fan_in as f64).sqrt())
+    }
+
+    fn bias(&mut self, _: usize, _: usize) -> f64 {
+        0.0
+    }
+}
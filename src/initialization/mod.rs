@@ -1,9 +1,15 @@
+pub mod he;
 pub mod initialization_impl;
+pub mod lecun;
 pub mod uniform_distribution;
 pub mod utils;
+pub mod xavier;
 pub mod zero_centered;
 
+pub use he::He;
 pub use initialization_impl::Initialization;
+pub use lecun::Lecun;
 pub use uniform_distribution::UniformDistribution;
-pub use utils::get_initialization;
+pub use utils::{get_initialization, get_rng, sample_normal};
+pub use xavier::Xavier;
 pub use zero_centered::ZeroCentered;
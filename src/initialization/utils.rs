@@ -1,10 +1,35 @@
+use rand::{Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
 use crate::config::ConfigInitialization;
+use crate::error::Error;
 use crate::initialization::*;
 
-pub fn get_initialization(config: &ConfigInitialization) -> Box<dyn Initialization> {
-    match config.method.as_str() {
+pub fn get_initialization(config: &ConfigInitialization) -> Result<Box<dyn Initialization>, Error> {
+    let initialization: Box<dyn Initialization> = match config.method.as_str() {
         "zero-centered" => Box::new(ZeroCentered::new(config.seed)),
         "uniform-distribution" => Box::new(UniformDistribution::new(config.seed)),
-        _ => panic!("Invalid initialization method"),
+        "xavier-uniform" => Box::new(Xavier::new(config.seed, false)),
+        "xavier-normal" => Box::new(Xavier::new(config.seed, true)),
+        "he-uniform" => Box::new(He::new(config.seed, false)),
+        "he-normal" => Box::new(He::new(config.seed, true)),
+        "lecun-normal" => Box::new(Lecun::new(config.seed)),
+        _ => return Err(Error::UnknownInitialization(config.method.clone())),
+    };
+    Ok(initialization)
+}
+
+/// Seeded `ChaCha8Rng` when `seed` is set, thread-local RNG otherwise.
+pub fn get_rng(seed: Option<u64>) -> Box<dyn RngCore> {
+    match seed {
+        Some(seed) => Box::new(ChaCha8Rng::seed_from_u64(seed)),
+        None => Box::new(rand::thread_rng()),
     }
 }
+
+/// Normal sample with zero mean, using the Box-Muller transform.
+pub fn sample_normal(rng: &mut dyn RngCore, std: f64) -> f64 {
+    let u1: f64 = 1.0 - rng.gen::<f64>();
+    let u2: f64 = rng.gen();
+    std * (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
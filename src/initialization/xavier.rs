@@ -0,0 +1,34 @@
+use rand::{Rng, RngCore};
+
+use crate::initialization::{get_rng, sample_normal, Initialization};
+
+/// Glorot & Bengio, see https://proceedings.mlr.press/v9/glorot10a.html
+pub struct Xavier {
+    pub normal: bool,
+    rng: Box<dyn RngCore>,
+}
+
+impl Xavier {
+    pub fn new(seed: Option<u64>, normal: bool) -> Self {
+        Self {
+            normal,
+            rng: get_rng(seed),
+        }
+    }
+}
+
+impl Initialization for Xavier {
+    fn init(&mut self, fan_in: usize, fan_out: usize) -> f64 {
+        let fan = (fan_in + fan_out) as f64;
+        if self.normal {
+            sample_normal(self.rng.as_mut(), (2.0 / fan).sqrt())
+        } else {
+            let limit = (6.0 / fan).sqrt();
+            self.rng.gen_range(-limit..limit)
+        }
+    }
+
+    fn bias(&mut self, _: usize, _: usize) -> f64 {
+        0.0
+    }
+}
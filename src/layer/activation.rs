@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+
+use rand::RngCore;
+
+use crate::activation::{get_activation, Activation};
+use crate::config::ConfigLayer;
+use crate::error::Error;
+use crate::layer::utils::check_width;
+use crate::layer::{Layer, Mode};
+use crate::matrix::Matrix;
+use crate::tape::Cache;
+
+/// Applies an activation function to each sample, without parameters.
+pub struct ActivationLayer {
+    pub name: String,
+    pub size: usize,
+    pub activation: Box<dyn Activation>,
+}
+
+impl ActivationLayer {
+    pub fn new(config: &ConfigLayer) -> Result<Self, Error> {
+        Ok(Self {
+            name: config.name.clone(),
+            size: config.input_size,
+            activation: get_activation(&config.activation, HashMap::new())?,
+        })
+    }
+}
+
+impl Layer for ActivationLayer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn output_shape(&self) -> Vec<usize> {
+        vec![self.size]
+    }
+
+    fn check_inputs(&self, inputs: &Matrix) -> Result<(), Error> {
+        check_width(inputs, self.size)
+    }
+
+    fn forward(&self, inputs: &Matrix) -> Matrix {
+        let mut outputs = inputs.clone();
+        for i in 0..outputs.rows {
+            let activations = self.activation.function(inputs.row(i));
+            outputs.row_mut(i).copy_from_slice(&activations);
+        }
+        outputs
+    }
+
+    fn forward_cached(
+        &mut self,
+        inputs: &Matrix,
+        cache: &mut Cache,
+        _mode: Mode,
+        _rng: &mut dyn RngCore,
+    ) {
+        cache.inputs.clone_from(inputs);
+        cache.pre_activations.clone_from(inputs);
+        cache.outputs = self.forward(inputs);
+    }
+
+    fn backward(&mut self, cache: &Cache, output_grads: &Matrix) -> Matrix {
+        let mut errors = Matrix::zeros(output_grads.rows, output_grads.cols);
+        for i in 0..errors.rows {
+            let error = self
+                .activation
+                .backward(cache.pre_activations.row(i), output_grads.row(i));
+            errors.row_mut(i).copy_from_slice(&error);
+        }
+        errors
+    }
+}
@@ -0,0 +1,254 @@
+use rand::RngCore;
+
+use crate::config::ConfigLayer;
+use crate::error::Error;
+use crate::initialization::Initialization;
+use crate::layer::utils::check_width;
+use crate::layer::{Dense, Layer, Mode, Sublayers};
+use crate::matrix::Matrix;
+use crate::optimizer::Optimizer;
+use crate::tape::Cache;
+
+const QUERY: usize = 0;
+const KEY: usize = 1;
+const VALUE: usize = 2;
+const OUTPUT: usize = 3;
+
+/// Multi-head scaled dot-product self-attention over samples of `steps`
+/// vectors of `features` values. The queries, keys and values of each step
+/// are linear projections of its vector; each head attends with its own
+/// `features / heads` slice of them, and the concatenated heads go through
+/// an output projection.
+pub struct MultiHeadAttention {
+    pub name: String,
+    pub steps: usize,
+    pub features: usize,
+    pub heads: usize,
+    /// Steps only attend to themselves and the steps before them.
+    pub causal: bool,
+    /// Query, key, value and output projections, applied to each step.
+    pub projections: Sublayers,
+}
+
+impl MultiHeadAttention {
+    pub fn new(
+        config: &ConfigLayer,
+        initialization: &mut Box<dyn Initialization>,
+    ) -> Result<Self, Error> {
+        let features = config.input_size / config.steps;
+        let mut projection = |name: &str| -> Result<Box<dyn Layer>, Error> {
+            let config = ConfigLayer {
+                name: format!("{}.{}", config.name, name),
+                kind: "dense".to_string(),
+                input_size: features,
+                output_size: features,
+                activation: "linear".to_string(),
+                ..config.clone()
+            };
+            Ok(Box::new(Dense::new(&config, initialization)?))
+        };
+        let projections = vec![
+            projection("query")?,
+            projection("key")?,
+            projection("value")?,
+            projection("output")?,
+        ];
+
+        Ok(Self {
+            name: config.name.clone(),
+            steps: config.steps,
+            features,
+            heads: config.heads,
+            causal: config.causal,
+            projections: Sublayers::new(projections),
+        })
+    }
+
+    /// Columns of head `h` in the projections of a step.
+    fn head<'a>(&self, values: &'a [f64], h: usize) -> &'a [f64] {
+        let size = self.features / self.heads;
+        &values[h * size..(h + 1) * size]
+    }
+
+    /// Number of steps that step `t` attends to.
+    fn visible(&self, t: usize) -> usize {
+        if self.causal {
+            t + 1
+        } else {
+            self.steps
+        }
+    }
+
+    /// Attention weights of each sample, `heads × steps × steps` per row,
+    /// and the concatenated heads of each step, from the projections of each
+    /// step.
+    fn attend(&self, queries: &Matrix, keys: &Matrix, values: &Matrix) -> (Matrix, Matrix) {
+        let (steps, size) = (self.steps, self.features / self.heads);
+        let scale = 1.0 / (size as f64).sqrt();
+        let samples = queries.rows / steps;
+        let mut weights = Matrix::zeros(samples, self.heads * steps * steps);
+        let mut context = Matrix::zeros(queries.rows, self.features);
+        for n in 0..samples {
+            let step = |t: usize| n * steps + t;
+            for h in 0..self.heads {
+                for t in 0..steps {
+                    let query = self.head(queries.row(step(t)), h);
+                    let scores: Vec<f64> = (0..self.visible(t))
+                        .map(|s| dot(query, self.head(keys.row(step(s)), h)) * scale)
+                        .collect();
+                    let max = scores.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+                    let exps: Vec<f64> = scores.iter().map(|s| (s - max).exp()).collect();
+                    let sum: f64 = exps.iter().sum();
+
+                    let offset = (h * steps + t) * steps;
+                    let attention = &mut weights.row_mut(n)[offset..offset + steps];
+                    let output = &mut context.row_mut(step(t))[h * size..(h + 1) * size];
+                    for (s, e) in exps.iter().enumerate() {
+                        attention[s] = e / sum;
+                        output
+                            .iter_mut()
+                            .zip(self.head(values.row(step(s)), h))
+                            .for_each(|(o, v)| *o += attention[s] * v);
+                    }
+                }
+            }
+        }
+        (weights, context)
+    }
+}
+
+impl Layer for MultiHeadAttention {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn output_shape(&self) -> Vec<usize> {
+        vec![self.steps, self.features]
+    }
+
+    fn check_inputs(&self, inputs: &Matrix) -> Result<(), Error> {
+        check_width(inputs, self.steps * self.features)
+    }
+
+    fn forward(&self, inputs: &Matrix) -> Matrix {
+        let x = inputs.reshape(self.features);
+        let (_, context) = self.attend(
+            &self.projections.forward(QUERY, &x),
+            &self.projections.forward(KEY, &x),
+            &self.projections.forward(VALUE, &x),
+        );
+        let outputs = self.projections.forward(OUTPUT, &context);
+        outputs.reshape(inputs.cols)
+    }
+
+    /// Records the attention weights as the states, and the projections in
+    /// the children of the cache.
+    fn forward_cached(
+        &mut self,
+        inputs: &Matrix,
+        cache: &mut Cache,
+        mode: Mode,
+        rng: &mut dyn RngCore,
+    ) {
+        let x = inputs.reshape(self.features);
+        let p = &mut self.projections;
+        let queries = p.forward_cached(QUERY, &x, cache, mode, rng);
+        let keys = p.forward_cached(KEY, &x, cache, mode, rng);
+        let values = p.forward_cached(VALUE, &x, cache, mode, rng);
+        let (weights, context) = self.attend(&queries, &keys, &values);
+        let outputs = self
+            .projections
+            .forward_cached(OUTPUT, &context, cache, mode, rng);
+        cache.inputs.clone_from(inputs);
+        cache.outputs = outputs.reshape(inputs.cols);
+        cache.states = weights;
+    }
+
+    fn backward(&mut self, cache: &Cache, output_grads: &Matrix) -> Matrix {
+        let (steps, size) = (self.steps, self.features / self.heads);
+        let scale = 1.0 / (size as f64).sqrt();
+        let context_grads =
+            self.projections
+                .backward(OUTPUT, cache, &output_grads.reshape(self.features));
+        let (queries, keys, values) = (
+            &cache.children[QUERY].outputs,
+            &cache.children[KEY].outputs,
+            &cache.children[VALUE].outputs,
+        );
+        let mut query_grads = Matrix::zeros(queries.rows, self.features);
+        let mut key_grads = Matrix::zeros(queries.rows, self.features);
+        let mut value_grads = Matrix::zeros(queries.rows, self.features);
+        let columns = |h: usize| h * size..(h + 1) * size;
+        for n in 0..cache.states.rows {
+            let step = |t: usize| n * steps + t;
+            for h in 0..self.heads {
+                for t in 0..steps {
+                    let offset = (h * steps + t) * steps;
+                    let attention = &cache.states.row(n)[offset..offset + steps];
+                    let output_grad = self.head(context_grads.row(step(t)), h);
+                    let visible = self.visible(t);
+
+                    let weight_grads: Vec<f64> = (0..visible)
+                        .map(|s| dot(output_grad, self.head(values.row(step(s)), h)))
+                        .collect();
+                    let total = dot(&attention[..visible], &weight_grads);
+                    for s in 0..visible {
+                        value_grads.row_mut(step(s))[columns(h)]
+                            .iter_mut()
+                            .zip(output_grad)
+                            .for_each(|(g, d)| *g += attention[s] * d);
+
+                        let score_grad = attention[s] * (weight_grads[s] - total) * scale;
+                        query_grads.row_mut(step(t))[columns(h)]
+                            .iter_mut()
+                            .zip(self.head(keys.row(step(s)), h))
+                            .for_each(|(g, k)| *g += score_grad * k);
+                        key_grads.row_mut(step(s))[columns(h)]
+                            .iter_mut()
+                            .zip(self.head(queries.row(step(t)), h))
+                            .for_each(|(g, q)| *g += score_grad * q);
+                    }
+                }
+            }
+        }
+
+        let mut input_grads = self.projections.backward(QUERY, cache, &query_grads);
+        for (k, grads) in [(KEY, &key_grads), (VALUE, &value_grads)] {
+            let grads = self.projections.backward(k, cache, grads);
+            input_grads
+                .data
+                .iter_mut()
+                .zip(grads.data)
+                .for_each(|(g, d)| *g += d);
+        }
+        input_grads.reshape(output_grads.cols)
+    }
+
+    fn parameters(&self) -> Vec<&[f64]> {
+        self.projections.parameters()
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        self.projections.shapes()
+    }
+
+    fn gradients(&self) -> Vec<&[f64]> {
+        self.projections.gradients()
+    }
+
+    fn parameters_mut(&mut self) -> Vec<(&mut [f64], &mut [f64])> {
+        self.projections.parameters_mut()
+    }
+
+    fn penalty(&self) -> f64 {
+        self.projections.penalty()
+    }
+
+    fn update(&mut self, lr: f64, optimizer: &mut dyn Optimizer, id: usize, batch_size: usize) {
+        self.projections.update(lr, optimizer, id, batch_size);
+    }
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
+}
@@ -0,0 +1,86 @@
+use rand::RngCore;
+
+use crate::config::ConfigLayer;
+use crate::error::Error;
+use crate::layer::utils::check_width;
+use crate::layer::{Layer, Mode, Window};
+use crate::matrix::Matrix;
+use crate::tape::Cache;
+
+/// Mean of each window of each channel, laid out like `Conv1D`. Padding
+/// positions are not counted.
+pub struct AvgPool1D {
+    pub name: String,
+    pub channels: usize,
+    pub window: Window,
+}
+
+impl AvgPool1D {
+    pub fn new(config: &ConfigLayer) -> Self {
+        Self {
+            name: config.name.clone(),
+            channels: config.channels,
+            window: Window::new(config, config.input_size / config.channels),
+        }
+    }
+
+    /// Calls `f(output, input, weight)` for each position of each window of
+    /// a sample, where `weight` is one over the size of the window.
+    fn taps(&self, mut f: impl FnMut(usize, usize, f64)) {
+        let (length, outputs) = (self.window.size, self.window.outputs());
+        for c in 0..self.channels {
+            for o in 0..outputs {
+                let positions: Vec<usize> = (0..self.window.kernel)
+                    .filter_map(|k| self.window.position(o, k))
+                    .collect();
+                let weight = 1.0 / positions.len() as f64;
+                for p in positions {
+                    f(c * outputs + o, c * length + p, weight);
+                }
+            }
+        }
+    }
+}
+
+impl Layer for AvgPool1D {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn output_shape(&self) -> Vec<usize> {
+        vec![self.channels, self.window.outputs()]
+    }
+
+    fn check_inputs(&self, inputs: &Matrix) -> Result<(), Error> {
+        check_width(inputs, self.channels * self.window.size)
+    }
+
+    fn forward(&self, inputs: &Matrix) -> Matrix {
+        let mut outputs = Matrix::zeros(inputs.rows, self.channels * self.window.outputs());
+        for i in 0..inputs.rows {
+            let (x, y) = (inputs.row(i), outputs.row_mut(i));
+            self.taps(|o, p, weight| y[o] += weight * x[p]);
+        }
+        outputs
+    }
+
+    fn forward_cached(
+        &mut self,
+        inputs: &Matrix,
+        cache: &mut Cache,
+        _mode: Mode,
+        _rng: &mut dyn RngCore,
+    ) {
+        cache.inputs.clone_from(inputs);
+        cache.outputs = self.forward(inputs);
+    }
+
+    fn backward(&mut self, cache: &Cache, output_grads: &Matrix) -> Matrix {
+        let mut errors = Matrix::zeros(cache.inputs.rows, cache.inputs.cols);
+        for i in 0..output_grads.rows {
+            let (g, error) = (output_grads.row(i), errors.row_mut(i));
+            self.taps(|o, p, weight| error[p] += weight * g[o]);
+        }
+        errors
+    }
+}
@@ -0,0 +1,94 @@
+use rand::RngCore;
+
+use crate::config::ConfigLayer;
+use crate::error::Error;
+use crate::layer::utils::check_width;
+use crate::layer::{Im2Col, Layer, Mode, Window};
+use crate::matrix::Matrix;
+use crate::tape::Cache;
+
+/// Mean of each patch of each channel, laid out like `Conv2D`. Padding
+/// positions are not counted.
+pub struct AvgPool2D {
+    pub name: String,
+    pub channels: usize,
+    pub height: Window,
+    pub width: Window,
+    pub patches: Im2Col,
+}
+
+impl AvgPool2D {
+    pub fn new(config: &ConfigLayer) -> Self {
+        let (height, width) = (
+            Window::new(config, config.height),
+            Window::new(config, config.width),
+        );
+        Self {
+            name: config.name.clone(),
+            channels: config.channels,
+            patches: Im2Col::new(config.channels, &height, &width),
+            height,
+            width,
+        }
+    }
+
+    /// Calls `f(output, input, weight)` for each position of each patch of
+    /// a sample, where `weight` is one over the size of the patch.
+    fn taps(&self, mut f: impl FnMut(usize, usize, f64)) {
+        let (taps, positions) = (self.patches.taps(self.channels), self.patches.cols);
+        for c in 0..self.channels {
+            for o in 0..positions {
+                let indices: Vec<usize> = (c * taps..(c + 1) * taps)
+                    .filter_map(|row| self.patches.indices[row * positions + o])
+                    .collect();
+                let weight = 1.0 / indices.len() as f64;
+                for p in indices {
+                    f(c * positions + o, p, weight);
+                }
+            }
+        }
+    }
+}
+
+impl Layer for AvgPool2D {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn output_shape(&self) -> Vec<usize> {
+        vec![self.channels, self.height.outputs(), self.width.outputs()]
+    }
+
+    fn check_inputs(&self, inputs: &Matrix) -> Result<(), Error> {
+        check_width(inputs, self.channels * self.height.size * self.width.size)
+    }
+
+    fn forward(&self, inputs: &Matrix) -> Matrix {
+        let mut outputs = Matrix::zeros(inputs.rows, self.channels * self.patches.cols);
+        for i in 0..inputs.rows {
+            let (x, y) = (inputs.row(i), outputs.row_mut(i));
+            self.taps(|o, p, weight| y[o] += weight * x[p]);
+        }
+        outputs
+    }
+
+    fn forward_cached(
+        &mut self,
+        inputs: &Matrix,
+        cache: &mut Cache,
+        _mode: Mode,
+        _rng: &mut dyn RngCore,
+    ) {
+        cache.inputs.clone_from(inputs);
+        cache.outputs = self.forward(inputs);
+    }
+
+    fn backward(&mut self, cache: &Cache, output_grads: &Matrix) -> Matrix {
+        let mut errors = Matrix::zeros(cache.inputs.rows, cache.inputs.cols);
+        for i in 0..output_grads.rows {
+            let (g, error) = (output_grads.row(i), errors.row_mut(i));
+            self.taps(|o, p, weight| error[p] += weight * g[o]);
+        }
+        errors
+    }
+}
@@ -0,0 +1,203 @@
+use rand::RngCore;
+
+use crate::config::ConfigLayer;
+use crate::error::Error;
+use crate::layer::utils::check_width;
+use crate::layer::{Layer, Mode};
+use crate::matrix::Matrix;
+use crate::tape::Cache;
+
+/// Batch normalization: normalizes each feature with the statistics of the
+/// batch in `Train` mode and with running statistics in `Eval` mode, then
+/// scales by `gamma` and shifts by `beta`.
+pub struct BatchNorm {
+    pub name: String,
+    pub size: usize,
+    pub gamma: Vec<f64>,
+    pub beta: Vec<f64>,
+    pub gamma_grads: Vec<f64>,
+    pub beta_grads: Vec<f64>,
+    pub running_mean: Vec<f64>,
+    pub running_var: Vec<f64>,
+    /// Weight of the current batch in the running statistics.
+    pub momentum: f64,
+    pub epsilon: f64,
+}
+
+impl BatchNorm {
+    pub fn new(config: &ConfigLayer) -> Self {
+        let size = config.input_size;
+        Self {
+            name: config.name.clone(),
+            size,
+            gamma: vec![1.0; size],
+            beta: vec![0.0; size],
+            gamma_grads: vec![0.0; size],
+            beta_grads: vec![0.0; size],
+            running_mean: vec![0.0; size],
+            running_var: vec![1.0; size],
+            momentum: config.momentum,
+            epsilon: config.epsilon,
+        }
+    }
+
+    /// Per-feature mean and biased variance of the batch.
+    fn statistics(inputs: &Matrix) -> (Vec<f64>, Vec<f64>) {
+        let n = inputs.rows as f64;
+        let mut mean = vec![0.0; inputs.cols];
+        let mut var = vec![0.0; inputs.cols];
+        for i in 0..inputs.rows {
+            mean.iter_mut()
+                .zip(inputs.row(i))
+                .for_each(|(m, x)| *m += x / n);
+        }
+        for i in 0..inputs.rows {
+            var.iter_mut()
+                .zip(inputs.row(i).iter().zip(mean.iter()))
+                .for_each(|(v, (x, m))| *v += (x - m).powi(2) / n);
+        }
+        (mean, var)
+    }
+
+    /// Normalizes `inputs` and returns them with the per-feature inverse
+    /// standard deviation.
+    fn normalize(&self, inputs: &Matrix, mean: &[f64], var: &[f64]) -> (Matrix, Vec<f64>) {
+        let inv_std: Vec<f64> = var
+            .iter()
+            .map(|v| 1.0 / (v + self.epsilon).sqrt())
+            .collect();
+        let mut normalized = inputs.clone();
+        for i in 0..normalized.rows {
+            normalized
+                .row_mut(i)
+                .iter_mut()
+                .zip(mean.iter().zip(inv_std.iter()))
+                .for_each(|(x, (m, s))| *x = (*x - m) * s);
+        }
+        (normalized, inv_std)
+    }
+
+    fn scale(&self, normalized: &Matrix) -> Matrix {
+        let mut outputs = normalized.clone();
+        for i in 0..outputs.rows {
+            outputs
+                .row_mut(i)
+                .iter_mut()
+                .zip(self.gamma.iter().zip(self.beta.iter()))
+                .for_each(|(x, (g, b))| *x = *x * g + b);
+        }
+        outputs
+    }
+}
+
+impl Layer for BatchNorm {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn output_shape(&self) -> Vec<usize> {
+        vec![self.size]
+    }
+
+    fn check_inputs(&self, inputs: &Matrix) -> Result<(), Error> {
+        check_width(inputs, self.size)
+    }
+
+    fn forward(&self, inputs: &Matrix) -> Matrix {
+        let (normalized, _) = self.normalize(inputs, &self.running_mean, &self.running_var);
+        self.scale(&normalized)
+    }
+
+    /// Records the normalized inputs as the pre-activations and the inverse
+    /// standard deviations as the statistics.
+    fn forward_cached(
+        &mut self,
+        inputs: &Matrix,
+        cache: &mut Cache,
+        mode: Mode,
+        _rng: &mut dyn RngCore,
+    ) {
+        let (normalized, inv_std) = match mode {
+            Mode::Train => {
+                let (mean, var) = Self::statistics(inputs);
+                let n = inputs.rows as f64;
+                let unbiased = if inputs.rows > 1 { n / (n - 1.0) } else { 1.0 };
+                let momentum = self.momentum;
+                self.running_mean
+                    .iter_mut()
+                    .zip(mean.iter())
+                    .for_each(|(r, m)| *r = (1.0 - momentum) * *r + momentum * m);
+                self.running_var
+                    .iter_mut()
+                    .zip(var.iter())
+                    .for_each(|(r, v)| *r = (1.0 - momentum) * *r + momentum * v * unbiased);
+                self.normalize(inputs, &mean, &var)
+            }
+            Mode::Eval => self.normalize(inputs, &self.running_mean, &self.running_var),
+        };
+        cache.inputs.clone_from(inputs);
+        cache.outputs = self.scale(&normalized);
+        cache.pre_activations = normalized;
+        cache.statistics = inv_std;
+        cache.mode = mode;
+    }
+
+    fn backward(&mut self, cache: &Cache, output_grads: &Matrix) -> Matrix {
+        let normalized = &cache.pre_activations;
+        let n = output_grads.rows as f64;
+        let mut errors = Matrix::zeros(output_grads.rows, output_grads.cols);
+        let mut sum = vec![0.0; output_grads.cols];
+        let mut dot = vec![0.0; output_grads.cols];
+        for i in 0..output_grads.rows {
+            for (j, (dy, x)) in output_grads
+                .row(i)
+                .iter()
+                .zip(normalized.row(i))
+                .enumerate()
+            {
+                let dx = dy * self.gamma[j];
+                self.gamma_grads[j] += dy * x;
+                self.beta_grads[j] += dy;
+                errors.row_mut(i)[j] = dx;
+                sum[j] += dx;
+                dot[j] += dx * x;
+            }
+        }
+
+        // in eval mode the statistics do not depend on the batch
+        for i in 0..errors.rows {
+            let x = normalized.row(i).to_vec();
+            for (j, e) in errors.row_mut(i).iter_mut().enumerate() {
+                let inv_std = cache.statistics[j];
+                *e = match cache.mode {
+                    Mode::Train => inv_std / n * (n * *e - sum[j] - x[j] * dot[j]),
+                    Mode::Eval => *e * inv_std,
+                };
+            }
+        }
+        errors
+    }
+
+    fn parameters(&self) -> Vec<&[f64]> {
+        vec![&self.gamma, &self.beta]
+    }
+
+    fn gradients(&self) -> Vec<&[f64]> {
+        vec![&self.gamma_grads, &self.beta_grads]
+    }
+
+    fn parameters_mut(&mut self) -> Vec<(&mut [f64], &mut [f64])> {
+        vec![
+            (&mut self.gamma, &mut self.gamma_grads),
+            (&mut self.beta, &mut self.beta_grads),
+        ]
+    }
+
+    fn buffers(&self) -> Vec<&[f64]> {
+        vec![&self.running_mean, &self.running_var]
+    }
+
+    fn buffers_mut(&mut self) -> Vec<&mut [f64]> {
+        vec![&mut self.running_mean, &mut self.running_var]
+    }
+}
@@ -0,0 +1,197 @@
+use std::collections::HashMap;
+
+use rand::RngCore;
+
+use crate::activation::{get_activation, Activation};
+use crate::config::ConfigLayer;
+use crate::error::Error;
+use crate::initialization::Initialization;
+use crate::layer::utils::check_width;
+use crate::layer::{Layer, Mode, Window};
+use crate::matrix::Matrix;
+use crate::optimizer::Optimizer;
+use crate::regularization::Regularization;
+use crate::tape::Cache;
+
+/// 1D convolution over `channels` signals, producing one signal per filter.
+/// Samples are stored channel by channel: value `t` of channel `c` is at
+/// `c * length + t`.
+pub struct Conv1D {
+    pub name: String,
+    pub channels: usize,
+    pub window: Window,
+    /// Row `f` holds the kernel of filter `f`, channel by channel.
+    pub weights: Matrix,
+    pub biases: Vec<f64>,
+    pub weight_grads: Matrix,
+    pub bias_grads: Vec<f64>,
+    pub activation: Box<dyn Activation>,
+    pub regularization: Regularization,
+}
+
+impl Conv1D {
+    pub fn new(
+        config: &ConfigLayer,
+        initialization: &mut Box<dyn Initialization>,
+    ) -> Result<Self, Error> {
+        let columns = config.channels * config.kernel_size;
+        let mut weights = Matrix::zeros(config.filters, columns);
+        let mut biases = vec![0.0; config.filters];
+        let (fan_in, fan_out) = (columns, config.filters * config.kernel_size);
+        for (f, bias) in biases.iter_mut().enumerate() {
+            *bias = initialization.bias(fan_in, fan_out);
+            weights
+                .row_mut(f)
+                .iter_mut()
+                .for_each(|w| *w = initialization.init(fan_in, fan_out));
+        }
+
+        Ok(Self {
+            name: config.name.clone(),
+            channels: config.channels,
+            window: Window::new(config, config.input_size / config.channels),
+            activation: get_activation(&config.activation, HashMap::new())?,
+            regularization: Regularization::new(config),
+            weight_grads: Matrix::zeros(config.filters, columns),
+            bias_grads: vec![0.0; config.filters],
+            weights,
+            biases,
+        })
+    }
+
+    /// Calls `f(output, input, weight)` for each tap of each output of a
+    /// sample, with indices into the sample, its outputs and the weights.
+    fn taps(&self, mut f: impl FnMut(usize, usize, usize)) {
+        let (length, outputs) = (self.window.size, self.window.outputs());
+        let kernel = self.window.kernel;
+        for filter in 0..self.weights.rows {
+            for o in 0..outputs {
+                for c in 0..self.channels {
+                    for k in 0..kernel {
+                        if let Some(p) = self.window.position(o, k) {
+                            f(
+                                filter * outputs + o,
+                                c * length + p,
+                                filter * self.weights.cols + c * kernel + k,
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn pre_activations(&self, inputs: &Matrix) -> Matrix {
+        let outputs = self.window.outputs();
+        let mut pre_activations = Matrix::zeros(inputs.rows, self.weights.rows * outputs);
+        for i in 0..inputs.rows {
+            let (x, z) = (inputs.row(i), pre_activations.row_mut(i));
+            for (filter, bias) in self.biases.iter().enumerate() {
+                z[filter * outputs..(filter + 1) * outputs].fill(*bias);
+            }
+            self.taps(|o, p, w| z[o] += self.weights.data[w] * x[p]);
+        }
+        pre_activations
+    }
+}
+
+impl Layer for Conv1D {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn output_shape(&self) -> Vec<usize> {
+        vec![self.weights.rows, self.window.outputs()]
+    }
+
+    fn check_inputs(&self, inputs: &Matrix) -> Result<(), Error> {
+        check_width(inputs, self.channels * self.window.size)
+    }
+
+    fn forward(&self, inputs: &Matrix) -> Matrix {
+        let mut outputs = self.pre_activations(inputs);
+        for i in 0..outputs.rows {
+            let row = outputs.row_mut(i);
+            let activations = self.activation.function(row);
+            row.copy_from_slice(&activations);
+        }
+        outputs
+    }
+
+    fn forward_cached(
+        &mut self,
+        inputs: &Matrix,
+        cache: &mut Cache,
+        _mode: Mode,
+        _rng: &mut dyn RngCore,
+    ) {
+        cache.inputs.clone_from(inputs);
+        cache.pre_activations = self.pre_activations(inputs);
+        cache.outputs.clone_from(&cache.pre_activations);
+        for i in 0..cache.outputs.rows {
+            let activations = self.activation.function(cache.pre_activations.row(i));
+            cache.outputs.row_mut(i).copy_from_slice(&activations);
+        }
+    }
+
+    fn backward(&mut self, cache: &Cache, output_grads: &Matrix) -> Matrix {
+        let outputs = self.window.outputs();
+        let mut input_grads = Matrix::zeros(cache.inputs.rows, cache.inputs.cols);
+        let mut weight_grads = std::mem::take(&mut self.weight_grads);
+        for i in 0..output_grads.rows {
+            let error = self
+                .activation
+                .backward(cache.pre_activations.row(i), output_grads.row(i));
+            for (filter, g) in self.bias_grads.iter_mut().enumerate() {
+                *g += error[filter * outputs..(filter + 1) * outputs]
+                    .iter()
+                    .sum::<f64>();
+            }
+            let (x, dx) = (cache.inputs.row(i), input_grads.row_mut(i));
+            self.taps(|o, p, w| {
+                weight_grads.data[w] += error[o] * x[p];
+                dx[p] += error[o] * self.weights.data[w];
+            });
+        }
+        self.weight_grads = weight_grads;
+        input_grads
+    }
+
+    fn parameters(&self) -> Vec<&[f64]> {
+        vec![&self.weights.data, &self.biases]
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        vec![
+            vec![self.weights.rows, self.weights.cols],
+            vec![self.biases.len()],
+        ]
+    }
+
+    fn gradients(&self) -> Vec<&[f64]> {
+        vec![&self.weight_grads.data, &self.bias_grads]
+    }
+
+    fn parameters_mut(&mut self) -> Vec<(&mut [f64], &mut [f64])> {
+        vec![
+            (&mut self.weights.data, &mut self.weight_grads.data),
+            (&mut self.biases, &mut self.bias_grads),
+        ]
+    }
+
+    fn penalty(&self) -> f64 {
+        self.regularization
+            .layer_penalty(&self.weights.data, &self.biases)
+    }
+
+    fn update(&mut self, lr: f64, optimizer: &mut dyn Optimizer, id: usize, batch_size: usize) {
+        self.regularization.update(
+            lr,
+            optimizer,
+            id,
+            batch_size,
+            (&mut self.weights.data, &mut self.weight_grads.data),
+            (&mut self.biases, &mut self.bias_grads),
+        );
+    }
+}
@@ -0,0 +1,196 @@
+use std::collections::HashMap;
+
+use rand::RngCore;
+
+use crate::activation::{get_activation, Activation};
+use crate::config::ConfigLayer;
+use crate::error::Error;
+use crate::initialization::Initialization;
+use crate::layer::utils::check_width;
+use crate::layer::{Im2Col, Layer, Mode, Window};
+use crate::matrix::Matrix;
+use crate::optimizer::Optimizer;
+use crate::regularization::Regularization;
+use crate::tape::Cache;
+
+/// 2D convolution over `channels` images, producing one image per filter.
+/// Samples are stored channel by channel, each image row by row. Each sample
+/// is unfolded with `Im2Col` so that the convolution is a single product with
+/// the weights.
+pub struct Conv2D {
+    pub name: String,
+    pub channels: usize,
+    pub height: Window,
+    pub width: Window,
+    pub patches: Im2Col,
+    /// Row `f` holds the kernel of filter `f`, in the order of the patch rows.
+    pub weights: Matrix,
+    pub biases: Vec<f64>,
+    pub weight_grads: Matrix,
+    pub bias_grads: Vec<f64>,
+    pub activation: Box<dyn Activation>,
+    pub regularization: Regularization,
+}
+
+impl Conv2D {
+    pub fn new(
+        config: &ConfigLayer,
+        initialization: &mut Box<dyn Initialization>,
+    ) -> Result<Self, Error> {
+        let (height, width) = (
+            Window::new(config, config.height),
+            Window::new(config, config.width),
+        );
+        let patches = Im2Col::new(config.channels, &height, &width);
+        let taps = config.kernel_size * config.kernel_size;
+        let mut weights = Matrix::zeros(config.filters, patches.rows);
+        let mut biases = vec![0.0; config.filters];
+        let (fan_in, fan_out) = (config.channels * taps, config.filters * taps);
+        for (f, bias) in biases.iter_mut().enumerate() {
+            *bias = initialization.bias(fan_in, fan_out);
+            weights
+                .row_mut(f)
+                .iter_mut()
+                .for_each(|w| *w = initialization.init(fan_in, fan_out));
+        }
+
+        Ok(Self {
+            name: config.name.clone(),
+            activation: get_activation(&config.activation, HashMap::new())?,
+            regularization: Regularization::new(config),
+            weight_grads: Matrix::zeros(config.filters, patches.rows),
+            bias_grads: vec![0.0; config.filters],
+            channels: config.channels,
+            height,
+            width,
+            patches,
+            weights,
+            biases,
+        })
+    }
+
+    pub fn pre_activations(&self, inputs: &Matrix) -> Matrix {
+        let mut pre_activations = Matrix::zeros(inputs.rows, self.weights.rows * self.patches.cols);
+        for i in 0..inputs.rows {
+            let z = self.weights.matmul(&self.patches.gather(inputs.row(i)));
+            pre_activations.row_mut(i).copy_from_slice(&z.data);
+            for (f, bias) in self.biases.iter().enumerate() {
+                pre_activations.row_mut(i)[f * z.cols..(f + 1) * z.cols]
+                    .iter_mut()
+                    .for_each(|v| *v += bias);
+            }
+        }
+        pre_activations
+    }
+}
+
+impl Layer for Conv2D {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn output_shape(&self) -> Vec<usize> {
+        vec![
+            self.weights.rows,
+            self.height.outputs(),
+            self.width.outputs(),
+        ]
+    }
+
+    fn check_inputs(&self, inputs: &Matrix) -> Result<(), Error> {
+        check_width(inputs, self.channels * self.height.size * self.width.size)
+    }
+
+    fn forward(&self, inputs: &Matrix) -> Matrix {
+        let mut outputs = self.pre_activations(inputs);
+        for i in 0..outputs.rows {
+            let row = outputs.row_mut(i);
+            let activations = self.activation.function(row);
+            row.copy_from_slice(&activations);
+        }
+        outputs
+    }
+
+    fn forward_cached(
+        &mut self,
+        inputs: &Matrix,
+        cache: &mut Cache,
+        _mode: Mode,
+        _rng: &mut dyn RngCore,
+    ) {
+        cache.inputs.clone_from(inputs);
+        cache.pre_activations = self.pre_activations(inputs);
+        cache.outputs.clone_from(&cache.pre_activations);
+        for i in 0..cache.outputs.rows {
+            let activations = self.activation.function(cache.pre_activations.row(i));
+            cache.outputs.row_mut(i).copy_from_slice(&activations);
+        }
+    }
+
+    fn backward(&mut self, cache: &Cache, output_grads: &Matrix) -> Matrix {
+        let positions = self.patches.cols;
+        let mut input_grads = Matrix::zeros(cache.inputs.rows, cache.inputs.cols);
+        for i in 0..output_grads.rows {
+            let error = Matrix {
+                rows: self.weights.rows,
+                cols: positions,
+                data: self
+                    .activation
+                    .backward(cache.pre_activations.row(i), output_grads.row(i)),
+            };
+            for (f, g) in self.bias_grads.iter_mut().enumerate() {
+                *g += error.row(f).iter().sum::<f64>();
+            }
+            let columns = self.patches.gather(cache.inputs.row(i));
+            let weight_grads = error.matmul_transpose(&columns);
+            self.weight_grads
+                .data
+                .iter_mut()
+                .zip(weight_grads.data.iter())
+                .for_each(|(g, d)| *g += d);
+
+            let mut column_grads = Matrix::zeros(self.patches.rows, positions);
+            column_grads.add_transpose_matmul(&self.weights, &error);
+            self.patches.scatter(&column_grads, input_grads.row_mut(i));
+        }
+        input_grads
+    }
+
+    fn parameters(&self) -> Vec<&[f64]> {
+        vec![&self.weights.data, &self.biases]
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        vec![
+            vec![self.weights.rows, self.weights.cols],
+            vec![self.biases.len()],
+        ]
+    }
+
+    fn gradients(&self) -> Vec<&[f64]> {
+        vec![&self.weight_grads.data, &self.bias_grads]
+    }
+
+    fn parameters_mut(&mut self) -> Vec<(&mut [f64], &mut [f64])> {
+        vec![
+            (&mut self.weights.data, &mut self.weight_grads.data),
+            (&mut self.biases, &mut self.bias_grads),
+        ]
+    }
+
+    fn penalty(&self) -> f64 {
+        self.regularization
+            .layer_penalty(&self.weights.data, &self.biases)
+    }
+
+    fn update(&mut self, lr: f64, optimizer: &mut dyn Optimizer, id: usize, batch_size: usize) {
+        self.regularization.update(
+            lr,
+            optimizer,
+            id,
+            batch_size,
+            (&mut self.weights.data, &mut self.weight_grads.data),
+            (&mut self.biases, &mut self.bias_grads),
+        );
+    }
+}
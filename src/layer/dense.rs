@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+
+use rand::RngCore;
+
+use crate::activation::{get_activation, Activation};
+use crate::config::ConfigLayer;
+use crate::error::Error;
+use crate::initialization::Initialization;
+use crate::layer::utils::check_width;
+use crate::layer::{Layer, Mode};
+use crate::matrix::Matrix;
+use crate::neuron::Neuron;
+use crate::optimizer::Optimizer;
+use crate::regularization::Regularization;
+use crate::tape::Cache;
+
+/// Fully connected layer, `activation(weights · x + biases)`.
+pub struct Dense {
+    pub name: String,
+    /// Row `j` holds the weights of neuron `j`.
+    pub weights: Matrix,
+    pub biases: Vec<f64>,
+    pub weight_grads: Matrix,
+    pub bias_grads: Vec<f64>,
+    pub activation: Box<dyn Activation>,
+    pub regularization: Regularization,
+}
+
+impl Dense {
+    pub fn new(
+        config: &ConfigLayer,
+        initialization: &mut Box<dyn Initialization>,
+    ) -> Result<Self, Error> {
+        let mut weights = Matrix::zeros(config.output_size, config.input_size);
+        let mut biases = vec![0.0; config.output_size];
+        let (fan_in, fan_out) = (config.input_size, config.output_size);
+        for (j, bias) in biases.iter_mut().enumerate() {
+            *bias = initialization.bias(fan_in, fan_out);
+            weights
+                .row_mut(j)
+                .iter_mut()
+                .for_each(|w| *w = initialization.init(fan_in, fan_out));
+        }
+
+        Ok(Self {
+            name: config.name.clone(),
+            activation: get_activation(&config.activation, HashMap::new())?,
+            regularization: Regularization::new(config),
+            weight_grads: Matrix::zeros(config.output_size, config.input_size),
+            bias_grads: vec![0.0; config.output_size],
+            weights,
+            biases,
+        })
+    }
+
+    /// Read-only per-neuron copy of the weights and biases.
+    pub fn neurons(&self) -> Vec<Neuron> {
+        self.biases
+            .iter()
+            .enumerate()
+            .map(|(j, bias)| Neuron {
+                bias: *bias,
+                weights: self.weights.row(j).to_vec(),
+            })
+            .collect()
+    }
+
+    pub fn pre_activations(&self, inputs: &Matrix) -> Matrix {
+        let mut outputs = inputs.matmul_transpose(&self.weights);
+        for i in 0..outputs.rows {
+            outputs
+                .row_mut(i)
+                .iter_mut()
+                .zip(self.biases.iter())
+                .for_each(|(o, b)| *o += b);
+        }
+        outputs
+    }
+}
+
+impl Layer for Dense {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn output_shape(&self) -> Vec<usize> {
+        vec![self.weights.rows]
+    }
+
+    fn as_dense(&self) -> Option<&Dense> {
+        Some(self)
+    }
+
+    fn check_inputs(&self, inputs: &Matrix) -> Result<(), Error> {
+        check_width(inputs, self.weights.cols)
+    }
+
+    fn forward(&self, inputs: &Matrix) -> Matrix {
+        let mut outputs = self.pre_activations(inputs);
+        for i in 0..outputs.rows {
+            let row = outputs.row_mut(i);
+            let activations = self.activation.function(row);
+            row.copy_from_slice(&activations);
+        }
+        outputs
+    }
+
+    fn forward_cached(
+        &mut self,
+        inputs: &Matrix,
+        cache: &mut Cache,
+        _mode: Mode,
+        _rng: &mut dyn RngCore,
+    ) {
+        cache.inputs.clone_from(inputs);
+        cache.pre_activations = self.pre_activations(inputs);
+        cache.outputs.clone_from(&cache.pre_activations);
+        for i in 0..cache.outputs.rows {
+            let activations = self.activation.function(cache.pre_activations.row(i));
+            cache.outputs.row_mut(i).copy_from_slice(&activations);
+        }
+    }
+
+    fn backward(&mut self, cache: &Cache, output_grads: &Matrix) -> Matrix {
+        let mut errors = Matrix::zeros(output_grads.rows, output_grads.cols);
+        for i in 0..errors.rows {
+            let error = self
+                .activation
+                .backward(cache.pre_activations.row(i), output_grads.row(i));
+            errors.row_mut(i).copy_from_slice(&error);
+            self.bias_grads
+                .iter_mut()
+                .zip(error.iter())
+                .for_each(|(g, e)| *g += e);
+        }
+        self.weight_grads
+            .add_transpose_matmul(&errors, &cache.inputs);
+
+        errors.matmul(&self.weights)
+    }
+
+    fn parameters(&self) -> Vec<&[f64]> {
+        vec![&self.weights.data, &self.biases]
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        vec![
+            vec![self.weights.rows, self.weights.cols],
+            vec![self.biases.len()],
+        ]
+    }
+
+    fn gradients(&self) -> Vec<&[f64]> {
+        vec![&self.weight_grads.data, &self.bias_grads]
+    }
+
+    fn parameters_mut(&mut self) -> Vec<(&mut [f64], &mut [f64])> {
+        vec![
+            (&mut self.weights.data, &mut self.weight_grads.data),
+            (&mut self.biases, &mut self.bias_grads),
+        ]
+    }
+
+    fn penalty(&self) -> f64 {
+        self.regularization
+            .layer_penalty(&self.weights.data, &self.biases)
+    }
+
+    fn update(&mut self, lr: f64, optimizer: &mut dyn Optimizer, id: usize, batch_size: usize) {
+        self.regularization.update(
+            lr,
+            optimizer,
+            id,
+            batch_size,
+            (&mut self.weights.data, &mut self.weight_grads.data),
+            (&mut self.biases, &mut self.bias_grads),
+        );
+    }
+}
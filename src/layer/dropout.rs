@@ -0,0 +1,80 @@
+use rand::{Rng, RngCore};
+
+use crate::config::ConfigLayer;
+use crate::error::Error;
+use crate::layer::utils::check_width;
+use crate::layer::{Layer, Mode};
+use crate::matrix::Matrix;
+use crate::tape::Cache;
+
+/// Inverted dropout: zeroes each input with probability `rate` and scales
+/// the others by `1 / (1 - rate)` in `Train` mode.
+pub struct Dropout {
+    pub name: String,
+    pub size: usize,
+    pub rate: f64,
+}
+
+impl Dropout {
+    pub fn new(config: &ConfigLayer) -> Self {
+        Self {
+            name: config.name.clone(),
+            size: config.input_size,
+            rate: config.rate,
+        }
+    }
+}
+
+impl Layer for Dropout {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn output_shape(&self) -> Vec<usize> {
+        vec![self.size]
+    }
+
+    fn check_inputs(&self, inputs: &Matrix) -> Result<(), Error> {
+        check_width(inputs, self.size)
+    }
+
+    fn forward(&self, inputs: &Matrix) -> Matrix {
+        inputs.clone()
+    }
+
+    /// Records the scaled mask as the pre-activations.
+    fn forward_cached(
+        &mut self,
+        inputs: &Matrix,
+        cache: &mut Cache,
+        mode: Mode,
+        rng: &mut dyn RngCore,
+    ) {
+        cache.inputs.clone_from(inputs);
+        cache.pre_activations = Matrix::zeros(inputs.rows, inputs.cols);
+        cache.pre_activations.data.iter_mut().for_each(|m| {
+            *m = match mode {
+                Mode::Train if rng.gen::<f64>() < self.rate => 0.0,
+                Mode::Train => 1.0 / (1.0 - self.rate),
+                Mode::Eval => 1.0,
+            }
+        });
+        cache.outputs.clone_from(inputs);
+        cache
+            .outputs
+            .data
+            .iter_mut()
+            .zip(cache.pre_activations.data.iter())
+            .for_each(|(o, m)| *o *= m);
+    }
+
+    fn backward(&mut self, cache: &Cache, output_grads: &Matrix) -> Matrix {
+        let mut errors = output_grads.clone();
+        errors
+            .data
+            .iter_mut()
+            .zip(cache.pre_activations.data.iter())
+            .for_each(|(e, m)| *e *= m);
+        errors
+    }
+}
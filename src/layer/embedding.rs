@@ -0,0 +1,190 @@
+use rand::RngCore;
+
+use crate::config::ConfigLayer;
+use crate::data::read_embeddings;
+use crate::error::Error;
+use crate::initialization::Initialization;
+use crate::layer::utils::check_width;
+use crate::layer::{Layer, Mode};
+use crate::matrix::Matrix;
+use crate::optimizer::Optimizer;
+use crate::regularization::Regularization;
+use crate::tape::Cache;
+
+/// Lookup table of one learnable vector per token. Samples hold `tokens`
+/// indices stored as floats; the outputs are their vectors, one after the
+/// other. Only the rows of the tokens seen since the last update are
+/// updated, each as its own optimizer group.
+pub struct Embedding {
+    pub name: String,
+    pub tokens: usize,
+    /// Row `v` holds the vector of token `v`.
+    pub weights: Matrix,
+    pub weight_grads: Matrix,
+    /// Token embedded as zeros, whose row is never updated.
+    pub padding_index: Option<usize>,
+    /// Rows with gradients since the last update.
+    pub touched: Vec<bool>,
+    pub regularization: Regularization,
+}
+
+impl Embedding {
+    /// Reads the weights from `config.embeddings` if set, which must hold
+    /// `vocab_size` rows of `embedding_size` values.
+    pub fn new(
+        config: &ConfigLayer,
+        initialization: &mut Box<dyn Initialization>,
+    ) -> Result<Self, Error> {
+        let (vocab, size) = (config.vocab_size, config.embedding_size);
+        let mut weights = match &config.embeddings {
+            Some(path) => {
+                let weights = read_embeddings(path, config.embedding_words)?;
+                if weights.rows != vocab || weights.cols != size {
+                    return Err(Error::ShapeMismatch {
+                        expected: vocab * size,
+                        actual: weights.rows * weights.cols,
+                    });
+                }
+                weights
+            }
+            None => {
+                let mut weights = Matrix::zeros(vocab, size);
+                weights
+                    .data
+                    .iter_mut()
+                    .for_each(|w| *w = initialization.init(vocab, size));
+                weights
+            }
+        };
+        if let Some(padding) = config.padding_index {
+            weights.row_mut(padding).fill(0.0);
+        }
+
+        Ok(Self {
+            name: config.name.clone(),
+            tokens: config.input_size,
+            weight_grads: Matrix::zeros(vocab, size),
+            padding_index: config.padding_index,
+            touched: vec![false; vocab],
+            regularization: Regularization::new(config),
+            weights,
+        })
+    }
+
+    /// Row of the token at position `t` of a sample, `None` for padding.
+    /// The token must be in the vocabulary, see `check_inputs`.
+    fn token(&self, sample: &[f64], t: usize) -> Option<usize> {
+        Some(sample[t].round() as usize).filter(|v| Some(*v) != self.padding_index)
+    }
+}
+
+impl Layer for Embedding {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn output_shape(&self) -> Vec<usize> {
+        vec![self.tokens, self.weights.cols]
+    }
+
+    fn check_inputs(&self, inputs: &Matrix) -> Result<(), Error> {
+        check_width(inputs, self.tokens)?;
+        let vocab = self.weights.rows;
+        match inputs.data.iter().find(|t| {
+            let token = t.round();
+            !(token >= 0.0 && (token as usize) < vocab)
+        }) {
+            Some(token) => Err(Error::Validation(format!(
+                "token {} out of the vocabulary of {}",
+                token, vocab
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// # Panics
+    /// If a token is not in the vocabulary.
+    fn forward(&self, inputs: &Matrix) -> Matrix {
+        self.check_inputs(inputs)
+            .unwrap_or_else(|e| panic!("{}", e));
+        let size = self.weights.cols;
+        let mut outputs = Matrix::zeros(inputs.rows, self.tokens * size);
+        for i in 0..inputs.rows {
+            for t in 0..self.tokens {
+                if let Some(v) = self.token(inputs.row(i), t) {
+                    outputs.row_mut(i)[t * size..(t + 1) * size]
+                        .copy_from_slice(self.weights.row(v));
+                }
+            }
+        }
+        outputs
+    }
+
+    fn forward_cached(
+        &mut self,
+        inputs: &Matrix,
+        cache: &mut Cache,
+        _mode: Mode,
+        _rng: &mut dyn RngCore,
+    ) {
+        cache.inputs.clone_from(inputs);
+        cache.outputs = self.forward(inputs);
+    }
+
+    /// Accumulates the gradients of the rows looked up. Token indices have
+    /// no gradient, so the input gradients are zero.
+    fn backward(&mut self, cache: &Cache, output_grads: &Matrix) -> Matrix {
+        let size = self.weights.cols;
+        for i in 0..cache.inputs.rows {
+            for t in 0..self.tokens {
+                if let Some(v) = self.token(cache.inputs.row(i), t) {
+                    self.touched[v] = true;
+                    self.weight_grads
+                        .row_mut(v)
+                        .iter_mut()
+                        .zip(&output_grads.row(i)[t * size..(t + 1) * size])
+                        .for_each(|(g, d)| *g += d);
+                }
+            }
+        }
+        Matrix::zeros(cache.inputs.rows, cache.inputs.cols)
+    }
+
+    fn parameters(&self) -> Vec<&[f64]> {
+        vec![&self.weights.data]
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        vec![vec![self.weights.rows, self.weights.cols]]
+    }
+
+    fn gradients(&self) -> Vec<&[f64]> {
+        vec![&self.weight_grads.data]
+    }
+
+    fn parameters_mut(&mut self) -> Vec<(&mut [f64], &mut [f64])> {
+        vec![(&mut self.weights.data, &mut self.weight_grads.data)]
+    }
+
+    fn penalty(&self) -> f64 {
+        self.regularization.penalty(&self.weights.data)
+    }
+
+    /// Sparse update: each touched row is stepped as group `row` of the
+    /// layer, so that optimizer state such as momentum only advances for
+    /// rows that were seen. Penalties and decay also only apply to them.
+    fn update(&mut self, lr: f64, optimizer: &mut dyn Optimizer, id: usize, batch_size: usize) {
+        let scale = 1.0 / batch_size as f64;
+        for v in 0..self.weights.rows {
+            if !std::mem::take(&mut self.touched[v]) {
+                continue;
+            }
+            let (weights, grads) = (self.weights.row_mut(v), self.weight_grads.row_mut(v));
+            grads.iter_mut().for_each(|g| *g *= scale);
+            self.regularization.gradient(weights, grads);
+            optimizer.update((id, v), weights, grads, lr);
+            self.regularization.decay(lr, weights);
+            grads.fill(0.0);
+        }
+    }
+}
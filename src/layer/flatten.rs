@@ -0,0 +1,57 @@
+use rand::RngCore;
+
+use crate::config::ConfigLayer;
+use crate::error::Error;
+use crate::layer::utils::check_width;
+use crate::layer::{Layer, Mode};
+use crate::matrix::Matrix;
+use crate::tape::Cache;
+
+/// Drops the channel and spatial structure of its inputs. Samples are
+/// already stored flat, so the values pass through unchanged.
+pub struct Flatten {
+    pub name: String,
+    pub size: usize,
+}
+
+impl Flatten {
+    pub fn new(config: &ConfigLayer) -> Self {
+        Self {
+            name: config.name.clone(),
+            size: config.input_size,
+        }
+    }
+}
+
+impl Layer for Flatten {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn output_shape(&self) -> Vec<usize> {
+        vec![self.size]
+    }
+
+    fn check_inputs(&self, inputs: &Matrix) -> Result<(), Error> {
+        check_width(inputs, self.size)
+    }
+
+    fn forward(&self, inputs: &Matrix) -> Matrix {
+        inputs.clone()
+    }
+
+    fn forward_cached(
+        &mut self,
+        inputs: &Matrix,
+        cache: &mut Cache,
+        _mode: Mode,
+        _rng: &mut dyn RngCore,
+    ) {
+        cache.inputs.clone_from(inputs);
+        cache.outputs.clone_from(inputs);
+    }
+
+    fn backward(&mut self, _cache: &Cache, output_grads: &Matrix) -> Matrix {
+        output_grads.clone()
+    }
+}
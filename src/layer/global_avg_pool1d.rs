@@ -0,0 +1,74 @@
+use rand::RngCore;
+
+use crate::config::ConfigLayer;
+use crate::error::Error;
+use crate::layer::utils::check_width;
+use crate::layer::{Layer, Mode};
+use crate::matrix::Matrix;
+use crate::tape::Cache;
+
+/// Mean of each channel over the whole signal, one output per channel.
+pub struct GlobalAvgPool1D {
+    pub name: String,
+    pub channels: usize,
+    pub length: usize,
+}
+
+impl GlobalAvgPool1D {
+    pub fn new(config: &ConfigLayer) -> Self {
+        Self {
+            name: config.name.clone(),
+            channels: config.channels,
+            length: config.input_size / config.channels,
+        }
+    }
+}
+
+impl Layer for GlobalAvgPool1D {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn output_shape(&self) -> Vec<usize> {
+        vec![self.channels]
+    }
+
+    fn check_inputs(&self, inputs: &Matrix) -> Result<(), Error> {
+        check_width(inputs, self.channels * self.length)
+    }
+
+    fn forward(&self, inputs: &Matrix) -> Matrix {
+        let mut outputs = Matrix::zeros(inputs.rows, self.channels);
+        for i in 0..inputs.rows {
+            outputs
+                .row_mut(i)
+                .iter_mut()
+                .zip(inputs.row(i).chunks(self.length))
+                .for_each(|(o, signal)| *o = signal.iter().sum::<f64>() / self.length as f64);
+        }
+        outputs
+    }
+
+    fn forward_cached(
+        &mut self,
+        inputs: &Matrix,
+        cache: &mut Cache,
+        _mode: Mode,
+        _rng: &mut dyn RngCore,
+    ) {
+        cache.inputs.clone_from(inputs);
+        cache.outputs = self.forward(inputs);
+    }
+
+    fn backward(&mut self, cache: &Cache, output_grads: &Matrix) -> Matrix {
+        let mut errors = Matrix::zeros(cache.inputs.rows, cache.inputs.cols);
+        for i in 0..output_grads.rows {
+            errors
+                .row_mut(i)
+                .chunks_mut(self.length)
+                .zip(output_grads.row(i))
+                .for_each(|(signal, g)| signal.fill(g / self.length as f64));
+        }
+        errors
+    }
+}
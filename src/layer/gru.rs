@@ -0,0 +1,178 @@
+use rand::RngCore;
+
+use crate::config::ConfigLayer;
+use crate::error::Error;
+use crate::initialization::Initialization;
+use crate::layer::recurrent::sigmoid;
+use crate::layer::utils::check_width;
+use crate::layer::{Layer, Mode, Recurrent};
+use crate::matrix::Matrix;
+use crate::optimizer::Optimizer;
+use crate::tape::Cache;
+
+/// Gated recurrent unit layer. The weight rows hold the reset, update and
+/// candidate gates, in that order; the candidate sees the reset hidden
+/// state, `n_t = tanh(W_n · [x_t; r_t * h_{t-1}] + b_n)`, and
+/// `h_t = (1 - z_t) * n_t + z_t * h_{t-1}`.
+pub struct Gru {
+    pub name: String,
+    pub recurrent: Recurrent,
+}
+
+/// Values recorded per step: the three gates and the hidden state.
+const STATE: usize = 4;
+
+impl Gru {
+    pub fn new(config: &ConfigLayer, initialization: &mut Box<dyn Initialization>) -> Self {
+        Self {
+            name: config.name.clone(),
+            recurrent: Recurrent::new(config, 3, initialization),
+        }
+    }
+
+    /// Gates and hidden state of each step, and the outputs.
+    fn run(&self, inputs: &Matrix) -> (Matrix, Matrix) {
+        let r = &self.recurrent;
+        let n = r.hidden;
+        let mut states = Matrix::zeros(inputs.rows, r.steps * STATE * n);
+        let mut outputs = Matrix::zeros(inputs.rows, r.output_size());
+        for i in 0..inputs.rows {
+            let mut h = vec![0.0; n];
+            for t in 0..r.steps {
+                let x = r.input(inputs.row(i), t);
+                let mut gates = r.pre_activations(0..2 * n, x, &h);
+                gates.iter_mut().for_each(|v| *v = sigmoid(*v));
+                let reset: Vec<f64> = gates[..n]
+                    .iter()
+                    .zip(h.iter())
+                    .map(|(r, h)| r * h)
+                    .collect();
+                let candidate: Vec<f64> = r
+                    .pre_activations(2 * n..3 * n, x, &reset)
+                    .iter()
+                    .map(|v| v.tanh())
+                    .collect();
+                for j in 0..n {
+                    let update = gates[n + j];
+                    h[j] = (1.0 - update) * candidate[j] + update * h[j];
+                }
+                let state = &mut states.row_mut(i)[t * STATE * n..(t + 1) * STATE * n];
+                state[..2 * n].copy_from_slice(&gates);
+                state[2 * n..3 * n].copy_from_slice(&candidate);
+                state[3 * n..].copy_from_slice(&h);
+                r.emit(outputs.row_mut(i), t, &h);
+            }
+        }
+        (states, outputs)
+    }
+}
+
+impl Layer for Gru {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn output_shape(&self) -> Vec<usize> {
+        self.recurrent.output_shape()
+    }
+
+    fn check_inputs(&self, inputs: &Matrix) -> Result<(), Error> {
+        check_width(inputs, self.recurrent.steps * self.recurrent.features)
+    }
+
+    fn forward(&self, inputs: &Matrix) -> Matrix {
+        self.run(inputs).1
+    }
+
+    fn forward_cached(
+        &mut self,
+        inputs: &Matrix,
+        cache: &mut Cache,
+        _mode: Mode,
+        _rng: &mut dyn RngCore,
+    ) {
+        cache.inputs.clone_from(inputs);
+        (cache.states, cache.outputs) = self.run(inputs);
+    }
+
+    fn backward(&mut self, cache: &Cache, output_grads: &Matrix) -> Matrix {
+        let (steps, n, features) = (
+            self.recurrent.steps,
+            self.recurrent.hidden,
+            self.recurrent.features,
+        );
+        let mut input_grads = Matrix::zeros(cache.inputs.rows, cache.inputs.cols);
+        for i in 0..output_grads.rows {
+            let states = cache.states.row(i);
+            let mut dh_next = vec![0.0; n];
+            for t in (0..steps).rev() {
+                let mut dh = self.recurrent.output_grad(output_grads.row(i), t);
+                dh.iter_mut().zip(dh_next.iter()).for_each(|(d, n)| *d += n);
+                let state = &states[t * STATE * n..(t + 1) * STATE * n];
+                let h_prev = match t {
+                    0 => vec![0.0; n],
+                    _ => states[(t - 1) * STATE * n + 3 * n..t * STATE * n].to_vec(),
+                };
+                let (reset, update, candidate) =
+                    (&state[..n], &state[n..2 * n], &state[2 * n..3 * n]);
+                let reset_h: Vec<f64> = reset
+                    .iter()
+                    .zip(h_prev.iter())
+                    .map(|(r, h)| r * h)
+                    .collect();
+
+                // candidate gate, whose hidden input is the reset state
+                dh_next.fill(0.0);
+                let dz_candidate: Vec<f64> = (0..n)
+                    .map(|j| dh[j] * (1.0 - update[j]) * (1.0 - candidate[j] * candidate[j]))
+                    .collect();
+                let mut d_reset_h = vec![0.0; n];
+                let x = self.recurrent.input(cache.inputs.row(i), t);
+                let dx = &mut input_grads.row_mut(i)[t * features..(t + 1) * features];
+                self.recurrent.backward(
+                    2 * n..3 * n,
+                    &dz_candidate,
+                    (x, &reset_h),
+                    (dx, &mut d_reset_h),
+                );
+
+                let mut dz = vec![0.0; 2 * n];
+                for j in 0..n {
+                    dz[j] = d_reset_h[j] * h_prev[j] * reset[j] * (1.0 - reset[j]);
+                    dz[n + j] = dh[j] * (h_prev[j] - candidate[j]) * update[j] * (1.0 - update[j]);
+                    dh_next[j] = dh[j] * update[j] + d_reset_h[j] * reset[j];
+                }
+                self.recurrent
+                    .backward(0..2 * n, &dz, (x, &h_prev), (dx, &mut dh_next));
+                if self.recurrent.cut(t) {
+                    dh_next.fill(0.0);
+                }
+            }
+        }
+        input_grads
+    }
+
+    fn parameters(&self) -> Vec<&[f64]> {
+        self.recurrent.parameters()
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        self.recurrent.shapes()
+    }
+
+    fn gradients(&self) -> Vec<&[f64]> {
+        self.recurrent.gradients()
+    }
+
+    fn parameters_mut(&mut self) -> Vec<(&mut [f64], &mut [f64])> {
+        self.recurrent.parameters_mut()
+    }
+
+    fn penalty(&self) -> f64 {
+        self.recurrent.penalty()
+    }
+
+    fn update(&mut self, lr: f64, optimizer: &mut dyn Optimizer, id: usize, batch_size: usize) {
+        self.recurrent.update(lr, optimizer, id, batch_size);
+    }
+}
@@ -0,0 +1,68 @@
+use crate::layer::Window;
+use crate::matrix::Matrix;
+
+/// Index table of the image patches seen by a 2D kernel, computed once per
+/// layer. Row `(c * kernel + ki) * kernel + kj` holds, for each output
+/// position `oi * output_width + oj`, the index into the sample of tap
+/// `(ki, kj)` of channel `c`, or `None` in the padding.
+#[derive(Clone, Debug)]
+pub struct Im2Col {
+    pub rows: usize,
+    pub cols: usize,
+    pub indices: Vec<Option<usize>>,
+}
+
+impl Im2Col {
+    pub fn new(channels: usize, height: &Window, width: &Window) -> Self {
+        let (output_height, output_width) = (height.outputs(), width.outputs());
+        let rows = channels * height.kernel * width.kernel;
+        let cols = output_height * output_width;
+        let mut indices = Vec::with_capacity(rows * cols);
+        for c in 0..channels {
+            for ki in 0..height.kernel {
+                for kj in 0..width.kernel {
+                    for oi in 0..output_height {
+                        for oj in 0..output_width {
+                            let index = height
+                                .position(oi, ki)
+                                .zip(width.position(oj, kj))
+                                .map(|(i, j)| (c * height.size + i) * width.size + j);
+                            indices.push(index);
+                        }
+                    }
+                }
+            }
+        }
+        Self {
+            rows,
+            cols,
+            indices,
+        }
+    }
+
+    /// Taps of a kernel in each channel.
+    pub fn taps(&self, channels: usize) -> usize {
+        self.rows / channels
+    }
+
+    /// Patch matrix of a sample, zero in the padding.
+    pub fn gather(&self, x: &[f64]) -> Matrix {
+        let mut columns = Matrix::zeros(self.rows, self.cols);
+        columns
+            .data
+            .iter_mut()
+            .zip(self.indices.iter())
+            .for_each(|(v, index)| *v = index.map_or(0.0, |p| x[p]));
+        columns
+    }
+
+    /// Adds the patch gradients back to the positions they were gathered
+    /// from.
+    pub fn scatter(&self, columns: &Matrix, dx: &mut [f64]) {
+        for (g, index) in columns.data.iter().zip(self.indices.iter()) {
+            if let Some(p) = index {
+                dx[*p] += g;
+            }
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use rand::RngCore;
+
+use crate::error::Error;
+use crate::layer::Dense;
+use crate::matrix::Matrix;
+use crate::optimizer::Optimizer;
+use crate::tape::Cache;
+
+/// Whether stochastic layers are active. Dropout only draws masks in
+/// `Train` mode and is the identity in `Eval` mode and at inference.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Mode {
+    #[default]
+    Train,
+    Eval,
+}
+
+/// A layer of a `Network`, processing batches with one sample per row.
+pub trait Layer {
+    fn name(&self) -> &str;
+
+    /// Shape of the outputs of one sample, flattened in row-major order.
+    fn output_shape(&self) -> Vec<usize>;
+
+    /// Checks inputs the layer cannot process, e.g. samples of the wrong
+    /// size or tokens out of the vocabulary of an embedding, which `forward`
+    /// panics on.
+    fn check_inputs(&self, _inputs: &Matrix) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Inference forward pass.
+    fn forward(&self, inputs: &Matrix) -> Matrix;
+
+    /// Training forward pass recording what `backward` needs into `cache`.
+    /// Stochastic layers draw from `rng` in `Train` mode.
+    fn forward_cached(
+        &mut self,
+        inputs: &Matrix,
+        cache: &mut Cache,
+        mode: Mode,
+        rng: &mut dyn RngCore,
+    );
+
+    /// Accumulates the parameter gradients of the batch recorded in `cache`
+    /// and returns the gradients of the loss with respect to its inputs.
+    fn backward(&mut self, cache: &Cache, output_grads: &Matrix) -> Matrix;
+
+    /// Trainable parameter groups, e.g. weights and biases.
+    fn parameters(&self) -> Vec<&[f64]> {
+        Vec::new()
+    }
+
+    /// Shape of each group of `parameters`, e.g. `[rows, cols]` of a weight
+    /// matrix. Defaults to the length of each group.
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        self.parameters().iter().map(|p| vec![p.len()]).collect()
+    }
+
+    /// Gradients accumulated for each group of `parameters`.
+    fn gradients(&self) -> Vec<&[f64]> {
+        Vec::new()
+    }
+
+    /// Parameter groups paired with their accumulated gradients.
+    fn parameters_mut(&mut self) -> Vec<(&mut [f64], &mut [f64])> {
+        Vec::new()
+    }
+
+    /// State that is not trained by the optimizer but saved with the model,
+    /// e.g. running statistics.
+    fn buffers(&self) -> Vec<&[f64]> {
+        Vec::new()
+    }
+
+    fn buffers_mut(&mut self) -> Vec<&mut [f64]> {
+        Vec::new()
+    }
+
+    /// The layer as a `Dense` layer, e.g. to read its `neurons` through a
+    /// `Network`.
+    fn as_dense(&self) -> Option<&Dense> {
+        None
+    }
+
+    /// Regularization penalty of the current parameters.
+    fn penalty(&self) -> f64 {
+        0.0
+    }
+
+    /// Applies the gradients accumulated over `batch_size` samples with
+    /// `optimizer` and resets them. `id` is the position of the layer; each
+    /// parameter group is a distinct group for the optimizer.
+    fn update(&mut self, lr: f64, optimizer: &mut dyn Optimizer, id: usize, batch_size: usize) {
+        let scale = 1.0 / batch_size as f64;
+        for (group, (params, grads)) in self.parameters_mut().into_iter().enumerate() {
+            grads.iter_mut().for_each(|g| *g *= scale);
+            optimizer.update((id, group), params, grads, lr);
+            grads.iter_mut().for_each(|g| *g = 0.0);
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use rand::RngCore;
+
+use crate::config::ConfigLayer;
+use crate::error::Error;
+use crate::layer::utils::check_width;
+use crate::layer::{Layer, Mode};
+use crate::matrix::Matrix;
+use crate::tape::Cache;
+
+/// Layer normalization: normalizes the features of each sample with its own
+/// mean and variance, then scales by `gamma` and shifts by `beta`. Behaves
+/// the same in `Train` and `Eval` mode, including for batches of one sample.
+pub struct LayerNorm {
+    pub name: String,
+    pub size: usize,
+    pub gamma: Vec<f64>,
+    pub beta: Vec<f64>,
+    pub gamma_grads: Vec<f64>,
+    pub beta_grads: Vec<f64>,
+    pub epsilon: f64,
+}
+
+impl LayerNorm {
+    pub fn new(config: &ConfigLayer) -> Self {
+        let size = config.input_size;
+        Self {
+            name: config.name.clone(),
+            size,
+            gamma: vec![1.0; size],
+            beta: vec![0.0; size],
+            gamma_grads: vec![0.0; size],
+            beta_grads: vec![0.0; size],
+            epsilon: config.epsilon,
+        }
+    }
+
+    /// Normalizes each row of `inputs` and returns it with the per-sample
+    /// inverse standard deviation.
+    fn normalize(&self, inputs: &Matrix) -> (Matrix, Vec<f64>) {
+        let d = inputs.cols as f64;
+        let mut normalized = inputs.clone();
+        let mut inv_std = Vec::with_capacity(inputs.rows);
+        for i in 0..normalized.rows {
+            let row = normalized.row_mut(i);
+            let mean = row.iter().sum::<f64>() / d;
+            let var = row.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / d;
+            let s = 1.0 / (var + self.epsilon).sqrt();
+            row.iter_mut().for_each(|x| *x = (*x - mean) * s);
+            inv_std.push(s);
+        }
+        (normalized, inv_std)
+    }
+
+    fn scale(&self, normalized: &Matrix) -> Matrix {
+        let mut outputs = normalized.clone();
+        for i in 0..outputs.rows {
+            outputs
+                .row_mut(i)
+                .iter_mut()
+                .zip(self.gamma.iter().zip(self.beta.iter()))
+                .for_each(|(x, (g, b))| *x = *x * g + b);
+        }
+        outputs
+    }
+}
+
+impl Layer for LayerNorm {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn output_shape(&self) -> Vec<usize> {
+        vec![self.size]
+    }
+
+    fn check_inputs(&self, inputs: &Matrix) -> Result<(), Error> {
+        check_width(inputs, self.size)
+    }
+
+    fn forward(&self, inputs: &Matrix) -> Matrix {
+        self.scale(&self.normalize(inputs).0)
+    }
+
+    /// Records the normalized inputs as the pre-activations and the inverse
+    /// standard deviation of each sample as the statistics.
+    fn forward_cached(
+        &mut self,
+        inputs: &Matrix,
+        cache: &mut Cache,
+        mode: Mode,
+        _rng: &mut dyn RngCore,
+    ) {
+        let (normalized, inv_std) = self.normalize(inputs);
+        cache.inputs.clone_from(inputs);
+        cache.outputs = self.scale(&normalized);
+        cache.pre_activations = normalized;
+        cache.statistics = inv_std;
+        cache.mode = mode;
+    }
+
+    fn backward(&mut self, cache: &Cache, output_grads: &Matrix) -> Matrix {
+        let d = output_grads.cols as f64;
+        let mut errors = Matrix::zeros(output_grads.rows, output_grads.cols);
+        for i in 0..output_grads.rows {
+            let normalized = cache.pre_activations.row(i);
+            let (mut sum, mut dot) = (0.0, 0.0);
+            let error = errors.row_mut(i);
+            for (j, (dy, x)) in output_grads.row(i).iter().zip(normalized).enumerate() {
+                self.gamma_grads[j] += dy * x;
+                self.beta_grads[j] += dy;
+                error[j] = dy * self.gamma[j];
+                sum += error[j];
+                dot += error[j] * x;
+            }
+            let inv_std = cache.statistics[i];
+            error
+                .iter_mut()
+                .zip(normalized)
+                .for_each(|(e, x)| *e = inv_std / d * (d * *e - sum - x * dot));
+        }
+        errors
+    }
+
+    fn parameters(&self) -> Vec<&[f64]> {
+        vec![&self.gamma, &self.beta]
+    }
+
+    fn gradients(&self) -> Vec<&[f64]> {
+        vec![&self.gamma_grads, &self.beta_grads]
+    }
+
+    fn parameters_mut(&mut self) -> Vec<(&mut [f64], &mut [f64])> {
+        vec![
+            (&mut self.gamma, &mut self.gamma_grads),
+            (&mut self.beta, &mut self.beta_grads),
+        ]
+    }
+}
@@ -0,0 +1,162 @@
+use rand::RngCore;
+
+use crate::config::ConfigLayer;
+use crate::error::Error;
+use crate::initialization::Initialization;
+use crate::layer::recurrent::sigmoid;
+use crate::layer::utils::check_width;
+use crate::layer::{Layer, Mode, Recurrent};
+use crate::matrix::Matrix;
+use crate::optimizer::Optimizer;
+use crate::tape::Cache;
+
+/// Long short-term memory layer. The weight rows hold the input, forget,
+/// cell and output gates, in that order.
+pub struct Lstm {
+    pub name: String,
+    pub recurrent: Recurrent,
+}
+
+/// Values recorded per step: the four gates, the cell and the hidden state.
+const STATE: usize = 6;
+
+impl Lstm {
+    pub fn new(config: &ConfigLayer, initialization: &mut Box<dyn Initialization>) -> Self {
+        Self {
+            name: config.name.clone(),
+            recurrent: Recurrent::new(config, 4, initialization),
+        }
+    }
+
+    /// Gates, cell and hidden state of each step, and the outputs.
+    fn run(&self, inputs: &Matrix) -> (Matrix, Matrix) {
+        let r = &self.recurrent;
+        let n = r.hidden;
+        let mut states = Matrix::zeros(inputs.rows, r.steps * STATE * n);
+        let mut outputs = Matrix::zeros(inputs.rows, r.output_size());
+        for i in 0..inputs.rows {
+            let (mut h, mut c) = (vec![0.0; n], vec![0.0; n]);
+            for t in 0..r.steps {
+                let mut gates = r.pre_activations(0..4 * n, r.input(inputs.row(i), t), &h);
+                gates[..2 * n].iter_mut().for_each(|v| *v = sigmoid(*v));
+                gates[2 * n..3 * n].iter_mut().for_each(|v| *v = v.tanh());
+                gates[3 * n..].iter_mut().for_each(|v| *v = sigmoid(*v));
+                for j in 0..n {
+                    let (input, forget, cell, output) =
+                        (gates[j], gates[n + j], gates[2 * n + j], gates[3 * n + j]);
+                    c[j] = forget * c[j] + input * cell;
+                    h[j] = output * c[j].tanh();
+                }
+                let state = &mut states.row_mut(i)[t * STATE * n..(t + 1) * STATE * n];
+                state[..4 * n].copy_from_slice(&gates);
+                state[4 * n..5 * n].copy_from_slice(&c);
+                state[5 * n..].copy_from_slice(&h);
+                r.emit(outputs.row_mut(i), t, &h);
+            }
+        }
+        (states, outputs)
+    }
+}
+
+impl Layer for Lstm {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn output_shape(&self) -> Vec<usize> {
+        self.recurrent.output_shape()
+    }
+
+    fn check_inputs(&self, inputs: &Matrix) -> Result<(), Error> {
+        check_width(inputs, self.recurrent.steps * self.recurrent.features)
+    }
+
+    fn forward(&self, inputs: &Matrix) -> Matrix {
+        self.run(inputs).1
+    }
+
+    fn forward_cached(
+        &mut self,
+        inputs: &Matrix,
+        cache: &mut Cache,
+        _mode: Mode,
+        _rng: &mut dyn RngCore,
+    ) {
+        cache.inputs.clone_from(inputs);
+        (cache.states, cache.outputs) = self.run(inputs);
+    }
+
+    fn backward(&mut self, cache: &Cache, output_grads: &Matrix) -> Matrix {
+        let (steps, n, features) = (
+            self.recurrent.steps,
+            self.recurrent.hidden,
+            self.recurrent.features,
+        );
+        let mut input_grads = Matrix::zeros(cache.inputs.rows, cache.inputs.cols);
+        for i in 0..output_grads.rows {
+            let states = cache.states.row(i);
+            let step = |t: usize| &states[t * STATE * n..(t + 1) * STATE * n];
+            let (mut dh_next, mut dc_next) = (vec![0.0; n], vec![0.0; n]);
+            for t in (0..steps).rev() {
+                let mut dh = self.recurrent.output_grad(output_grads.row(i), t);
+                dh.iter_mut().zip(dh_next.iter()).for_each(|(d, n)| *d += n);
+                let state = step(t);
+                let (c_prev, h_prev) = match t {
+                    0 => (vec![0.0; n], vec![0.0; n]),
+                    _ => (
+                        step(t - 1)[4 * n..5 * n].to_vec(),
+                        step(t - 1)[5 * n..].to_vec(),
+                    ),
+                };
+
+                let mut dz = vec![0.0; 4 * n];
+                for j in 0..n {
+                    let (input, forget, cell, output) =
+                        (state[j], state[n + j], state[2 * n + j], state[3 * n + j]);
+                    let tanh_c = state[4 * n + j].tanh();
+                    let dc = dc_next[j] + dh[j] * output * (1.0 - tanh_c * tanh_c);
+                    dz[j] = dc * cell * input * (1.0 - input);
+                    dz[n + j] = dc * c_prev[j] * forget * (1.0 - forget);
+                    dz[2 * n + j] = dc * input * (1.0 - cell * cell);
+                    dz[3 * n + j] = dh[j] * tanh_c * output * (1.0 - output);
+                    dc_next[j] = dc * forget;
+                }
+
+                dh_next.fill(0.0);
+                let x = self.recurrent.input(cache.inputs.row(i), t);
+                let dx = &mut input_grads.row_mut(i)[t * features..(t + 1) * features];
+                self.recurrent
+                    .backward(0..4 * n, &dz, (x, &h_prev), (dx, &mut dh_next));
+                if self.recurrent.cut(t) {
+                    dh_next.fill(0.0);
+                    dc_next.fill(0.0);
+                }
+            }
+        }
+        input_grads
+    }
+
+    fn parameters(&self) -> Vec<&[f64]> {
+        self.recurrent.parameters()
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        self.recurrent.shapes()
+    }
+
+    fn gradients(&self) -> Vec<&[f64]> {
+        self.recurrent.gradients()
+    }
+
+    fn parameters_mut(&mut self) -> Vec<(&mut [f64], &mut [f64])> {
+        self.recurrent.parameters_mut()
+    }
+
+    fn penalty(&self) -> f64 {
+        self.recurrent.penalty()
+    }
+
+    fn update(&mut self, lr: f64, optimizer: &mut dyn Optimizer, id: usize, batch_size: usize) {
+        self.recurrent.update(lr, optimizer, id, batch_size);
+    }
+}
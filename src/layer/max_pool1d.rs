@@ -0,0 +1,105 @@
+use rand::RngCore;
+
+use crate::config::ConfigLayer;
+use crate::error::Error;
+use crate::layer::utils::check_width;
+use crate::layer::{Layer, Mode, Window};
+use crate::matrix::Matrix;
+use crate::tape::Cache;
+
+/// Maximum of each window of each channel, laid out like `Conv1D`. Padding
+/// positions are ignored.
+pub struct MaxPool1D {
+    pub name: String,
+    pub channels: usize,
+    pub window: Window,
+}
+
+impl MaxPool1D {
+    pub fn new(config: &ConfigLayer) -> Self {
+        Self {
+            name: config.name.clone(),
+            channels: config.channels,
+            window: Window::new(config, config.input_size / config.channels),
+        }
+    }
+
+    /// Index into the sample of the maximum of each window, `None` for
+    /// windows that only cover padding.
+    fn argmax(&self, x: &[f64]) -> Vec<Option<usize>> {
+        let (length, outputs) = (self.window.size, self.window.outputs());
+        let mut indices = Vec::with_capacity(self.channels * outputs);
+        for c in 0..self.channels {
+            for o in 0..outputs {
+                let index = (0..self.window.kernel)
+                    .filter_map(|k| self.window.position(o, k))
+                    .map(|p| c * length + p)
+                    .reduce(|best, p| if x[p] > x[best] { p } else { best });
+                indices.push(index);
+            }
+        }
+        indices
+    }
+}
+
+impl Layer for MaxPool1D {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn output_shape(&self) -> Vec<usize> {
+        vec![self.channels, self.window.outputs()]
+    }
+
+    fn check_inputs(&self, inputs: &Matrix) -> Result<(), Error> {
+        check_width(inputs, self.channels * self.window.size)
+    }
+
+    fn forward(&self, inputs: &Matrix) -> Matrix {
+        let mut outputs = Matrix::zeros(inputs.rows, self.channels * self.window.outputs());
+        for i in 0..inputs.rows {
+            let x = inputs.row(i);
+            outputs
+                .row_mut(i)
+                .iter_mut()
+                .zip(self.argmax(x))
+                .for_each(|(o, index)| *o = index.map_or(0.0, |p| x[p]));
+        }
+        outputs
+    }
+
+    /// Records the index of each maximum as the pre-activations, `-1` for
+    /// windows that only cover padding.
+    fn forward_cached(
+        &mut self,
+        inputs: &Matrix,
+        cache: &mut Cache,
+        _mode: Mode,
+        _rng: &mut dyn RngCore,
+    ) {
+        cache.inputs.clone_from(inputs);
+        cache.outputs = self.forward(inputs);
+        cache.pre_activations = Matrix::zeros(inputs.rows, cache.outputs.cols);
+        for i in 0..inputs.rows {
+            cache
+                .pre_activations
+                .row_mut(i)
+                .iter_mut()
+                .zip(self.argmax(inputs.row(i)))
+                .for_each(|(m, index)| *m = index.map_or(-1.0, |p| p as f64));
+        }
+    }
+
+    fn backward(&mut self, cache: &Cache, output_grads: &Matrix) -> Matrix {
+        let mut errors = Matrix::zeros(cache.inputs.rows, cache.inputs.cols);
+        for i in 0..output_grads.rows {
+            let error = errors.row_mut(i);
+            for (g, index) in output_grads.row(i).iter().zip(cache.pre_activations.row(i)) {
+                if *index >= 0.0 {
+                    error[*index as usize] += g;
+                }
+            }
+        }
+        errors
+    }
+}
@@ -0,0 +1,112 @@
+use rand::RngCore;
+
+use crate::config::ConfigLayer;
+use crate::error::Error;
+use crate::layer::utils::check_width;
+use crate::layer::{Im2Col, Layer, Mode, Window};
+use crate::matrix::Matrix;
+use crate::tape::Cache;
+
+/// Maximum of each patch of each channel, laid out like `Conv2D`. Padding
+/// positions are ignored.
+pub struct MaxPool2D {
+    pub name: String,
+    pub channels: usize,
+    pub height: Window,
+    pub width: Window,
+    pub patches: Im2Col,
+}
+
+impl MaxPool2D {
+    pub fn new(config: &ConfigLayer) -> Self {
+        let (height, width) = (
+            Window::new(config, config.height),
+            Window::new(config, config.width),
+        );
+        Self {
+            name: config.name.clone(),
+            channels: config.channels,
+            patches: Im2Col::new(config.channels, &height, &width),
+            height,
+            width,
+        }
+    }
+
+    /// Index into the sample of the maximum of each patch, `None` for
+    /// patches that only cover padding.
+    fn argmax(&self, x: &[f64]) -> Vec<Option<usize>> {
+        let (taps, positions) = (self.patches.taps(self.channels), self.patches.cols);
+        let mut indices = Vec::with_capacity(self.channels * positions);
+        for c in 0..self.channels {
+            for o in 0..positions {
+                let index = (c * taps..(c + 1) * taps)
+                    .filter_map(|row| self.patches.indices[row * positions + o])
+                    .reduce(|best, p| if x[p] > x[best] { p } else { best });
+                indices.push(index);
+            }
+        }
+        indices
+    }
+}
+
+impl Layer for MaxPool2D {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn output_shape(&self) -> Vec<usize> {
+        vec![self.channels, self.height.outputs(), self.width.outputs()]
+    }
+
+    fn check_inputs(&self, inputs: &Matrix) -> Result<(), Error> {
+        check_width(inputs, self.channels * self.height.size * self.width.size)
+    }
+
+    fn forward(&self, inputs: &Matrix) -> Matrix {
+        let mut outputs = Matrix::zeros(inputs.rows, self.channels * self.patches.cols);
+        for i in 0..inputs.rows {
+            let x = inputs.row(i);
+            outputs
+                .row_mut(i)
+                .iter_mut()
+                .zip(self.argmax(x))
+                .for_each(|(o, index)| *o = index.map_or(0.0, |p| x[p]));
+        }
+        outputs
+    }
+
+    /// Records the index of each maximum as the pre-activations, `-1` for
+    /// patches that only cover padding.
+    fn forward_cached(
+        &mut self,
+        inputs: &Matrix,
+        cache: &mut Cache,
+        _mode: Mode,
+        _rng: &mut dyn RngCore,
+    ) {
+        cache.inputs.clone_from(inputs);
+        cache.outputs = self.forward(inputs);
+        cache.pre_activations = Matrix::zeros(inputs.rows, cache.outputs.cols);
+        for i in 0..inputs.rows {
+            cache
+                .pre_activations
+                .row_mut(i)
+                .iter_mut()
+                .zip(self.argmax(inputs.row(i)))
+                .for_each(|(m, index)| *m = index.map_or(-1.0, |p| p as f64));
+        }
+    }
+
+    fn backward(&mut self, cache: &Cache, output_grads: &Matrix) -> Matrix {
+        let mut errors = Matrix::zeros(cache.inputs.rows, cache.inputs.cols);
+        for i in 0..output_grads.rows {
+            let error = errors.row_mut(i);
+            for (g, index) in output_grads.row(i).iter().zip(cache.pre_activations.row(i)) {
+                if *index >= 0.0 {
+                    error[*index as usize] += g;
+                }
+            }
+        }
+        errors
+    }
+}
@@ -0,0 +1,53 @@
+pub mod activation;
+pub mod attention;
+pub mod avg_pool1d;
+pub mod avg_pool2d;
+pub mod batch_norm;
+pub mod conv1d;
+pub mod conv2d;
+pub mod dense;
+pub mod dropout;
+pub mod embedding;
+pub mod flatten;
+pub mod global_avg_pool1d;
+pub mod gru;
+pub mod im2col;
+pub mod layer_impl;
+pub mod layer_norm;
+pub mod lstm;
+pub mod max_pool1d;
+pub mod max_pool2d;
+pub mod positional_encoding;
+pub mod recurrent;
+pub mod rnn;
+pub mod sublayers;
+pub mod transformer;
+pub mod utils;
+pub mod window;
+
+pub use crate::layer::activation::ActivationLayer;
+pub use crate::layer::attention::MultiHeadAttention;
+pub use crate::layer::avg_pool1d::AvgPool1D;
+pub use crate::layer::avg_pool2d::AvgPool2D;
+pub use crate::layer::batch_norm::BatchNorm;
+pub use crate::layer::conv1d::Conv1D;
+pub use crate::layer::conv2d::Conv2D;
+pub use crate::layer::dense::Dense;
+pub use crate::layer::dropout::Dropout;
+pub use crate::layer::embedding::Embedding;
+pub use crate::layer::flatten::Flatten;
+pub use crate::layer::global_avg_pool1d::GlobalAvgPool1D;
+pub use crate::layer::gru::Gru;
+pub use crate::layer::im2col::Im2Col;
+pub use crate::layer::layer_impl::{Layer, Mode};
+pub use crate::layer::layer_norm::LayerNorm;
+pub use crate::layer::lstm::Lstm;
+pub use crate::layer::max_pool1d::MaxPool1D;
+pub use crate::layer::max_pool2d::MaxPool2D;
+pub use crate::layer::positional_encoding::PositionalEncoding;
+pub use crate::layer::recurrent::Recurrent;
+pub use crate::layer::rnn::Rnn;
+pub use crate::layer::sublayers::Sublayers;
+pub use crate::layer::transformer::Transformer;
+pub use crate::layer::utils::{get_layer, LAYERS};
+pub use crate::layer::window::Window;
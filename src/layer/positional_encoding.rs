@@ -0,0 +1,76 @@
+use rand::RngCore;
+
+use crate::config::ConfigLayer;
+use crate::error::Error;
+use crate::layer::utils::check_width;
+use crate::layer::{Layer, Mode};
+use crate::matrix::Matrix;
+use crate::tape::Cache;
+
+/// Adds sinusoidal position encodings to samples of `steps` vectors of
+/// `features` values: feature `2i` of step `t` gets `sin(t / 10000^(2i / d))`
+/// and feature `2i + 1` the matching cosine.
+pub struct PositionalEncoding {
+    pub name: String,
+    pub steps: usize,
+    /// Encodings of all steps, in the layout of a sample.
+    pub encodings: Vec<f64>,
+}
+
+impl PositionalEncoding {
+    pub fn new(config: &ConfigLayer) -> Self {
+        let features = config.input_size / config.steps;
+        let mut encodings = vec![0.0; config.input_size];
+        for (k, e) in encodings.iter_mut().enumerate() {
+            let (t, j) = (k / features, k % features);
+            let angle = t as f64 / 10000f64.powf((j - j % 2) as f64 / features as f64);
+            *e = if j % 2 == 0 { angle.sin() } else { angle.cos() };
+        }
+        Self {
+            name: config.name.clone(),
+            steps: config.steps,
+            encodings,
+        }
+    }
+}
+
+impl Layer for PositionalEncoding {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn output_shape(&self) -> Vec<usize> {
+        vec![self.steps, self.encodings.len() / self.steps]
+    }
+
+    fn check_inputs(&self, inputs: &Matrix) -> Result<(), Error> {
+        check_width(inputs, self.encodings.len())
+    }
+
+    fn forward(&self, inputs: &Matrix) -> Matrix {
+        let mut outputs = inputs.clone();
+        for i in 0..outputs.rows {
+            outputs
+                .row_mut(i)
+                .iter_mut()
+                .zip(self.encodings.iter())
+                .for_each(|(x, e)| *x += e);
+        }
+        outputs
+    }
+
+    fn forward_cached(
+        &mut self,
+        inputs: &Matrix,
+        cache: &mut Cache,
+        _mode: Mode,
+        _rng: &mut dyn RngCore,
+    ) {
+        cache.inputs.clone_from(inputs);
+        cache.outputs = self.forward(inputs);
+    }
+
+    fn backward(&mut self, _cache: &Cache, output_grads: &Matrix) -> Matrix {
+        output_grads.clone()
+    }
+}
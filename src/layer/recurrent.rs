@@ -0,0 +1,180 @@
+use std::ops::Range;
+
+use crate::config::ConfigLayer;
+use crate::initialization::Initialization;
+use crate::matrix::Matrix;
+use crate::optimizer::Optimizer;
+use crate::regularization::Regularization;
+
+/// Weights shared by the recurrent layers. Samples hold `steps` consecutive
+/// input vectors; each gate of a step is computed from the input vector and
+/// the previous hidden state as `weights · [x; h] + biases`.
+pub struct Recurrent {
+    pub steps: usize,
+    pub features: usize,
+    pub hidden: usize,
+    pub return_sequences: bool,
+    /// Length of the chunks of steps that gradients flow back through, zero
+    /// for the whole sequence.
+    pub truncation: usize,
+    /// `gates × hidden` rows of `features + hidden` weights.
+    pub weights: Matrix,
+    pub biases: Vec<f64>,
+    pub weight_grads: Matrix,
+    pub bias_grads: Vec<f64>,
+    pub regularization: Regularization,
+}
+
+impl Recurrent {
+    pub fn new(
+        config: &ConfigLayer,
+        gates: usize,
+        initialization: &mut Box<dyn Initialization>,
+    ) -> Self {
+        let (features, hidden) = (config.input_size / config.steps, config.hidden_size);
+        let (rows, cols) = (gates * hidden, features + hidden);
+        let mut weights = Matrix::zeros(rows, cols);
+        let mut biases = vec![0.0; rows];
+        for (j, bias) in biases.iter_mut().enumerate() {
+            *bias = initialization.bias(cols, hidden);
+            weights
+                .row_mut(j)
+                .iter_mut()
+                .for_each(|w| *w = initialization.init(cols, hidden));
+        }
+
+        Self {
+            steps: config.steps,
+            features,
+            hidden,
+            return_sequences: config.return_sequences,
+            truncation: config.truncation,
+            weight_grads: Matrix::zeros(rows, cols),
+            bias_grads: vec![0.0; rows],
+            regularization: Regularization::new(config),
+            weights,
+            biases,
+        }
+    }
+
+    /// Input vector of step `t` of a sample.
+    pub fn input<'a>(&self, sample: &'a [f64], t: usize) -> &'a [f64] {
+        &sample[t * self.features..(t + 1) * self.features]
+    }
+
+    pub fn output_size(&self) -> usize {
+        if self.return_sequences {
+            self.steps * self.hidden
+        } else {
+            self.hidden
+        }
+    }
+
+    pub fn output_shape(&self) -> Vec<usize> {
+        if self.return_sequences {
+            vec![self.steps, self.hidden]
+        } else {
+            vec![self.hidden]
+        }
+    }
+
+    /// Copies the hidden state of step `t` to the outputs of a sample.
+    pub fn emit(&self, outputs: &mut [f64], t: usize, h: &[f64]) {
+        if self.return_sequences {
+            outputs[t * self.hidden..(t + 1) * self.hidden].copy_from_slice(h);
+        } else if t + 1 == self.steps {
+            outputs.copy_from_slice(h);
+        }
+    }
+
+    /// Gradient of the outputs of a sample with respect to the hidden state
+    /// of step `t`.
+    pub fn output_grad(&self, output_grads: &[f64], t: usize) -> Vec<f64> {
+        if self.return_sequences {
+            output_grads[t * self.hidden..(t + 1) * self.hidden].to_vec()
+        } else if t + 1 == self.steps {
+            output_grads.to_vec()
+        } else {
+            vec![0.0; self.hidden]
+        }
+    }
+
+    /// Whether gradients stop flowing from step `t` to step `t - 1`.
+    pub fn cut(&self, t: usize) -> bool {
+        self.truncation > 0 && t.is_multiple_of(self.truncation)
+    }
+
+    /// `weights · [x; h] + biases` for the weight rows in `gates`.
+    pub fn pre_activations(&self, gates: Range<usize>, x: &[f64], h: &[f64]) -> Vec<f64> {
+        gates
+            .map(|j| {
+                let (wx, wh) = self.weights.row(j).split_at(self.features);
+                let dot = |w: &[f64], v: &[f64]| w.iter().zip(v).map(|(w, v)| w * v).sum::<f64>();
+                self.biases[j] + dot(wx, x) + dot(wh, h)
+            })
+            .collect()
+    }
+
+    /// Accumulates the gradients of the weight rows in `gates` from the
+    /// pre-activation gradients `dz`, adds the gradients of `x` and `h` to
+    /// `dx` and `dh`.
+    pub fn backward(
+        &mut self,
+        gates: Range<usize>,
+        dz: &[f64],
+        (x, h): (&[f64], &[f64]),
+        (dx, dh): (&mut [f64], &mut [f64]),
+    ) {
+        for (j, d) in gates.zip(dz) {
+            self.bias_grads[j] += d;
+            let (gx, gh) = self.weight_grads.row_mut(j).split_at_mut(self.features);
+            gx.iter_mut().zip(x).for_each(|(g, x)| *g += d * x);
+            gh.iter_mut().zip(h).for_each(|(g, h)| *g += d * h);
+            let (wx, wh) = self.weights.row(j).split_at(self.features);
+            dx.iter_mut().zip(wx).for_each(|(g, w)| *g += d * w);
+            dh.iter_mut().zip(wh).for_each(|(g, w)| *g += d * w);
+        }
+    }
+
+    pub fn parameters(&self) -> Vec<&[f64]> {
+        vec![&self.weights.data, &self.biases]
+    }
+
+    pub fn shapes(&self) -> Vec<Vec<usize>> {
+        vec![
+            vec![self.weights.rows, self.weights.cols],
+            vec![self.biases.len()],
+        ]
+    }
+
+    pub fn gradients(&self) -> Vec<&[f64]> {
+        vec![&self.weight_grads.data, &self.bias_grads]
+    }
+
+    pub fn parameters_mut(&mut self) -> Vec<(&mut [f64], &mut [f64])> {
+        vec![
+            (&mut self.weights.data, &mut self.weight_grads.data),
+            (&mut self.biases, &mut self.bias_grads),
+        ]
+    }
+
+    pub fn penalty(&self) -> f64 {
+        self.regularization
+            .layer_penalty(&self.weights.data, &self.biases)
+    }
+
+    pub fn update(&mut self, lr: f64, optimizer: &mut dyn Optimizer, id: usize, batch_size: usize) {
+        self.regularization.update(
+            lr,
+            optimizer,
+            id,
+            batch_size,
+            (&mut self.weights.data, &mut self.weight_grads.data),
+            (&mut self.biases, &mut self.bias_grads),
+        );
+    }
+}
+
+pub fn sigmoid(x: f64) -> f64 {
+    1.0 / (1.0 + (-x).exp())
+}
@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+
+use rand::RngCore;
+
+use crate::activation::{get_activation, Activation};
+use crate::config::ConfigLayer;
+use crate::error::Error;
+use crate::initialization::Initialization;
+use crate::layer::utils::check_width;
+use crate::layer::{Layer, Mode, Recurrent};
+use crate::matrix::Matrix;
+use crate::optimizer::Optimizer;
+use crate::tape::Cache;
+
+/// Elman recurrent layer, `h_t = activation(W · [x_t; h_{t-1}] + b)`.
+pub struct Rnn {
+    pub name: String,
+    pub recurrent: Recurrent,
+    pub activation: Box<dyn Activation>,
+}
+
+impl Rnn {
+    pub fn new(
+        config: &ConfigLayer,
+        initialization: &mut Box<dyn Initialization>,
+    ) -> Result<Self, Error> {
+        Ok(Self {
+            name: config.name.clone(),
+            recurrent: Recurrent::new(config, 1, initialization),
+            activation: get_activation(&config.activation, HashMap::new())?,
+        })
+    }
+
+    /// Pre-activations and hidden state of each step, and the outputs.
+    fn run(&self, inputs: &Matrix) -> (Matrix, Matrix) {
+        let r = &self.recurrent;
+        let mut states = Matrix::zeros(inputs.rows, r.steps * 2 * r.hidden);
+        let mut outputs = Matrix::zeros(inputs.rows, r.output_size());
+        for i in 0..inputs.rows {
+            let mut h = vec![0.0; r.hidden];
+            for t in 0..r.steps {
+                let z = r.pre_activations(0..r.hidden, r.input(inputs.row(i), t), &h);
+                h = self.activation.function(&z);
+                let state = &mut states.row_mut(i)[t * 2 * r.hidden..(t + 1) * 2 * r.hidden];
+                state[..r.hidden].copy_from_slice(&z);
+                state[r.hidden..].copy_from_slice(&h);
+                r.emit(outputs.row_mut(i), t, &h);
+            }
+        }
+        (states, outputs)
+    }
+}
+
+impl Layer for Rnn {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn output_shape(&self) -> Vec<usize> {
+        self.recurrent.output_shape()
+    }
+
+    fn check_inputs(&self, inputs: &Matrix) -> Result<(), Error> {
+        check_width(inputs, self.recurrent.steps * self.recurrent.features)
+    }
+
+    fn forward(&self, inputs: &Matrix) -> Matrix {
+        self.run(inputs).1
+    }
+
+    fn forward_cached(
+        &mut self,
+        inputs: &Matrix,
+        cache: &mut Cache,
+        _mode: Mode,
+        _rng: &mut dyn RngCore,
+    ) {
+        cache.inputs.clone_from(inputs);
+        (cache.states, cache.outputs) = self.run(inputs);
+    }
+
+    fn backward(&mut self, cache: &Cache, output_grads: &Matrix) -> Matrix {
+        let (steps, hidden, features) = (
+            self.recurrent.steps,
+            self.recurrent.hidden,
+            self.recurrent.features,
+        );
+        let mut input_grads = Matrix::zeros(cache.inputs.rows, cache.inputs.cols);
+        for i in 0..output_grads.rows {
+            let states = cache.states.row(i);
+            let mut dh_next = vec![0.0; hidden];
+            for t in (0..steps).rev() {
+                let mut dh = self.recurrent.output_grad(output_grads.row(i), t);
+                dh.iter_mut().zip(dh_next.iter()).for_each(|(d, n)| *d += n);
+                let z = &states[t * 2 * hidden..t * 2 * hidden + hidden];
+                let h_prev = match t {
+                    0 => vec![0.0; hidden],
+                    _ => states[(t - 1) * 2 * hidden + hidden..t * 2 * hidden].to_vec(),
+                };
+                let dz = self.activation.backward(z, &dh);
+
+                dh_next.fill(0.0);
+                let x = self.recurrent.input(cache.inputs.row(i), t);
+                let dx = &mut input_grads.row_mut(i)[t * features..(t + 1) * features];
+                self.recurrent
+                    .backward(0..hidden, &dz, (x, &h_prev), (dx, &mut dh_next));
+                if self.recurrent.cut(t) {
+                    dh_next.fill(0.0);
+                }
+            }
+        }
+        input_grads
+    }
+
+    fn parameters(&self) -> Vec<&[f64]> {
+        self.recurrent.parameters()
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        self.recurrent.shapes()
+    }
+
+    fn gradients(&self) -> Vec<&[f64]> {
+        self.recurrent.gradients()
+    }
+
+    fn parameters_mut(&mut self) -> Vec<(&mut [f64], &mut [f64])> {
+        self.recurrent.parameters_mut()
+    }
+
+    fn penalty(&self) -> f64 {
+        self.recurrent.penalty()
+    }
+
+    fn update(&mut self, lr: f64, optimizer: &mut dyn Optimizer, id: usize, batch_size: usize) {
+        self.recurrent.update(lr, optimizer, id, batch_size);
+    }
+}
@@ -0,0 +1,95 @@
+use rand::RngCore;
+
+use crate::layer::{Layer, Mode};
+use crate::matrix::Matrix;
+use crate::optimizer::{Optimizer, Shifted};
+use crate::tape::Cache;
+
+/// Layers that a composite layer is built from. Sublayer `k` records its
+/// forward pass into child `k` of the composite layer's cache, and its
+/// parameter groups follow those of the sublayers before it.
+pub struct Sublayers {
+    pub layers: Vec<Box<dyn Layer>>,
+}
+
+impl Sublayers {
+    pub fn new(layers: Vec<Box<dyn Layer>>) -> Self {
+        Self { layers }
+    }
+
+    pub fn forward(&self, k: usize, inputs: &Matrix) -> Matrix {
+        self.layers[k].forward(inputs)
+    }
+
+    /// Runs sublayer `k` and returns a copy of its outputs.
+    pub fn forward_cached(
+        &mut self,
+        k: usize,
+        inputs: &Matrix,
+        cache: &mut Cache,
+        mode: Mode,
+        rng: &mut dyn RngCore,
+    ) -> Matrix {
+        if cache.children.len() < self.layers.len() {
+            cache
+                .children
+                .resize_with(self.layers.len(), Cache::default);
+        }
+        let child = &mut cache.children[k];
+        self.layers[k].forward_cached(inputs, child, mode, rng);
+        child.outputs.clone()
+    }
+
+    pub fn backward(&mut self, k: usize, cache: &Cache, output_grads: &Matrix) -> Matrix {
+        self.layers[k].backward(&cache.children[k], output_grads)
+    }
+
+    pub fn parameters(&self) -> Vec<&[f64]> {
+        self.layers.iter().flat_map(|l| l.parameters()).collect()
+    }
+
+    pub fn shapes(&self) -> Vec<Vec<usize>> {
+        self.layers.iter().flat_map(|l| l.shapes()).collect()
+    }
+
+    pub fn gradients(&self) -> Vec<&[f64]> {
+        self.layers.iter().flat_map(|l| l.gradients()).collect()
+    }
+
+    pub fn parameters_mut(&mut self) -> Vec<(&mut [f64], &mut [f64])> {
+        self.layers
+            .iter_mut()
+            .flat_map(|l| l.parameters_mut())
+            .collect()
+    }
+
+    pub fn buffers(&self) -> Vec<&[f64]> {
+        self.layers.iter().flat_map(|l| l.buffers()).collect()
+    }
+
+    pub fn buffers_mut(&mut self) -> Vec<&mut [f64]> {
+        self.layers
+            .iter_mut()
+            .flat_map(|l| l.buffers_mut())
+            .collect()
+    }
+
+    pub fn penalty(&self) -> f64 {
+        self.layers.iter().map(|l| l.penalty()).sum()
+    }
+
+    /// Updates each sublayer with its groups shifted past those of the
+    /// sublayers before it.
+    pub fn update(&mut self, lr: f64, optimizer: &mut dyn Optimizer, id: usize, batch_size: usize) {
+        let mut offset = 0;
+        for layer in self.layers.iter_mut() {
+            let groups = layer.parameters().len();
+            let mut shifted = Shifted {
+                optimizer: &mut *optimizer,
+                offset,
+            };
+            layer.update(lr, &mut shifted, id, batch_size);
+            offset += groups;
+        }
+    }
+}
@@ -0,0 +1,164 @@
+use rand::RngCore;
+
+use crate::config::ConfigLayer;
+use crate::error::Error;
+use crate::initialization::Initialization;
+use crate::layer::utils::check_width;
+use crate::layer::{Dense, Layer, LayerNorm, Mode, MultiHeadAttention, Sublayers};
+use crate::matrix::Matrix;
+use crate::optimizer::Optimizer;
+use crate::tape::Cache;
+
+const ATTENTION: usize = 0;
+const ATTENTION_NORM: usize = 1;
+const HIDDEN: usize = 2;
+const OUTPUT: usize = 3;
+const OUTPUT_NORM: usize = 4;
+
+/// Transformer encoder block over samples of `steps` vectors of `features`
+/// values: `h = norm(x + attention(x))`, then `norm(h + feed_forward(h))`.
+/// The feed-forward network has `hidden_size` units with the configured
+/// activation and is applied to each step.
+pub struct Transformer {
+    pub name: String,
+    pub steps: usize,
+    pub features: usize,
+    /// Attention, its norm, the feed-forward layers and their norm.
+    pub layers: Sublayers,
+}
+
+impl Transformer {
+    pub fn new(
+        config: &ConfigLayer,
+        initialization: &mut Box<dyn Initialization>,
+    ) -> Result<Self, Error> {
+        let features = config.input_size / config.steps;
+        let sublayer = |name: &str, kind: &str, sizes: (usize, usize)| ConfigLayer {
+            name: format!("{}.{}", config.name, name),
+            kind: kind.to_string(),
+            input_size: sizes.0,
+            output_size: sizes.1,
+            ..config.clone()
+        };
+        let output = ConfigLayer {
+            activation: "linear".to_string(),
+            ..sublayer("output", "dense", (config.hidden_size, features))
+        };
+        let layers: Vec<Box<dyn Layer>> = vec![
+            Box::new(MultiHeadAttention::new(
+                &sublayer(
+                    "attention",
+                    "attention",
+                    (config.input_size, config.input_size),
+                ),
+                initialization,
+            )?),
+            Box::new(LayerNorm::new(&sublayer(
+                "attention-norm",
+                "layer-norm",
+                (features, features),
+            ))),
+            Box::new(Dense::new(
+                &sublayer("hidden", "dense", (features, config.hidden_size)),
+                initialization,
+            )?),
+            Box::new(Dense::new(&output, initialization)?),
+            Box::new(LayerNorm::new(&sublayer(
+                "output-norm",
+                "layer-norm",
+                (features, features),
+            ))),
+        ];
+
+        Ok(Self {
+            name: config.name.clone(),
+            steps: config.steps,
+            features,
+            layers: Sublayers::new(layers),
+        })
+    }
+}
+
+/// Element-wise sum of two matrices, reshaped to `cols` columns.
+fn residual(a: &Matrix, b: &Matrix, cols: usize) -> Matrix {
+    let mut sum = a.reshape(cols);
+    sum.data.iter_mut().zip(&b.data).for_each(|(s, b)| *s += b);
+    sum
+}
+
+impl Layer for Transformer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn output_shape(&self) -> Vec<usize> {
+        vec![self.steps, self.features]
+    }
+
+    fn check_inputs(&self, inputs: &Matrix) -> Result<(), Error> {
+        check_width(inputs, self.steps * self.features)
+    }
+
+    fn forward(&self, inputs: &Matrix) -> Matrix {
+        let l = &self.layers;
+        let attention = l.forward(ATTENTION, inputs);
+        let h = l.forward(ATTENTION_NORM, &residual(inputs, &attention, self.features));
+        let f = l.forward(OUTPUT, &l.forward(HIDDEN, &h));
+        let outputs = l.forward(OUTPUT_NORM, &residual(&h, &f, self.features));
+        outputs.reshape(inputs.cols)
+    }
+
+    fn forward_cached(
+        &mut self,
+        inputs: &Matrix,
+        cache: &mut Cache,
+        mode: Mode,
+        rng: &mut dyn RngCore,
+    ) {
+        let (l, d) = (&mut self.layers, self.features);
+        let attention = l.forward_cached(ATTENTION, inputs, cache, mode, rng);
+        let sum = residual(inputs, &attention, d);
+        let h = l.forward_cached(ATTENTION_NORM, &sum, cache, mode, rng);
+        let hidden = l.forward_cached(HIDDEN, &h, cache, mode, rng);
+        let f = l.forward_cached(OUTPUT, &hidden, cache, mode, rng);
+        let outputs = l.forward_cached(OUTPUT_NORM, &residual(&h, &f, d), cache, mode, rng);
+        cache.inputs.clone_from(inputs);
+        cache.outputs = outputs.reshape(inputs.cols);
+    }
+
+    fn backward(&mut self, cache: &Cache, output_grads: &Matrix) -> Matrix {
+        let (l, d) = (&mut self.layers, self.features);
+        let sum_grads = l.backward(OUTPUT_NORM, cache, &output_grads.reshape(d));
+        let hidden_grads = l.backward(OUTPUT, cache, &sum_grads);
+        let h_grads = residual(&sum_grads, &l.backward(HIDDEN, cache, &hidden_grads), d);
+        let sum_grads = l
+            .backward(ATTENTION_NORM, cache, &h_grads)
+            .reshape(output_grads.cols);
+        let attention_grads = l.backward(ATTENTION, cache, &sum_grads);
+        residual(&sum_grads, &attention_grads, output_grads.cols)
+    }
+
+    fn parameters(&self) -> Vec<&[f64]> {
+        self.layers.parameters()
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        self.layers.shapes()
+    }
+
+    fn gradients(&self) -> Vec<&[f64]> {
+        self.layers.gradients()
+    }
+
+    fn parameters_mut(&mut self) -> Vec<(&mut [f64], &mut [f64])> {
+        self.layers.parameters_mut()
+    }
+
+    fn penalty(&self) -> f64 {
+        self.layers.penalty()
+    }
+
+    fn update(&mut self, lr: f64, optimizer: &mut dyn Optimizer, id: usize, batch_size: usize) {
+        self.layers.update(lr, optimizer, id, batch_size);
+    }
+}
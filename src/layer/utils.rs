@@ -0,0 +1,71 @@
+use crate::config::ConfigLayer;
+use crate::error::Error;
+use crate::initialization::Initialization;
+use crate::layer::*;
+use crate::matrix::Matrix;
+
+/// Kinds accepted by `get_layer`.
+pub const LAYERS: [&str; 20] = [
+    "dense",
+    "activation",
+    "dropout",
+    "batch-norm",
+    "layer-norm",
+    "conv1d",
+    "max-pool1d",
+    "avg-pool1d",
+    "global-avg-pool1d",
+    "conv2d",
+    "max-pool2d",
+    "avg-pool2d",
+    "flatten",
+    "rnn",
+    "lstm",
+    "gru",
+    "embedding",
+    "positional-encoding",
+    "attention",
+    "transformer",
+];
+
+pub fn get_layer(
+    config: &ConfigLayer,
+    initialization: &mut Box<dyn Initialization>,
+) -> Result<Box<dyn Layer>, Error> {
+    let layer: Box<dyn Layer> = match config.kind.as_str() {
+        "dense" => Box::new(Dense::new(config, initialization)?),
+        "activation" => Box::new(ActivationLayer::new(config)?),
+        "dropout" => Box::new(Dropout::new(config)),
+        "batch-norm" => Box::new(BatchNorm::new(config)),
+        "layer-norm" => Box::new(LayerNorm::new(config)),
+        "conv1d" => Box::new(Conv1D::new(config, initialization)?),
+        "max-pool1d" => Box::new(MaxPool1D::new(config)),
+        "avg-pool1d" => Box::new(AvgPool1D::new(config)),
+        "global-avg-pool1d" => Box::new(GlobalAvgPool1D::new(config)),
+        "conv2d" => Box::new(Conv2D::new(config, initialization)?),
+        "max-pool2d" => Box::new(MaxPool2D::new(config)),
+        "avg-pool2d" => Box::new(AvgPool2D::new(config)),
+        "flatten" => Box::new(Flatten::new(config)),
+        "rnn" => Box::new(Rnn::new(config, initialization)?),
+        "lstm" => Box::new(Lstm::new(config, initialization)),
+        "gru" => Box::new(Gru::new(config, initialization)),
+        "embedding" => Box::new(Embedding::new(config, initialization)?),
+        "positional-encoding" => Box::new(PositionalEncoding::new(config)),
+        "attention" => Box::new(MultiHeadAttention::new(config, initialization)?),
+        "transformer" => Box::new(Transformer::new(config, initialization)?),
+        _ => return Err(Error::UnknownLayer(config.kind.clone())),
+    };
+    Ok(layer)
+}
+
+/// Checks that `inputs` holds samples of `size` values, see
+/// `Layer::check_inputs`.
+pub(crate) fn check_width(inputs: &Matrix, size: usize) -> Result<(), Error> {
+    if inputs.cols != size {
+        return Err(Error::ShapeMismatch {
+            expected: size,
+            actual: inputs.cols,
+        });
+    }
+    Ok(())
+}
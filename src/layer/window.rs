@@ -0,0 +1,42 @@
+use crate::config::ConfigLayer;
+
+/// Positions covered by a kernel sliding along one axis of a signal.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Window {
+    /// Length of the unpadded signal.
+    pub size: usize,
+    pub kernel: usize,
+    pub stride: usize,
+    pub padding: usize,
+    pub dilation: usize,
+}
+
+impl Window {
+    pub fn new(config: &ConfigLayer, size: usize) -> Self {
+        Self {
+            size,
+            kernel: config.kernel_size,
+            stride: config.stride,
+            padding: config.padding,
+            dilation: config.dilation,
+        }
+    }
+
+    /// Number of kernel positions, zero if the kernel does not fit.
+    pub fn outputs(&self) -> usize {
+        let span = self.dilation * (self.kernel.max(1) - 1) + 1;
+        let padded = self.size + 2 * self.padding;
+        if self.stride == 0 || padded < span {
+            return 0;
+        }
+        (padded - span) / self.stride + 1
+    }
+
+    /// Signal position of tap `k` at output `output`, or `None` in the
+    /// padding.
+    pub fn position(&self, output: usize, k: usize) -> Option<usize> {
+        (output * self.stride + k * self.dilation)
+            .checked_sub(self.padding)
+            .filter(|p| *p < self.size)
+    }
+}
@@ -1,8 +1,19 @@
 pub mod activation;
 pub mod config;
+pub mod data;
+pub mod dataset;
+pub mod error;
+pub mod gradcheck;
 pub mod initialization;
 pub mod layer;
 pub mod logger;
 pub mod loss;
+pub mod matrix;
+pub mod metric;
 pub mod network;
 pub mod neuron;
+pub mod optimizer;
+pub mod preprocessing;
+pub mod regularization;
+pub mod schedule;
+pub mod tape;
@@ -4,6 +4,8 @@ use serde_json::json;
 use std::fs::OpenOptions;
 use std::{collections::HashMap, io::Write};
 
+use crate::error::Error;
+
 #[derive(Serialize)]
 struct LogEntry<T> {
     value: T,
@@ -25,13 +27,31 @@ impl Default for Logger {
 
 impl Logger {
     pub fn new() -> Self {
+        Self::try_new().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `new`, returning an error if the log directory cannot be
+    /// created.
+    pub fn try_new() -> Result<Self, Error> {
         let now = Utc::now().to_rfc3339();
         let path = format!("logs/{}", now);
-        std::fs::create_dir_all(&path).expect("unable to create logs directory");
-        Self { path }
+        std::fs::create_dir_all(&path)?;
+        Ok(Self { path })
+    }
+
+    /// Directory of the log files, one JSON entry per line and file per
+    /// entry type.
+    pub fn path(&self) -> &str {
+        &self.path
     }
 
-    pub fn errors(&mut self, epoch: usize, id: usize, layer: &str, errors: &[f64]) {
+    pub fn errors(
+        &mut self,
+        epoch: usize,
+        id: usize,
+        layer: &str,
+        errors: &[f64],
+    ) -> Result<(), Error> {
         let entry = LogEntry {
             epoch,
             value: errors.to_vec(),
@@ -42,10 +62,10 @@ impl Logger {
                 ("layer".to_string(), layer.to_string()),
             ]),
         };
-        self.log(entry);
+        self.log(entry)
     }
 
-    pub fn loss(&mut self, epoch: usize, id: usize, loss: f64) {
+    pub fn loss(&mut self, epoch: usize, id: usize, loss: f64) -> Result<(), Error> {
         let entry = LogEntry {
             epoch,
             value: loss,
@@ -53,10 +73,32 @@ impl Logger {
             exec_date: Utc::now().to_rfc3339(),
             tags: HashMap::from([("id".to_string(), id.to_string())]),
         };
-        self.log(entry);
+        self.log(entry)
+    }
+
+    pub fn validation_loss(&mut self, epoch: usize, loss: f64) -> Result<(), Error> {
+        let entry = LogEntry {
+            epoch,
+            value: loss,
+            entry_type: "validation_loss".to_string(),
+            exec_date: Utc::now().to_rfc3339(),
+            tags: HashMap::new(),
+        };
+        self.log(entry)
+    }
+
+    pub fn lr(&mut self, epoch: usize, id: usize, lr: f64) -> Result<(), Error> {
+        let entry = LogEntry {
+            epoch,
+            value: lr,
+            entry_type: "lr".to_string(),
+            exec_date: Utc::now().to_rfc3339(),
+            tags: HashMap::from([("id".to_string(), id.to_string())]),
+        };
+        self.log(entry)
     }
 
-    pub fn accuracy(&mut self, epoch: usize, id: usize, accuracy: f64) {
+    pub fn accuracy(&mut self, epoch: usize, id: usize, accuracy: f64) -> Result<(), Error> {
         let entry = LogEntry {
             epoch,
             value: accuracy,
@@ -64,10 +106,16 @@ impl Logger {
             exec_date: Utc::now().to_rfc3339(),
             tags: HashMap::from([("id".to_string(), id.to_string())]),
         };
-        self.log(entry);
+        self.log(entry)
     }
 
-    pub fn gradients(&mut self, epoch: usize, id: usize, layer: &str, gradients: &[f64]) {
+    pub fn gradients(
+        &mut self,
+        epoch: usize,
+        id: usize,
+        layer: &str,
+        gradients: &[f64],
+    ) -> Result<(), Error> {
         let entry = LogEntry {
             epoch,
             value: gradients.to_vec(),
@@ -78,10 +126,16 @@ impl Logger {
                 ("layer".to_string(), layer.to_string()),
             ]),
         };
-        self.log(entry);
+        self.log(entry)
     }
 
-    pub fn weights(&mut self, epoch: usize, id: usize, layer: &str, weights: Vec<Vec<f64>>) {
+    pub fn weights(
+        &mut self,
+        epoch: usize,
+        id: usize,
+        layer: &str,
+        weights: Vec<Vec<f64>>,
+    ) -> Result<(), Error> {
         let entry = LogEntry {
             epoch,
             value: weights,
@@ -92,17 +146,17 @@ impl Logger {
                 ("layer".to_string(), layer.to_string()),
             ]),
         };
-        self.log(entry);
+        self.log(entry)
     }
 
-    fn log<T: Serialize>(&mut self, entry: LogEntry<T>) {
+    fn log<T: Serialize>(&mut self, entry: LogEntry<T>) -> Result<(), Error> {
         let file_name = format!("{}/{}.jsonl", self.path, entry.entry_type);
         let mut file = OpenOptions::new()
             .create(true)
             .append(true)
-            .open(file_name)
-            .expect("unable to open log file");
+            .open(file_name)?;
 
-        writeln!(file, "{}", json!(entry)).expect("Unable to write to log file");
+        writeln!(file, "{}", json!(entry))?;
+        Ok(())
     }
 }
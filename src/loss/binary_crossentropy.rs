@@ -2,6 +2,9 @@ use std::f64;
 
 use crate::loss::Loss;
 
+/// Binary cross-entropy over independent probabilities, usually the outputs of
+/// a sigmoid. Chained through the sigmoid, its gradient becomes `p - t` with
+/// respect to the logits.
 pub struct BinaryCrossEntropy;
 
 impl Loss for BinaryCrossEntropy {
@@ -20,7 +23,10 @@ impl Loss for BinaryCrossEntropy {
         targets
             .iter()
             .zip(outputs.iter())
-            .map(|(t, p)| p - t)
+            .map(|(t, p)| {
+                let p = p.clamp(f64::EPSILON, 1.0 - f64::EPSILON);
+                (p - t) / (p * (1.0 - p))
+            })
             .collect()
     }
 }
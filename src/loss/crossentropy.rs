@@ -2,6 +2,9 @@ use std::f64;
 
 use crate::loss::Loss;
 
+/// Categorical cross-entropy over probabilities, usually the outputs of a
+/// softmax. Chained through the softmax, its gradient becomes `p - t` with
+/// respect to the logits.
 pub struct CrossEntropy;
 
 impl Loss for CrossEntropy {
@@ -20,7 +23,7 @@ impl Loss for CrossEntropy {
         targets
             .iter()
             .zip(outputs.iter())
-            .map(|(t, p)| (p - t))
+            .map(|(t, p)| -t / p.clamp(f64::EPSILON, 1.0 - f64::EPSILON))
             .collect()
     }
 }
@@ -1,4 +1,8 @@
 pub trait Loss {
     fn function(&self, outputs: &[f64], targets: &[f64]) -> f64;
+
+    /// Gradient with respect to `outputs`, i.e. the activated outputs of the
+    /// last layer. The network chains it through the activation's `backward`
+    /// to get the gradient with respect to the pre-activations.
     fn gradient(&self, outputs: &[f64], targets: &[f64]) -> Vec<f64>;
 }
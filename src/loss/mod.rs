@@ -8,4 +8,4 @@ pub use crate::loss::binary_crossentropy::BinaryCrossEntropy;
 pub use crate::loss::crossentropy::CrossEntropy;
 pub use crate::loss::loss_impl::Loss;
 pub use crate::loss::mse::Mse;
-pub use crate::loss::utils::get_loss;
+pub use crate::loss::utils::{get_loss, LOSSES};
@@ -1,12 +1,17 @@
 use std::collections::HashMap;
 
+use crate::error::Error;
 use crate::loss::*;
 
-pub fn get_loss(name: &str, _: HashMap<String, f64>) -> Box<dyn Loss> {
-    match name {
+/// Names accepted by `get_loss`.
+pub const LOSSES: [&str; 3] = ["mse", "crossentropy", "binary-crossentropy"];
+
+pub fn get_loss(name: &str, _: HashMap<String, f64>) -> Result<Box<dyn Loss>, Error> {
+    let loss: Box<dyn Loss> = match name {
         "mse" => Box::new(Mse {}),
         "crossentropy" => Box::new(CrossEntropy {}),
         "binary-crossentropy" => Box::new(BinaryCrossEntropy {}),
-        _ => panic!("loss function should be mse"),
-    }
+        _ => return Err(Error::UnknownLoss(name.to_string())),
+    };
+    Ok(loss)
 }
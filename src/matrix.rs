@@ -0,0 +1,131 @@
+use serde::{Deserialize, Serialize};
+
+/// Dense row-major matrix.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct Matrix {
+    pub rows: usize,
+    pub cols: usize,
+    pub data: Vec<f64>,
+}
+
+impl Matrix {
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        Self {
+            rows,
+            cols,
+            data: vec![0.0; rows * cols],
+        }
+    }
+
+    /// Stacks `rows`, which must all have the same length.
+    pub fn from_rows<'a>(rows: impl IntoIterator<Item = &'a [f64]>) -> Self {
+        let mut matrix = Self::default();
+        for row in rows {
+            assert!(
+                matrix.rows == 0 || row.len() == matrix.cols,
+                "row {} has {} values, expected {}",
+                matrix.rows,
+                row.len(),
+                matrix.cols
+            );
+            matrix.cols = row.len();
+            matrix.rows += 1;
+            matrix.data.extend_from_slice(row);
+        }
+        matrix
+    }
+
+    pub fn row(&self, i: usize) -> &[f64] {
+        &self.data[i * self.cols..(i + 1) * self.cols]
+    }
+
+    pub fn row_mut(&mut self, i: usize) -> &mut [f64] {
+        &mut self.data[i * self.cols..(i + 1) * self.cols]
+    }
+
+    /// Same values with `cols` values per row.
+    pub fn reshape(&self, cols: usize) -> Matrix {
+        assert!(
+            self.data.len().is_multiple_of(cols),
+            "matrix shapes do not match"
+        );
+        Matrix {
+            rows: self.data.len() / cols,
+            cols,
+            data: self.data.clone(),
+        }
+    }
+
+    pub fn to_rows(&self) -> Vec<Vec<f64>> {
+        (0..self.rows).map(|i| self.row(i).to_vec()).collect()
+    }
+
+    /// `self · x`
+    pub fn matvec(&self, x: &[f64]) -> Vec<f64> {
+        assert_eq!(self.cols, x.len(), "matrix columns != vector length");
+        (0..self.rows)
+            .map(|i| self.row(i).iter().zip(x.iter()).map(|(a, b)| a * b).sum())
+            .collect()
+    }
+
+    /// `selfᵀ · x`
+    pub fn transpose_matvec(&self, x: &[f64]) -> Vec<f64> {
+        assert_eq!(self.rows, x.len(), "matrix rows != vector length");
+        let mut output = vec![0.0; self.cols];
+        for (i, x) in x.iter().enumerate() {
+            output
+                .iter_mut()
+                .zip(self.row(i).iter())
+                .for_each(|(o, a)| *o += a * x);
+        }
+        output
+    }
+
+    /// `self · other`
+    pub fn matmul(&self, other: &Matrix) -> Matrix {
+        assert_eq!(self.cols, other.rows, "matrix shapes do not match");
+        let mut output = Matrix::zeros(self.rows, other.cols);
+        for i in 0..self.rows {
+            let row = output.row_mut(i);
+            for (k, a) in self.row(i).iter().enumerate() {
+                row.iter_mut()
+                    .zip(other.row(k).iter())
+                    .for_each(|(o, b)| *o += a * b);
+            }
+        }
+        output
+    }
+
+    /// `self · otherᵀ`
+    pub fn matmul_transpose(&self, other: &Matrix) -> Matrix {
+        assert_eq!(self.cols, other.cols, "matrix shapes do not match");
+        let mut output = Matrix::zeros(self.rows, other.rows);
+        for i in 0..self.rows {
+            let a = self.row(i);
+            for j in 0..other.rows {
+                output.data[i * other.rows + j] =
+                    a.iter().zip(other.row(j).iter()).map(|(a, b)| a * b).sum();
+            }
+        }
+        output
+    }
+
+    /// `self += aᵀ · b`
+    pub fn add_transpose_matmul(&mut self, a: &Matrix, b: &Matrix) {
+        assert_eq!(a.rows, b.rows, "matrix shapes do not match");
+        assert_eq!(
+            (self.rows, self.cols),
+            (a.cols, b.cols),
+            "matrix shapes do not match"
+        );
+        for k in 0..a.rows {
+            let b_row = b.row(k);
+            for (i, a) in a.row(k).iter().enumerate() {
+                self.row_mut(i)
+                    .iter_mut()
+                    .zip(b_row.iter())
+                    .for_each(|(o, b)| *o += a * b);
+            }
+        }
+    }
+}
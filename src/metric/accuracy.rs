@@ -0,0 +1,26 @@
+use crate::metric::Metric;
+
+/// Single outputs are thresholded at 0.5, multiple outputs are compared by
+/// their largest value.
+pub struct Accuracy;
+
+fn argmax(x: &[f64]) -> usize {
+    x.iter()
+        .enumerate()
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map_or(0, |(i, _)| i)
+}
+
+impl Metric for Accuracy {
+    fn function(&self, outputs: &[f64], targets: &[f64]) -> f64 {
+        let correct = match (outputs, targets) {
+            ([o], [t]) => (*o >= 0.5) == (*t >= 0.5),
+            _ => argmax(outputs) == argmax(targets),
+        };
+        if correct {
+            1.0
+        } else {
+            0.0
+        }
+    }
+}
@@ -0,0 +1,15 @@
+use crate::metric::Metric;
+
+pub struct Mae;
+
+impl Metric for Mae {
+    fn function(&self, outputs: &[f64], targets: &[f64]) -> f64 {
+        let n = outputs.len() as f64;
+        outputs
+            .iter()
+            .zip(targets.iter())
+            .map(|(o, t)| (o - t).abs())
+            .sum::<f64>()
+            / n
+    }
+}
@@ -0,0 +1,4 @@
+pub trait Metric {
+    /// Score of a single sample, averaged over a dataset by `Network::evaluate`.
+    fn function(&self, outputs: &[f64], targets: &[f64]) -> f64;
+}
@@ -0,0 +1,9 @@
+pub mod accuracy;
+pub mod mae;
+pub mod metric_impl;
+pub mod utils;
+
+pub use crate::metric::accuracy::Accuracy;
+pub use crate::metric::mae::Mae;
+pub use crate::metric::metric_impl::Metric;
+pub use crate::metric::utils::{get_metric, METRICS};
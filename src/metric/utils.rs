@@ -0,0 +1,14 @@
+use crate::error::Error;
+use crate::metric::*;
+
+/// Names accepted by `get_metric`.
+pub const METRICS: [&str; 2] = ["accuracy", "mae"];
+
+pub fn get_metric(name: &str) -> Result<Box<dyn Metric>, Error> {
+    let metric: Box<dyn Metric> = match name {
+        "accuracy" => Box::new(Accuracy {}),
+        "mae" => Box::new(Mae {}),
+        _ => return Err(Error::UnknownMetric(name.to_string())),
+    };
+    Ok(metric)
+}
@@ -1,76 +1,562 @@
+use std::borrow::Cow;
 use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+
+use rand::seq::SliceRandom;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
 
 use crate::config::Config;
-use crate::initialization::{get_initialization, Initialization};
-use crate::layer::Layer;
+use crate::dataset::{Dataset, Sequence};
+use crate::error::Error;
+use crate::initialization::{get_initialization, get_rng, Initialization};
+use crate::layer::{get_layer, Dense, Layer, Mode};
 use crate::logger::Logger;
 use crate::loss::{get_loss, Loss};
+use crate::matrix::Matrix;
+use crate::metric::{get_metric, Metric};
+use crate::optimizer::{get_optimizer, Optimizer};
+use crate::preprocessing::Pipeline;
+use crate::schedule::{get_schedule, Constant, Schedule};
+use crate::tape::Tape;
+
+/// Parameter groups and buffers of a layer.
+#[derive(Serialize, Deserialize, Clone)]
+struct LayerState {
+    parameters: Vec<Vec<f64>>,
+    /// Shape of each parameter group, empty in files saved before shapes
+    /// were recorded.
+    #[serde(default)]
+    shapes: Vec<Vec<usize>>,
+    #[serde(default)]
+    buffers: Vec<Vec<f64>>,
+}
+
+/// Checks that saved groups match the shapes of the layer's groups.
+fn check_shapes(groups: &[&[f64]], saved: &[Vec<f64>]) -> Result<(), Error> {
+    if saved.len() != groups.len() {
+        return Err(Error::ShapeMismatch {
+            expected: groups.len(),
+            actual: saved.len(),
+        });
+    }
+    for (group, saved) in groups.iter().zip(saved.iter()) {
+        if saved.len() != group.len() {
+            return Err(Error::ShapeMismatch {
+                expected: group.len(),
+                actual: saved.len(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Checks that saved parameter shapes match the layer's dimension by
+/// dimension, so that e.g. transposed weights of the same length are
+/// rejected.
+fn check_dims(shapes: &[Vec<usize>], saved: &[Vec<usize>]) -> Result<(), Error> {
+    if saved.len() != shapes.len() {
+        return Err(Error::ShapeMismatch {
+            expected: shapes.len(),
+            actual: saved.len(),
+        });
+    }
+    for (shape, saved) in shapes.iter().zip(saved.iter()) {
+        if saved.len() != shape.len() {
+            return Err(Error::ShapeMismatch {
+                expected: shape.len(),
+                actual: saved.len(),
+            });
+        }
+        if let Some((&expected, &actual)) = shape.iter().zip(saved.iter()).find(|(e, a)| e != a) {
+            return Err(Error::ShapeMismatch { expected, actual });
+        }
+    }
+    Ok(())
+}
+
+#[derive(Serialize, Deserialize)]
+struct NetworkState {
+    config: Config,
+    #[serde(default)]
+    pipeline: Pipeline,
+    layers: Vec<LayerState>,
+}
+
+/// Mean loss and metrics of a network over a dataset.
+pub struct EvalReport {
+    pub loss: f64,
+    pub metrics: HashMap<String, f64>,
+}
 
 pub struct Network {
     pub config: Config,
     pub logger: Logger,
-    pub layers: Vec<Layer>,
+    pub layers: Vec<Box<dyn Layer>>,
     pub loss: Box<dyn Loss>,
+    pub optimizer: Box<dyn Optimizer>,
+    pub schedule: Box<dyn Schedule>,
+    pub metrics: Vec<Box<dyn Metric>>,
     pub initialization: Box<dyn Initialization>,
+    /// Preprocessing fitted by the first call to `train` or `fit` and applied
+    /// to the data given to `train`, `fit`, `evaluate` and `forward`.
+    pub pipeline: Pipeline,
+    mode: Mode,
+    /// Source of the dropout masks.
+    rng: Box<dyn RngCore>,
+    tape: Tape,
 }
 
 impl Network {
     pub fn new(config: Config) -> Self {
-        let mut initialization = get_initialization(&config.initialization);
+        Self::try_new(config).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_new(config: Config) -> Result<Self, Error> {
+        config.validate()?;
+        let mut initialization = get_initialization(&config.initialization)?;
         let layers = config
             .layers
             .iter()
-            .map(|c| Layer::new(c, &mut initialization))
-            .collect();
-        Self {
+            .map(|c| get_layer(c, &mut initialization))
+            .collect::<Result<Vec<Box<dyn Layer>>, Error>>()?;
+        Ok(Self {
+            rng: get_rng(config.initialization.seed),
             initialization,
-            logger: Logger::new(),
-            config: config.clone(),
-            loss: get_loss(config.loss.as_str(), HashMap::new()),
+            logger: Logger::try_new()?,
+            loss: get_loss(config.loss.as_str(), HashMap::new())?,
+            optimizer: get_optimizer(config.optimizer.as_str(), config.optimizer_params.clone())?,
+            schedule: match &config.schedule {
+                Some(schedule) => get_schedule(&schedule.method, schedule.params.clone())?,
+                None => Box::new(Constant {}),
+            },
+            metrics: config
+                .metrics
+                .iter()
+                .map(|m| get_metric(m))
+                .collect::<Result<Vec<Box<dyn Metric>>, Error>>()?,
+            config,
             layers,
-        }
+            pipeline: Pipeline::default(),
+            mode: Mode::default(),
+            tape: Tape::default(),
+        })
+    }
+
+    /// The `i`-th layer if it is a `Dense` layer.
+    pub fn dense(&self, i: usize) -> Option<&Dense> {
+        self.layers.get(i)?.as_dense()
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// Mode of the training passes (`forward_cached`, `accumulate`,
+    /// `backward`). `train` and `fit` run in `Train` mode and restore the
+    /// previous mode when done. `forward`, `forward_batch` and `evaluate`
+    /// are inference and always run in `Eval` mode, whatever the mode is.
+    pub fn set_mode(&mut self, mode: Mode) {
+        self.mode = mode;
+    }
+
+    /// Switches the training passes to `Train` mode, e.g. dropout masks on.
+    /// Named apart from `train`, which trains on a dataset.
+    pub fn train_mode(&mut self) {
+        self.set_mode(Mode::Train);
+    }
+
+    /// Switches the training passes to `Eval` mode, the same pass as
+    /// `forward`.
+    pub fn eval_mode(&mut self) {
+        self.set_mode(Mode::Eval);
     }
 
     pub fn forward(&self, inputs: &[f64]) -> Vec<f64> {
-        self.layers
-            .iter()
-            .fold(inputs.to_owned(), |outputs, l| l.forward(&outputs))
+        self.try_forward(inputs).unwrap_or_else(|e| panic!("{}", e))
     }
 
+    /// Like `forward`, returning an error if the pipeline cannot be applied
+    /// or the first layer rejects the inputs.
+    pub fn try_forward(&self, inputs: &[f64]) -> Result<Vec<f64>, Error> {
+        Ok(self.try_forward_batch(&Matrix::from_rows([inputs]))?.data)
+    }
+
+    /// Forward pass over a sequence of input vectors, split into the output
+    /// vector of each step, a single one for layers that only output their
+    /// last state.
+    pub fn forward_sequence(&self, sequence: &[Vec<f64>]) -> Vec<Vec<f64>> {
+        let outputs = self.forward(&sequence.concat());
+        let size = self
+            .layers
+            .last()
+            .and_then(|l| l.output_shape().last().copied())
+            .unwrap_or(outputs.len());
+        outputs.chunks(size.max(1)).map(|c| c.to_vec()).collect()
+    }
+
+    /// Outputs of every layer, before the targets pipeline is inverted.
+    ///
+    /// Panics if the pipeline cannot be applied to `inputs`.
     pub fn activations(&self, inputs: &[f64]) -> Vec<Vec<f64>> {
-        let mut output = inputs.to_vec();
+        let mut output = self
+            .preprocess(&Matrix::from_rows([inputs]))
+            .unwrap_or_else(|e| panic!("{}", e))
+            .into_owned();
         let mut outputs: Vec<Vec<f64>> = Vec::new();
 
         self.layers.iter().for_each(|l| {
             output = l.forward(&output);
-            outputs.push(output.clone());
+            outputs.push(output.data.clone());
         });
 
         outputs
     }
 
+    /// Forward pass over a batch, one sample per row. The input steps of the
+    /// pipeline are applied to `inputs` and its target scalers are inverted
+    /// on the outputs.
+    pub fn forward_batch(&self, inputs: &Matrix) -> Matrix {
+        self.try_forward_batch(inputs)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `forward_batch`, returning an error if the pipeline cannot be
+    /// applied or the first layer rejects the inputs, see
+    /// `Layer::check_inputs`.
+    pub fn try_forward_batch(&self, inputs: &Matrix) -> Result<Matrix, Error> {
+        let inputs = self.preprocess(inputs)?;
+        self.check_inputs(&inputs)?;
+        let outputs = self.propagate(&inputs);
+        Ok(self.pipeline.inverse_targets(&outputs)?.into_owned())
+    }
+
+    /// Checks `inputs` against the first layer, e.g. the token indices of an
+    /// embedding.
+    fn check_inputs(&self, inputs: &Matrix) -> Result<(), Error> {
+        self.layers
+            .first()
+            .map_or(Ok(()), |l| l.check_inputs(inputs))
+    }
+
+    fn propagate(&self, inputs: &Matrix) -> Matrix {
+        self.layers
+            .iter()
+            .fold(inputs.clone(), |outputs, l| l.forward(&outputs))
+    }
+
+    /// Applies the input steps of the pipeline.
+    fn preprocess<'a>(&self, inputs: &'a Matrix) -> Result<Cow<'a, Matrix>, Error> {
+        self.pipeline.transform_inputs(inputs)
+    }
+
+    /// Fits the pipeline on `dataset` if it is not fitted yet and applies it.
+    fn prepare<'a>(&mut self, dataset: &'a Dataset) -> Result<Cow<'a, Dataset>, Error> {
+        if !self.pipeline.fitted {
+            self.pipeline.fit(dataset)?;
+        }
+        self.pipeline.transform(dataset)
+    }
+
+    /// Forward pass over a batch recording every layer's inputs,
+    /// pre-activations and outputs into `tape`.
+    pub fn forward_cached(&mut self, inputs: &Matrix, tape: &mut Tape) {
+        tape.caches.resize_with(self.layers.len(), Default::default);
+        for (i, layer) in self.layers.iter_mut().enumerate() {
+            let (previous, next) = tape.caches.split_at_mut(i);
+            let inputs = previous.last().map_or(inputs, |c| &c.outputs);
+            layer.forward_cached(inputs, &mut next[0], self.mode, self.rng.as_mut());
+        }
+    }
+
     pub fn backward(&mut self, lr: f64, inputs: &[f64], targets: &[f64]) {
+        self.accumulate(inputs, targets);
+        self.update(lr, 1);
+    }
+
+    /// Backpropagates one sample, accumulating gradients without updating
+    /// weights. Returns the loss of the sample, including the regularization
+    /// penalty.
+    pub fn accumulate(&mut self, inputs: &[f64], targets: &[f64]) -> f64 {
+        self.accumulate_batch(&Matrix::from_rows([inputs]), &Matrix::from_rows([targets]))
+    }
+
+    /// Backpropagates a batch, one sample per row, accumulating gradients
+    /// without updating weights. Returns the summed loss of the batch, the
+    /// regularization penalty being added to the loss of every sample.
+    pub fn accumulate_batch(&mut self, inputs: &Matrix, targets: &Matrix) -> f64 {
+        let mut tape = std::mem::take(&mut self.tape);
+        self.forward_cached(inputs, &mut tape);
+        let penalty = self.penalty();
+        let loss = tape.outputs().map_or(0.0, |outputs| {
+            (0..outputs.rows)
+                .map(|i| self.loss.function(outputs.row(i), targets.row(i)) + penalty)
+                .sum()
+        });
+        self.backward_cached(&tape, targets);
+        self.tape = tape;
+        loss
+    }
+
+    /// Backward pass from the values recorded by `forward_cached`.
+    pub fn backward_cached(&mut self, tape: &Tape, targets: &Matrix) {
         // https://en.wikipedia.org/wiki/Backpropagation
 
-        let activations = self.activations(inputs);
+        let Some(outputs) = tape.outputs() else {
+            return;
+        };
+        let mut output_grads = Matrix::zeros(outputs.rows, outputs.cols);
+        for i in 0..outputs.rows {
+            let gradient = self.loss.gradient(outputs.row(i), targets.row(i));
+            output_grads.row_mut(i).copy_from_slice(&gradient);
+        }
+        for (layer, cache) in self.layers.iter_mut().zip(tape.caches.iter()).rev() {
+            output_grads = layer.backward(cache, &output_grads);
+        }
+    }
+
+    /// Regularization penalty of all layers, added to the training loss.
+    pub fn penalty(&self) -> f64 {
+        self.layers.iter().map(|l| l.penalty()).sum()
+    }
+
+    /// Applies the gradients accumulated over `batch_size` samples.
+    pub fn update(&mut self, lr: f64, batch_size: usize) {
+        for (i, layer) in self.layers.iter_mut().enumerate() {
+            layer.update(lr, self.optimizer.as_mut(), i, batch_size);
+        }
+    }
 
-        let output = activations.last().unwrap();
-        let mut output_grad = self.loss.gradient(output, targets);
+    pub fn train(&mut self, dataset: &Dataset) {
+        self.try_train(dataset).unwrap_or_else(|e| panic!("{}", e))
+    }
 
-        for (i, layer) in self.layers.iter_mut().enumerate().rev() {
-            let input = if i == 0 { inputs } else { &activations[i - 1] };
-            output_grad = layer.backward(lr, input, &output_grad);
+    /// Like `train`, returning an error if the pipeline cannot be applied,
+    /// the first layer rejects the inputs or the training log cannot be
+    /// written.
+    pub fn try_train(&mut self, dataset: &Dataset) -> Result<(), Error> {
+        if self.schedule.observes() {
+            return Err(Error::Validation(
+                "schedule follows the validation loss, train with fit".to_string(),
+            ));
         }
+        let dataset = self.prepare(dataset)?;
+        self.check_inputs(&dataset.inputs)?;
+        let mode = std::mem::replace(&mut self.mode, Mode::Train);
+        let result =
+            (0..self.config.epochs).try_for_each(|epoch| self.train_epoch(epoch, &dataset));
+        self.mode = mode;
+        result
+    }
+
+    /// Trains on sequences of input and target vectors, see
+    /// `Dataset::from_sequences`.
+    pub fn train_sequences(&mut self, sequences: &[Sequence]) {
+        self.train(&Dataset::from_sequences(sequences));
     }
 
-    pub fn train(&mut self, dataset: &[(Vec<f64>, Vec<f64>)]) {
+    fn train_epoch(&mut self, epoch: usize, dataset: &Dataset) -> Result<(), Error> {
+        let order = self.order(epoch, dataset.len());
+        let batches = dataset.len().div_ceil(self.config.batch_size);
+        for (id, indices) in order.chunks(self.config.batch_size).enumerate() {
+            let (inputs, targets) = dataset.batch(indices);
+            let loss = self.accumulate_batch(&inputs, &targets);
+            let lr = self
+                .schedule
+                .lr(self.config.lr, epoch, epoch * batches + id);
+            self.update(lr, indices.len());
+            self.logger.loss(epoch, id, loss / indices.len() as f64)?;
+            self.logger.lr(epoch, id, lr)?;
+        }
+        Ok(())
+    }
+
+    /// Order in which the samples are visited during `epoch`. When
+    /// `config.shuffle` is set, a permutation seeded from the
+    /// initialization seed and the epoch, so seeded runs are reproducible.
+    fn order(&self, epoch: usize, len: usize) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..len).collect();
+        if self.config.shuffle {
+            let seed = self
+                .config
+                .initialization
+                .seed
+                .map(|s| s ^ (epoch as u64 + 1).wrapping_mul(0x9e37_79b9_7f4a_7c15));
+            order.shuffle(&mut get_rng(seed));
+        }
+        order
+    }
+
+    /// Trains on `train` and evaluates on `validation` after every epoch.
+    /// When `config.patience` is set, stops once the validation loss has not
+    /// improved by more than `config.min_delta` for `patience` epochs and
+    /// restores the weights of the best epoch. Returns one report per epoch.
+    pub fn fit(&mut self, train: &Dataset, validation: &Dataset) -> Vec<EvalReport> {
+        self.try_fit(train, validation)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `fit`, returning an error if the pipeline cannot be applied, the
+    /// first layer rejects the inputs or the training log cannot be written.
+    pub fn try_fit(
+        &mut self,
+        train: &Dataset,
+        validation: &Dataset,
+    ) -> Result<Vec<EvalReport>, Error> {
+        let train = self.prepare(train)?;
+        let validation = self.pipeline.transform(validation)?;
+        self.check_inputs(&train.inputs)?;
+        self.check_inputs(&validation.inputs)?;
+        let mode = std::mem::replace(&mut self.mode, Mode::Train);
+        let result = self.fit_epochs(&train, &validation);
+        self.mode = mode;
+        result
+    }
+
+    fn fit_epochs(
+        &mut self,
+        train: &Dataset,
+        validation: &Dataset,
+    ) -> Result<Vec<EvalReport>, Error> {
+        let mut reports = Vec::new();
+        let mut best: Option<(f64, Vec<LayerState>)> = None;
+        let mut waiting = 0;
+
         for epoch in 0..self.config.epochs {
-            for (id, (inputs, targets)) in dataset.iter().enumerate() {
-                let outputs = self.forward(inputs);
-                let loss = self.loss.function(&outputs, targets);
-                self.backward(self.config.lr, inputs, targets);
-                self.logger.loss(epoch, id, loss);
+            self.train_epoch(epoch, train)?;
+            let report = self.evaluate_prepared(validation);
+            self.logger.validation_loss(epoch, report.loss)?;
+            self.schedule.observe(report.loss);
+
+            // a NaN loss never improves, so a diverging run stops early
+            let improved = match &best {
+                Some((loss, _)) => report.loss <= loss - self.config.min_delta,
+                None => !report.loss.is_nan(),
+            };
+            if improved {
+                waiting = 0;
+                best = Some((report.loss, self.state()));
+            } else {
+                waiting += 1;
+            }
+            reports.push(report);
+
+            if self.config.patience.is_some_and(|p| waiting >= p) {
+                break;
             }
         }
+
+        if let (Some(_), Some((_, state))) = (self.config.patience, best) {
+            self.restore(state);
+        }
+        Ok(reports)
+    }
+
+    /// Mean loss and configured metrics over `dataset`.
+    pub fn evaluate(&self, dataset: &Dataset) -> EvalReport {
+        self.try_evaluate(dataset)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `evaluate`, returning an error if the pipeline cannot be applied
+    /// or the first layer rejects the inputs.
+    pub fn try_evaluate(&self, dataset: &Dataset) -> Result<EvalReport, Error> {
+        let dataset = self.pipeline.transform(dataset)?;
+        self.check_inputs(&dataset.inputs)?;
+        Ok(self.evaluate_prepared(&dataset))
+    }
+
+    fn evaluate_prepared(&self, dataset: &Dataset) -> EvalReport {
+        let mut loss = 0.0;
+        let mut metrics = vec![0.0; self.metrics.len()];
+        let order: Vec<usize> = (0..dataset.len()).collect();
+        for indices in order.chunks(self.config.batch_size) {
+            let (inputs, targets) = dataset.batch(indices);
+            let outputs = self.propagate(&inputs);
+            for i in 0..outputs.rows {
+                loss += self.loss.function(outputs.row(i), targets.row(i));
+                for (total, metric) in metrics.iter_mut().zip(self.metrics.iter()) {
+                    *total += metric.function(outputs.row(i), targets.row(i));
+                }
+            }
+        }
+
+        let n = dataset.len().max(1) as f64;
+        EvalReport {
+            loss: loss / n,
+            metrics: self
+                .config
+                .metrics
+                .iter()
+                .zip(metrics)
+                .map(|(name, total)| (name.clone(), total / n))
+                .collect(),
+        }
+    }
+
+    fn state(&self) -> Vec<LayerState> {
+        self.layers
+            .iter()
+            .map(|l| LayerState {
+                parameters: l.parameters().iter().map(|p| p.to_vec()).collect(),
+                shapes: l.shapes(),
+                buffers: l.buffers().iter().map(|b| b.to_vec()).collect(),
+            })
+            .collect()
+    }
+
+    fn restore(&mut self, state: Vec<LayerState>) {
+        for (layer, saved) in self.layers.iter_mut().zip(state) {
+            for ((params, _), saved) in layer.parameters_mut().into_iter().zip(saved.parameters) {
+                params.copy_from_slice(&saved);
+            }
+            for (buffer, saved) in layer.buffers_mut().into_iter().zip(saved.buffers) {
+                buffer.copy_from_slice(&saved);
+            }
+        }
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), Error> {
+        let state = NetworkState {
+            config: self.config.clone(),
+            pipeline: self.pipeline.clone(),
+            layers: self.state(),
+        };
+        let file = BufWriter::new(File::create(path)?);
+        serde_json::to_writer(file, &state)?;
+        Ok(())
+    }
+
+    pub fn load(path: &str) -> Result<Self, Error> {
+        let file = BufReader::new(File::open(path)?);
+        let state: NetworkState = serde_json::from_reader(file)?;
+        if state.layers.len() != state.config.layers.len() {
+            return Err(Error::ShapeMismatch {
+                expected: state.config.layers.len(),
+                actual: state.layers.len(),
+            });
+        }
+
+        // the saved weights replace any pre-trained ones, so embedding files
+        // are not read again
+        let mut config = state.config.clone();
+        config
+            .layers
+            .iter_mut()
+            .for_each(|layer| layer.embeddings = None);
+        let mut network = Self::try_new(config)?;
+        for (layer, saved) in network.layers.iter().zip(state.layers.iter()) {
+            check_shapes(&layer.parameters(), &saved.parameters)?;
+            if !saved.shapes.is_empty() {
+                check_dims(&layer.shapes(), &saved.shapes)?;
+            }
+            check_shapes(&layer.buffers(), &saved.buffers)?;
+        }
+        network.restore(state.layers);
+        network.pipeline = state.pipeline;
+        network.config = state.config;
+
+        Ok(network)
     }
 }
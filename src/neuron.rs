@@ -1,5 +1,10 @@
+use serde::{Deserialize, Serialize};
+
 use crate::initialization::Initialization;
 
+/// Weights and bias of one unit, e.g. a row of a `Dense` layer, see
+/// `Dense::neurons`. Training goes through the layers.
+#[derive(Serialize, Deserialize, Clone)]
 pub struct Neuron {
     pub weights: Vec<f64>,
     pub bias: f64,
@@ -8,8 +13,8 @@ pub struct Neuron {
 impl Neuron {
     pub fn new(size: usize, initialization: &mut Box<dyn Initialization>) -> Self {
         Self {
-            bias: initialization.init(),
-            weights: (0..size).map(|_| initialization.init()).collect(),
+            bias: initialization.bias(size, 1),
+            weights: (0..size).map(|_| initialization.init(size, 1)).collect(),
         }
     }
 
@@ -26,18 +31,4 @@ impl Neuron {
             .sum::<f64>()
             + self.bias
     }
-
-    pub fn backward(&mut self, inputs: &[f64], output_grad: f64, lr: f64) -> Vec<f64> {
-        let mut input_grad = vec![0.0; inputs.len()];
-
-        let len = inputs.len();
-        for i in 0..len {
-            input_grad[i] = self.weights[i] * output_grad;
-            self.weights[i] -= lr * inputs[i] * output_grad;
-        }
-
-        self.bias -= lr * output_grad;
-
-        input_grad
-    }
 }
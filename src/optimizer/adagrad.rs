@@ -0,0 +1,33 @@
+use std::collections::HashMap;
+
+use itertools::izip;
+
+use crate::optimizer::Optimizer;
+
+pub struct AdaGrad {
+    pub epsilon: f64,
+    squares: HashMap<(usize, usize), Vec<f64>>,
+}
+
+impl AdaGrad {
+    pub fn new(params: HashMap<String, f64>) -> Self {
+        Self {
+            epsilon: *params.get("epsilon").unwrap_or(&1e-10),
+            squares: HashMap::new(),
+        }
+    }
+}
+
+impl Optimizer for AdaGrad {
+    fn update(&mut self, id: (usize, usize), params: &mut [f64], grads: &[f64], lr: f64) {
+        let squares = self
+            .squares
+            .entry(id)
+            .or_insert_with(|| vec![0.0; params.len()]);
+
+        for (p, g, s) in izip!(params.iter_mut(), grads.iter(), squares.iter_mut()) {
+            *s += g * g;
+            *p -= lr * g / (s.sqrt() + self.epsilon);
+        }
+    }
+}
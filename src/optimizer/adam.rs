@@ -0,0 +1,55 @@
+use std::collections::HashMap;
+
+use itertools::izip;
+
+use crate::optimizer::Optimizer;
+
+pub struct AdamState {
+    pub step: i32,
+    pub first_moments: Vec<f64>,
+    pub second_moments: Vec<f64>,
+}
+
+pub struct Adam {
+    pub beta1: f64,
+    pub beta2: f64,
+    pub epsilon: f64,
+    states: HashMap<(usize, usize), AdamState>,
+}
+
+impl Adam {
+    pub fn new(params: HashMap<String, f64>) -> Self {
+        Self {
+            beta1: *params.get("beta1").unwrap_or(&0.9),
+            beta2: *params.get("beta2").unwrap_or(&0.999),
+            epsilon: *params.get("epsilon").unwrap_or(&1e-8),
+            states: HashMap::new(),
+        }
+    }
+}
+
+impl Optimizer for Adam {
+    fn update(&mut self, id: (usize, usize), params: &mut [f64], grads: &[f64], lr: f64) {
+        let state = self.states.entry(id).or_insert_with(|| AdamState {
+            step: 0,
+            first_moments: vec![0.0; params.len()],
+            second_moments: vec![0.0; params.len()],
+        });
+        state.step += 1;
+
+        let correction1 = 1.0 - self.beta1.powi(state.step);
+        let correction2 = 1.0 - self.beta2.powi(state.step);
+        for (p, g, m, v) in izip!(
+            params.iter_mut(),
+            grads.iter(),
+            state.first_moments.iter_mut(),
+            state.second_moments.iter_mut()
+        ) {
+            *m = self.beta1 * *m + (1.0 - self.beta1) * g;
+            *v = self.beta2 * *v + (1.0 - self.beta2) * g * g;
+            let m_hat = *m / correction1;
+            let v_hat = *v / correction2;
+            *p -= lr * m_hat / (v_hat.sqrt() + self.epsilon);
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use std::collections::HashMap;
+
+use crate::optimizer::{Adam, Optimizer};
+
+/// Adam with decoupled weight decay, see https://arxiv.org/abs/1711.05101
+pub struct AdamW {
+    pub adam: Adam,
+    pub weight_decay: f64,
+}
+
+impl AdamW {
+    pub fn new(params: HashMap<String, f64>) -> Self {
+        Self {
+            weight_decay: *params.get("weight_decay").unwrap_or(&0.01),
+            adam: Adam::new(params),
+        }
+    }
+}
+
+impl Optimizer for AdamW {
+    fn update(&mut self, id: (usize, usize), params: &mut [f64], grads: &[f64], lr: f64) {
+        params
+            .iter_mut()
+            .for_each(|p| *p -= lr * self.weight_decay * *p);
+        self.adam.update(id, params, grads, lr);
+    }
+}
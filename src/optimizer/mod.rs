@@ -0,0 +1,19 @@
+pub mod adagrad;
+pub mod adam;
+pub mod adamw;
+pub mod momentum;
+pub mod optimizer_impl;
+pub mod rmsprop;
+pub mod sgd;
+pub mod shifted;
+pub mod utils;
+
+pub use crate::optimizer::adagrad::AdaGrad;
+pub use crate::optimizer::adam::Adam;
+pub use crate::optimizer::adamw::AdamW;
+pub use crate::optimizer::momentum::Momentum;
+pub use crate::optimizer::optimizer_impl::Optimizer;
+pub use crate::optimizer::rmsprop::RmsProp;
+pub use crate::optimizer::sgd::Sgd;
+pub use crate::optimizer::shifted::Shifted;
+pub use crate::optimizer::utils::get_optimizer;
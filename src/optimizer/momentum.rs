@@ -0,0 +1,39 @@
+use std::collections::HashMap;
+
+use itertools::izip;
+
+use crate::optimizer::Optimizer;
+
+pub struct Momentum {
+    pub momentum: f64,
+    pub nesterov: bool,
+    velocities: HashMap<(usize, usize), Vec<f64>>,
+}
+
+impl Momentum {
+    pub fn new(params: HashMap<String, f64>, nesterov: bool) -> Self {
+        Self {
+            nesterov,
+            momentum: *params.get("momentum").unwrap_or(&0.9),
+            velocities: HashMap::new(),
+        }
+    }
+}
+
+impl Optimizer for Momentum {
+    fn update(&mut self, id: (usize, usize), params: &mut [f64], grads: &[f64], lr: f64) {
+        let velocity = self
+            .velocities
+            .entry(id)
+            .or_insert_with(|| vec![0.0; params.len()]);
+
+        for (p, g, v) in izip!(params.iter_mut(), grads.iter(), velocity.iter_mut()) {
+            *v = self.momentum * *v + g;
+            if self.nesterov {
+                *p -= lr * (g + self.momentum * *v);
+            } else {
+                *p -= lr * *v;
+            }
+        }
+    }
+}
@@ -0,0 +1,5 @@
+pub trait Optimizer {
+    /// Updates `params` in place from `grads`. `id` identifies the parameter
+    /// group so that stateful optimizers can keep per-parameter state.
+    fn update(&mut self, id: (usize, usize), params: &mut [f64], grads: &[f64], lr: f64);
+}
@@ -0,0 +1,35 @@
+use std::collections::HashMap;
+
+use itertools::izip;
+
+use crate::optimizer::Optimizer;
+
+pub struct RmsProp {
+    pub rho: f64,
+    pub epsilon: f64,
+    squares: HashMap<(usize, usize), Vec<f64>>,
+}
+
+impl RmsProp {
+    pub fn new(params: HashMap<String, f64>) -> Self {
+        Self {
+            rho: *params.get("rho").unwrap_or(&0.9),
+            epsilon: *params.get("epsilon").unwrap_or(&1e-8),
+            squares: HashMap::new(),
+        }
+    }
+}
+
+impl Optimizer for RmsProp {
+    fn update(&mut self, id: (usize, usize), params: &mut [f64], grads: &[f64], lr: f64) {
+        let squares = self
+            .squares
+            .entry(id)
+            .or_insert_with(|| vec![0.0; params.len()]);
+
+        for (p, g, s) in izip!(params.iter_mut(), grads.iter(), squares.iter_mut()) {
+            *s = self.rho * *s + (1.0 - self.rho) * g * g;
+            *p -= lr * g / (s.sqrt() + self.epsilon);
+        }
+    }
+}
@@ -0,0 +1,12 @@
+use crate::optimizer::Optimizer;
+
+pub struct Sgd;
+
+impl Optimizer for Sgd {
+    fn update(&mut self, _: (usize, usize), params: &mut [f64], grads: &[f64], lr: f64) {
+        params
+            .iter_mut()
+            .zip(grads.iter())
+            .for_each(|(p, g)| *p -= lr * g);
+    }
+}
@@ -0,0 +1,15 @@
+use crate::optimizer::Optimizer;
+
+/// Optimizer seen by a sublayer of a composite layer. Shifts the parameter
+/// groups by `offset` so that each sublayer keeps its own optimizer state.
+pub struct Shifted<'a> {
+    pub optimizer: &'a mut dyn Optimizer,
+    pub offset: usize,
+}
+
+impl Optimizer for Shifted<'_> {
+    fn update(&mut self, (id, group): (usize, usize), params: &mut [f64], grads: &[f64], lr: f64) {
+        self.optimizer
+            .update((id, self.offset + group), params, grads, lr);
+    }
+}
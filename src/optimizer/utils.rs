@@ -0,0 +1,21 @@
+use std::collections::HashMap;
+
+use crate::error::Error;
+use crate::optimizer::*;
+
+pub fn get_optimizer(
+    name: &str,
+    params: HashMap<String, f64>,
+) -> Result<Box<dyn Optimizer>, Error> {
+    let optimizer: Box<dyn Optimizer> = match name {
+        "sgd" => Box::new(Sgd {}),
+        "momentum" => Box::new(Momentum::new(params, false)),
+        "nesterov" => Box::new(Momentum::new(params, true)),
+        "rmsprop" => Box::new(RmsProp::new(params)),
+        "adagrad" => Box::new(AdaGrad::new(params)),
+        "adam" => Box::new(Adam::new(params)),
+        "adamw" => Box::new(AdamW::new(params)),
+        _ => return Err(Error::UnknownOptimizer(name.to_string())),
+    };
+    Ok(optimizer)
+}
@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+use crate::matrix::Matrix;
+use crate::preprocessing::utils::{categories, check_width, is_selected};
+use crate::preprocessing::Preprocessor;
+
+/// Replaces the labels of each column by their index among the labels seen
+/// by `fit`, in sorted order.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct LabelEncoder {
+    pub columns: Vec<usize>,
+    /// Labels of every input column, empty for columns passed through.
+    pub classes: Vec<Vec<f64>>,
+}
+
+impl LabelEncoder {
+    pub fn new(columns: Vec<usize>) -> Self {
+        Self {
+            columns,
+            ..Default::default()
+        }
+    }
+
+    /// Label of column `column` encoded as `index`.
+    pub fn inverse(&self, column: usize, index: usize) -> Option<f64> {
+        self.classes.get(column)?.get(index).copied()
+    }
+}
+
+impl Preprocessor for LabelEncoder {
+    fn fit(&mut self, data: &Matrix) {
+        self.classes = (0..data.cols)
+            .map(|j| match is_selected(&self.columns, j) {
+                true => categories(data, j),
+                false => Vec::new(),
+            })
+            .collect();
+    }
+
+    fn transform(&self, data: &Matrix) -> Result<Matrix, Error> {
+        check_width(data, self.classes.len())?;
+        let mut outputs = data.clone();
+        for i in 0..outputs.rows {
+            for (x, classes) in outputs.row_mut(i).iter_mut().zip(self.classes.iter()) {
+                if classes.is_empty() {
+                    continue;
+                }
+                let index = classes
+                    .iter()
+                    .position(|c| c == x)
+                    .ok_or_else(|| Error::Validation(format!("unknown label: {}", x)))?;
+                *x = index as f64;
+            }
+        }
+        Ok(outputs)
+    }
+}
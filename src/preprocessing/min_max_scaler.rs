@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+use crate::matrix::Matrix;
+use crate::preprocessing::utils::{check_width, is_selected};
+use crate::preprocessing::Preprocessor;
+
+/// Scales columns to `[0, 1]` over the range seen by `fit`.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct MinMaxScaler {
+    pub columns: Vec<usize>,
+    pub min: Vec<f64>,
+    pub range: Vec<f64>,
+}
+
+impl MinMaxScaler {
+    pub fn new(columns: Vec<usize>) -> Self {
+        Self {
+            columns,
+            ..Default::default()
+        }
+    }
+}
+
+impl Preprocessor for MinMaxScaler {
+    fn fit(&mut self, data: &Matrix) {
+        self.min = vec![0.0; data.cols];
+        self.range = vec![1.0; data.cols];
+        for j in (0..data.cols).filter(|j| is_selected(&self.columns, *j)) {
+            let values = (0..data.rows).map(|i| data.row(i)[j]);
+            let min = values.clone().fold(f64::INFINITY, f64::min);
+            let max = values.fold(f64::NEG_INFINITY, f64::max);
+            if min.is_finite() {
+                self.min[j] = min;
+                self.range[j] = if max > min { max - min } else { 1.0 };
+            }
+        }
+    }
+
+    fn transform(&self, data: &Matrix) -> Result<Matrix, Error> {
+        check_width(data, self.min.len())?;
+        let mut outputs = data.clone();
+        for i in 0..outputs.rows {
+            outputs
+                .row_mut(i)
+                .iter_mut()
+                .zip(self.min.iter().zip(self.range.iter()))
+                .for_each(|(x, (min, range))| *x = (*x - min) / range);
+        }
+        Ok(outputs)
+    }
+
+    fn inverse_transform(&self, data: &Matrix) -> Result<Matrix, Error> {
+        check_width(data, self.min.len())?;
+        let mut outputs = data.clone();
+        for i in 0..outputs.rows {
+            outputs
+                .row_mut(i)
+                .iter_mut()
+                .zip(self.min.iter().zip(self.range.iter()))
+                .for_each(|(x, (min, range))| *x = *x * range + min);
+        }
+        Ok(outputs)
+    }
+}
@@ -0,0 +1,15 @@
+pub mod label_encoder;
+pub mod min_max_scaler;
+pub mod one_hot_encoder;
+pub mod pipeline;
+pub mod preprocessor_impl;
+pub mod standard_scaler;
+pub mod utils;
+
+pub use crate::preprocessing::label_encoder::LabelEncoder;
+pub use crate::preprocessing::min_max_scaler::MinMaxScaler;
+pub use crate::preprocessing::one_hot_encoder::OneHotEncoder;
+pub use crate::preprocessing::pipeline::{Pipeline, Step};
+pub use crate::preprocessing::preprocessor_impl::Preprocessor;
+pub use crate::preprocessing::standard_scaler::StandardScaler;
+pub use crate::preprocessing::utils::{get_preprocessor, PREPROCESSORS};
@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+use crate::matrix::Matrix;
+use crate::preprocessing::utils::{categories, check_width, is_selected};
+use crate::preprocessing::Preprocessor;
+
+/// Replaces each categorical column by one column per category seen by
+/// `fit`, in sorted order. Unseen categories encode to all zeros.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct OneHotEncoder {
+    pub columns: Vec<usize>,
+    /// Categories of every input column, empty for columns passed through.
+    pub categories: Vec<Vec<f64>>,
+}
+
+impl OneHotEncoder {
+    pub fn new(columns: Vec<usize>) -> Self {
+        Self {
+            columns,
+            ..Default::default()
+        }
+    }
+}
+
+impl Preprocessor for OneHotEncoder {
+    fn fit(&mut self, data: &Matrix) {
+        self.categories = (0..data.cols)
+            .map(|j| match is_selected(&self.columns, j) {
+                true => categories(data, j),
+                false => Vec::new(),
+            })
+            .collect();
+    }
+
+    fn transform(&self, data: &Matrix) -> Result<Matrix, Error> {
+        check_width(data, self.categories.len())?;
+        let rows: Vec<Vec<f64>> = (0..data.rows)
+            .map(|i| {
+                let mut row = Vec::new();
+                for (x, categories) in data.row(i).iter().zip(self.categories.iter()) {
+                    match categories.is_empty() {
+                        true => row.push(*x),
+                        false => {
+                            row.extend(categories.iter().map(|c| if c == x { 1.0 } else { 0.0 }))
+                        }
+                    }
+                }
+                row
+            })
+            .collect();
+        Ok(Matrix::from_rows(rows.iter().map(|r| r.as_slice())))
+    }
+}
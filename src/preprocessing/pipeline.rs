@@ -0,0 +1,145 @@
+use std::borrow::Cow;
+
+use serde::{Deserialize, Serialize};
+
+use crate::dataset::Dataset;
+use crate::error::Error;
+use crate::matrix::Matrix;
+use crate::preprocessing::*;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum Step {
+    StandardScaler(StandardScaler),
+    MinMaxScaler(MinMaxScaler),
+    OneHotEncoder(OneHotEncoder),
+    LabelEncoder(LabelEncoder),
+}
+
+impl Step {
+    fn preprocessor(&self) -> &dyn Preprocessor {
+        match self {
+            Step::StandardScaler(p) => p,
+            Step::MinMaxScaler(p) => p,
+            Step::OneHotEncoder(p) => p,
+            Step::LabelEncoder(p) => p,
+        }
+    }
+
+    /// Whether the step only rescales values, so that outputs predicted in
+    /// its space can be mapped back with `inverse_transform`.
+    fn is_scaler(&self) -> bool {
+        matches!(self, Step::StandardScaler(_) | Step::MinMaxScaler(_))
+    }
+
+    fn preprocessor_mut(&mut self) -> &mut dyn Preprocessor {
+        match self {
+            Step::StandardScaler(p) => p,
+            Step::MinMaxScaler(p) => p,
+            Step::OneHotEncoder(p) => p,
+            Step::LabelEncoder(p) => p,
+        }
+    }
+}
+
+impl Preprocessor for Step {
+    fn fit(&mut self, data: &Matrix) {
+        self.preprocessor_mut().fit(data)
+    }
+
+    fn transform(&self, data: &Matrix) -> Result<Matrix, Error> {
+        self.preprocessor().transform(data)
+    }
+
+    fn inverse_transform(&self, data: &Matrix) -> Result<Matrix, Error> {
+        self.preprocessor().inverse_transform(data)
+    }
+}
+
+/// Preprocessing steps applied in order to the inputs and to the targets of
+/// a dataset.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct Pipeline {
+    pub inputs: Vec<Step>,
+    pub targets: Vec<Step>,
+    pub fitted: bool,
+}
+
+impl Pipeline {
+    pub fn new(inputs: Vec<Step>, targets: Vec<Step>) -> Self {
+        Self {
+            inputs,
+            targets,
+            fitted: false,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inputs.is_empty() && self.targets.is_empty()
+    }
+
+    /// Fits every step on the output of the steps before it.
+    pub fn fit(&mut self, dataset: &Dataset) -> Result<(), Error> {
+        fit(&mut self.inputs, &dataset.inputs)?;
+        fit(&mut self.targets, &dataset.targets)?;
+        self.fitted = true;
+        Ok(())
+    }
+
+    pub fn transform<'a>(&self, dataset: &'a Dataset) -> Result<Cow<'a, Dataset>, Error> {
+        if self.is_empty() {
+            return Ok(Cow::Borrowed(dataset));
+        }
+        let dataset = Dataset::new(
+            self.transform_inputs(&dataset.inputs)?.into_owned(),
+            self.transform_targets(&dataset.targets)?.into_owned(),
+        )?;
+        Ok(Cow::Owned(dataset))
+    }
+
+    pub fn transform_inputs<'a>(&self, inputs: &'a Matrix) -> Result<Cow<'a, Matrix>, Error> {
+        self.apply(&self.inputs, inputs)
+    }
+
+    pub fn transform_targets<'a>(&self, targets: &'a Matrix) -> Result<Cow<'a, Matrix>, Error> {
+        self.apply(&self.targets, targets)
+    }
+
+    /// Maps outputs predicted in the space of the transformed targets back to
+    /// the scale of the targets, inverting the scalers after the last encoder.
+    /// Encoded outputs, e.g. the probabilities of one-hot classes, stay
+    /// encoded.
+    pub fn inverse_targets<'a>(&self, outputs: &'a Matrix) -> Result<Cow<'a, Matrix>, Error> {
+        let mut outputs = Cow::Borrowed(outputs);
+        for step in self.targets.iter().rev().take_while(|s| s.is_scaler()) {
+            if !self.fitted {
+                return Err(Error::Validation("pipeline is not fitted".to_string()));
+            }
+            outputs = Cow::Owned(step.inverse_transform(&outputs)?);
+        }
+        Ok(outputs)
+    }
+
+    fn apply<'a>(&self, steps: &[Step], data: &'a Matrix) -> Result<Cow<'a, Matrix>, Error> {
+        if steps.is_empty() {
+            return Ok(Cow::Borrowed(data));
+        }
+        if !self.fitted {
+            return Err(Error::Validation("pipeline is not fitted".to_string()));
+        }
+        let mut data = Cow::Borrowed(data);
+        for step in steps.iter() {
+            data = Cow::Owned(step.transform(&data)?);
+        }
+        Ok(data)
+    }
+}
+
+fn fit(steps: &mut [Step], data: &Matrix) -> Result<(), Error> {
+    let mut data = Cow::Borrowed(data);
+    for step in steps.iter_mut() {
+        step.fit(&data);
+        data = Cow::Owned(step.transform(&data)?);
+    }
+    Ok(())
+}
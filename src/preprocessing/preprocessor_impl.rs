@@ -0,0 +1,16 @@
+use crate::error::Error;
+use crate::matrix::Matrix;
+
+pub trait Preprocessor {
+    /// Learns the statistics of `data`, one sample per row.
+    fn fit(&mut self, data: &Matrix);
+    /// Applies the statistics learned by `fit` to `data`.
+    fn transform(&self, data: &Matrix) -> Result<Matrix, Error>;
+    /// Maps values transformed by `transform` back to the scale of the data
+    /// given to `fit`.
+    fn inverse_transform(&self, _: &Matrix) -> Result<Matrix, Error> {
+        Err(Error::Validation(
+            "preprocessor cannot be inverted".to_string(),
+        ))
+    }
+}
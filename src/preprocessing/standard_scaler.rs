@@ -0,0 +1,67 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+use crate::matrix::Matrix;
+use crate::preprocessing::utils::{check_width, is_selected};
+use crate::preprocessing::Preprocessor;
+
+/// Scales columns to zero mean and unit variance.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct StandardScaler {
+    pub columns: Vec<usize>,
+    pub mean: Vec<f64>,
+    pub std: Vec<f64>,
+}
+
+impl StandardScaler {
+    pub fn new(columns: Vec<usize>) -> Self {
+        Self {
+            columns,
+            ..Default::default()
+        }
+    }
+}
+
+impl Preprocessor for StandardScaler {
+    fn fit(&mut self, data: &Matrix) {
+        let n = data.rows.max(1) as f64;
+        self.mean = vec![0.0; data.cols];
+        self.std = vec![1.0; data.cols];
+        for j in (0..data.cols).filter(|j| is_selected(&self.columns, *j)) {
+            let mean = (0..data.rows).map(|i| data.row(i)[j]).sum::<f64>() / n;
+            let variance = (0..data.rows)
+                .map(|i| (data.row(i)[j] - mean).powi(2))
+                .sum::<f64>()
+                / n;
+            self.mean[j] = mean;
+            // constant columns are only centered
+            self.std[j] = if variance > 0.0 { variance.sqrt() } else { 1.0 };
+        }
+    }
+
+    fn transform(&self, data: &Matrix) -> Result<Matrix, Error> {
+        check_width(data, self.mean.len())?;
+        let mut outputs = data.clone();
+        for i in 0..outputs.rows {
+            outputs
+                .row_mut(i)
+                .iter_mut()
+                .zip(self.mean.iter().zip(self.std.iter()))
+                .for_each(|(x, (mean, std))| *x = (*x - mean) / std);
+        }
+        Ok(outputs)
+    }
+
+    fn inverse_transform(&self, data: &Matrix) -> Result<Matrix, Error> {
+        check_width(data, self.mean.len())?;
+        let mut outputs = data.clone();
+        for i in 0..outputs.rows {
+            outputs
+                .row_mut(i)
+                .iter_mut()
+                .zip(self.mean.iter().zip(self.std.iter()))
+                .for_each(|(x, (mean, std))| *x = *x * std + mean);
+        }
+        Ok(outputs)
+    }
+}
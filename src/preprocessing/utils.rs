@@ -0,0 +1,46 @@
+use crate::error::Error;
+use crate::matrix::Matrix;
+use crate::preprocessing::*;
+
+/// Names accepted by `get_preprocessor`.
+pub const PREPROCESSORS: [&str; 4] = [
+    "standard-scaler",
+    "min-max-scaler",
+    "one-hot-encoder",
+    "label-encoder",
+];
+
+/// Unfitted preprocessing step applied to `columns`, or to every column when
+/// `columns` is empty.
+pub fn get_preprocessor(name: &str, columns: Vec<usize>) -> Result<Step, Error> {
+    let step = match name {
+        "standard-scaler" => Step::StandardScaler(StandardScaler::new(columns)),
+        "min-max-scaler" => Step::MinMaxScaler(MinMaxScaler::new(columns)),
+        "one-hot-encoder" => Step::OneHotEncoder(OneHotEncoder::new(columns)),
+        "label-encoder" => Step::LabelEncoder(LabelEncoder::new(columns)),
+        _ => return Err(Error::UnknownPreprocessor(name.to_string())),
+    };
+    Ok(step)
+}
+
+pub fn is_selected(columns: &[usize], column: usize) -> bool {
+    columns.is_empty() || columns.contains(&column)
+}
+
+pub fn check_width(data: &Matrix, width: usize) -> Result<(), Error> {
+    if data.cols != width {
+        return Err(Error::ShapeMismatch {
+            expected: width,
+            actual: data.cols,
+        });
+    }
+    Ok(())
+}
+
+/// Distinct values of column `j`, sorted.
+pub fn categories(data: &Matrix, j: usize) -> Vec<f64> {
+    let mut values: Vec<f64> = (0..data.rows).map(|i| data.row(i)[j]).collect();
+    values.sort_by(f64::total_cmp);
+    values.dedup();
+    values
+}
@@ -0,0 +1,109 @@
+use serde::{Deserialize, Serialize};
+
+use crate::config::ConfigLayer;
+use crate::optimizer::Optimizer;
+
+/// Per-layer regularization coefficients.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct Regularization {
+    /// Coefficient of the `l1 * sum(|w|)` penalty.
+    pub l1: f64,
+    /// Coefficient of the `l2 / 2 * sum(w^2)` penalty.
+    pub l2: f64,
+    /// Decoupled weight decay, `w -= lr * weight_decay * w` after the
+    /// optimizer step. Not part of the loss.
+    pub weight_decay: f64,
+    /// Regularize the biases along with the weights.
+    pub biases: bool,
+}
+
+impl Regularization {
+    pub fn new(config: &ConfigLayer) -> Self {
+        Self {
+            l1: config.l1,
+            l2: config.l2,
+            weight_decay: config.weight_decay,
+            biases: config.regularize_biases,
+        }
+    }
+
+    pub fn penalty(&self, params: &[f64]) -> f64 {
+        if self.l1 == 0.0 && self.l2 == 0.0 {
+            return 0.0;
+        }
+        params
+            .iter()
+            .map(|w| self.l1 * w.abs() + 0.5 * self.l2 * w * w)
+            .sum()
+    }
+
+    /// Adds the gradient of the penalty to `grads`.
+    pub fn gradient(&self, params: &[f64], grads: &mut [f64]) {
+        if self.l1 == 0.0 && self.l2 == 0.0 {
+            return;
+        }
+        grads
+            .iter_mut()
+            .zip(params.iter())
+            .for_each(|(g, w)| *g += self.l1 * sign(*w) + self.l2 * w);
+    }
+
+    pub fn decay(&self, lr: f64, params: &mut [f64]) {
+        if self.weight_decay == 0.0 {
+            return;
+        }
+        params
+            .iter_mut()
+            .for_each(|w| *w -= lr * self.weight_decay * *w);
+    }
+
+    /// Penalty of a layer's weights, and of its biases if `biases` is set.
+    pub fn layer_penalty(&self, weights: &[f64], biases: &[f64]) -> f64 {
+        let mut penalty = self.penalty(weights);
+        if self.biases {
+            penalty += self.penalty(biases);
+        }
+        penalty
+    }
+
+    /// Optimizer step of a layer's weights and biases, each paired with its
+    /// gradients accumulated over `batch_size` samples. Adds the penalty
+    /// gradients before the step, decays after it and zeroes the gradients.
+    pub fn update(
+        &self,
+        lr: f64,
+        optimizer: &mut dyn Optimizer,
+        id: usize,
+        batch_size: usize,
+        (weights, weight_grads): (&mut [f64], &mut [f64]),
+        (biases, bias_grads): (&mut [f64], &mut [f64]),
+    ) {
+        let scale = 1.0 / batch_size as f64;
+        weight_grads.iter_mut().for_each(|g| *g *= scale);
+        bias_grads.iter_mut().for_each(|g| *g *= scale);
+
+        self.gradient(weights, weight_grads);
+        if self.biases {
+            self.gradient(biases, bias_grads);
+        }
+
+        optimizer.update((id, 0), weights, weight_grads, lr);
+        optimizer.update((id, 1), biases, bias_grads, lr);
+
+        self.decay(lr, weights);
+        if self.biases {
+            self.decay(lr, biases);
+        }
+
+        weight_grads.iter_mut().for_each(|g| *g = 0.0);
+        bias_grads.iter_mut().for_each(|g| *g = 0.0);
+    }
+}
+
+fn sign(x: f64) -> f64 {
+    if x == 0.0 {
+        0.0
+    } else {
+        x.signum()
+    }
+}
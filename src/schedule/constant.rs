@@ -0,0 +1,9 @@
+use crate::schedule::Schedule;
+
+pub struct Constant;
+
+impl Schedule for Constant {
+    fn lr(&self, lr: f64, _epoch: usize, _step: usize) -> f64 {
+        lr
+    }
+}
@@ -0,0 +1,35 @@
+use std::collections::HashMap;
+use std::f64::consts::PI;
+
+use crate::schedule::Schedule;
+
+/// Cosine annealing from the base rate down to `min_lr` over `period`
+/// epochs, restarting with a period multiplied by `mult` each time.
+// https://arxiv.org/abs/1608.03983
+pub struct CosineRestarts {
+    pub period: usize,
+    pub mult: usize,
+    pub min_lr: f64,
+}
+
+impl CosineRestarts {
+    pub fn new(params: HashMap<String, f64>) -> Self {
+        Self {
+            period: (*params.get("period").unwrap_or(&10.0) as usize).max(1),
+            mult: (*params.get("mult").unwrap_or(&1.0) as usize).max(1),
+            min_lr: *params.get("min_lr").unwrap_or(&0.0),
+        }
+    }
+}
+
+impl Schedule for CosineRestarts {
+    fn lr(&self, lr: f64, epoch: usize, _step: usize) -> f64 {
+        let (mut start, mut period) = (epoch, self.period);
+        while start >= period {
+            start -= period;
+            period *= self.mult;
+        }
+        let progress = start as f64 / period as f64;
+        self.min_lr + 0.5 * (lr - self.min_lr) * (1.0 + (PI * progress).cos())
+    }
+}
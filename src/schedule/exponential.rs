@@ -0,0 +1,22 @@
+use std::collections::HashMap;
+
+use crate::schedule::Schedule;
+
+/// Multiplies the learning rate by `gamma` every epoch.
+pub struct Exponential {
+    pub gamma: f64,
+}
+
+impl Exponential {
+    pub fn new(params: HashMap<String, f64>) -> Self {
+        Self {
+            gamma: *params.get("gamma").unwrap_or(&0.95),
+        }
+    }
+}
+
+impl Schedule for Exponential {
+    fn lr(&self, lr: f64, epoch: usize, _step: usize) -> f64 {
+        lr * self.gamma.powi(epoch as i32)
+    }
+}
@@ -0,0 +1,17 @@
+pub mod constant;
+pub mod cosine;
+pub mod exponential;
+pub mod plateau;
+pub mod schedule_impl;
+pub mod step;
+pub mod utils;
+pub mod warmup;
+
+pub use crate::schedule::constant::Constant;
+pub use crate::schedule::cosine::CosineRestarts;
+pub use crate::schedule::exponential::Exponential;
+pub use crate::schedule::plateau::ReduceOnPlateau;
+pub use crate::schedule::schedule_impl::Schedule;
+pub use crate::schedule::step::StepDecay;
+pub use crate::schedule::utils::{get_schedule, SCHEDULES};
+pub use crate::schedule::warmup::LinearWarmup;
@@ -0,0 +1,52 @@
+use std::collections::HashMap;
+
+use crate::schedule::Schedule;
+
+/// Multiplies the learning rate by `factor` once the validation loss has not
+/// improved by more than `threshold` for `patience` epochs, down to `min_lr`.
+pub struct ReduceOnPlateau {
+    pub factor: f64,
+    pub patience: usize,
+    pub threshold: f64,
+    pub min_lr: f64,
+    scale: f64,
+    best: f64,
+    waiting: usize,
+}
+
+impl ReduceOnPlateau {
+    pub fn new(params: HashMap<String, f64>) -> Self {
+        Self {
+            factor: *params.get("factor").unwrap_or(&0.1),
+            patience: *params.get("patience").unwrap_or(&10.0) as usize,
+            threshold: *params.get("threshold").unwrap_or(&1e-4),
+            min_lr: *params.get("min_lr").unwrap_or(&0.0),
+            scale: 1.0,
+            best: f64::INFINITY,
+            waiting: 0,
+        }
+    }
+}
+
+impl Schedule for ReduceOnPlateau {
+    fn lr(&self, lr: f64, _epoch: usize, _step: usize) -> f64 {
+        (lr * self.scale).max(self.min_lr)
+    }
+
+    fn observes(&self) -> bool {
+        true
+    }
+
+    fn observe(&mut self, loss: f64) {
+        if loss < self.best - self.threshold {
+            self.best = loss;
+            self.waiting = 0;
+            return;
+        }
+        self.waiting += 1;
+        if self.waiting > self.patience {
+            self.scale *= self.factor;
+            self.waiting = 0;
+        }
+    }
+}
@@ -0,0 +1,14 @@
+pub trait Schedule {
+    /// Learning rate of the optimization step `step` of the run, counted
+    /// from zero over all epochs, during `epoch`, given the base rate `lr`.
+    fn lr(&self, lr: f64, epoch: usize, step: usize) -> f64;
+
+    /// Receives the validation loss at the end of every epoch of `fit`.
+    fn observe(&mut self, _loss: f64) {}
+
+    /// Whether the rate depends on the losses given to `observe`, so that
+    /// the schedule can only be used with `fit`.
+    fn observes(&self) -> bool {
+        false
+    }
+}
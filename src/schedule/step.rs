@@ -0,0 +1,24 @@
+use std::collections::HashMap;
+
+use crate::schedule::Schedule;
+
+/// Multiplies the learning rate by `gamma` every `step_size` epochs.
+pub struct StepDecay {
+    pub step_size: usize,
+    pub gamma: f64,
+}
+
+impl StepDecay {
+    pub fn new(params: HashMap<String, f64>) -> Self {
+        Self {
+            step_size: (*params.get("step_size").unwrap_or(&10.0) as usize).max(1),
+            gamma: *params.get("gamma").unwrap_or(&0.1),
+        }
+    }
+}
+
+impl Schedule for StepDecay {
+    fn lr(&self, lr: f64, epoch: usize, _step: usize) -> f64 {
+        lr * self.gamma.powi((epoch / self.step_size) as i32)
+    }
+}
@@ -0,0 +1,27 @@
+use std::collections::HashMap;
+
+use crate::error::Error;
+use crate::schedule::*;
+
+/// Names accepted by `get_schedule`.
+pub const SCHEDULES: [&str; 6] = [
+    "constant",
+    "step",
+    "exponential",
+    "cosine-restarts",
+    "linear-warmup",
+    "reduce-on-plateau",
+];
+
+pub fn get_schedule(name: &str, params: HashMap<String, f64>) -> Result<Box<dyn Schedule>, Error> {
+    let schedule: Box<dyn Schedule> = match name {
+        "constant" => Box::new(Constant {}),
+        "step" => Box::new(StepDecay::new(params)),
+        "exponential" => Box::new(Exponential::new(params)),
+        "cosine-restarts" => Box::new(CosineRestarts::new(params)),
+        "linear-warmup" => Box::new(LinearWarmup::new(params)),
+        "reduce-on-plateau" => Box::new(ReduceOnPlateau::new(params)),
+        _ => return Err(Error::UnknownSchedule(name.to_string())),
+    };
+    Ok(schedule)
+}
@@ -0,0 +1,23 @@
+use std::collections::HashMap;
+
+use crate::schedule::Schedule;
+
+/// Increases the learning rate linearly over the first `steps` optimization
+/// steps, then keeps the base rate.
+pub struct LinearWarmup {
+    pub steps: usize,
+}
+
+impl LinearWarmup {
+    pub fn new(params: HashMap<String, f64>) -> Self {
+        Self {
+            steps: (*params.get("steps").unwrap_or(&100.0) as usize).max(1),
+        }
+    }
+}
+
+impl Schedule for LinearWarmup {
+    fn lr(&self, lr: f64, _epoch: usize, step: usize) -> f64 {
+        lr * ((step + 1) as f64 / self.steps as f64).min(1.0)
+    }
+}
@@ -0,0 +1,31 @@
+use crate::layer::Mode;
+use crate::matrix::Matrix;
+
+/// Values recorded by a layer during the forward pass and consumed by its
+/// backward pass, one sample per row.
+#[derive(Default, Clone)]
+pub struct Cache {
+    pub inputs: Matrix,
+    pub pre_activations: Matrix,
+    pub outputs: Matrix,
+    /// Mode of the pass that recorded the cache.
+    pub mode: Mode,
+    /// Per-feature values of the batch, e.g. normalization statistics.
+    pub statistics: Vec<f64>,
+    /// Per-step values of recurrent layers, one sample per row.
+    pub states: Matrix,
+    /// Caches of the sublayers of composite layers.
+    pub children: Vec<Cache>,
+}
+
+/// Per-layer caches of one forward pass. Buffers are reused between batches.
+#[derive(Default)]
+pub struct Tape {
+    pub caches: Vec<Cache>,
+}
+
+impl Tape {
+    pub fn outputs(&self) -> Option<&Matrix> {
+        self.caches.last().map(|c| &c.outputs)
+    }
+}
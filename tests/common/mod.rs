@@ -0,0 +1,121 @@
+//! Helpers shared by the layer tests. Each test crate only uses some of them.
+#![allow(dead_code)]
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use rust_nn::config::{Config, ConfigInitialization, ConfigLayer};
+use rust_nn::error::Error;
+use rust_nn::gradcheck::gradcheck;
+use rust_nn::initialization::get_initialization;
+use rust_nn::layer::{get_layer, Layer, Mode};
+use rust_nn::matrix::Matrix;
+use rust_nn::tape::Cache;
+
+/// Seeded initialization of the layer tests.
+pub fn initialization() -> ConfigInitialization {
+    ConfigInitialization {
+        method: "xavier-uniform".to_string(),
+        seed: Some(42),
+    }
+}
+
+pub fn try_build(
+    config: &ConfigLayer,
+    initialization: &ConfigInitialization,
+) -> Result<Box<dyn Layer>, Error> {
+    get_layer(config, &mut get_initialization(initialization).unwrap())
+}
+
+pub fn build(config: &ConfigLayer, initialization: &ConfigInitialization) -> Box<dyn Layer> {
+    try_build(config, initialization).unwrap()
+}
+
+pub fn assert_invalid(config: &Config) {
+    let result = config.validate();
+    assert!(matches!(result, Err(Error::Validation(_))), "{result:?}");
+}
+
+pub fn assert_shape_mismatch(config: &Config, expected: usize, actual: usize) {
+    let result = config.validate();
+    assert!(
+        matches!(result, Err(Error::ShapeMismatch { expected: e, actual: a })
+            if e == expected && a == actual),
+        "{result:?}"
+    );
+}
+
+/// Checks the gradients of every layer of a network built from `config`
+/// on one sample.
+pub fn assert_gradcheck(config: &Config, inputs: &[f64], targets: &[f64], epsilon: f64) {
+    for report in gradcheck(config, inputs, targets, epsilon).unwrap() {
+        assert!(report.max_relative_error < 1e-5, "{}", report.layer);
+    }
+}
+
+pub fn assert_close(actual: &[f64], expected: &[f64]) {
+    assert_eq!(actual.len(), expected.len());
+    for (a, e) in actual.iter().zip(expected) {
+        assert!((a - e).abs() < 1e-12, "{actual:?} != {expected:?}");
+    }
+}
+
+/// Weighted sum of the train-mode outputs, whose gradient is `weights`.
+pub fn objective(layer: &mut dyn Layer, inputs: &Matrix, weights: &[f64]) -> f64 {
+    let mut cache = Cache::default();
+    let mut rng = ChaCha8Rng::seed_from_u64(42);
+    layer.forward_cached(inputs, &mut cache, Mode::Train, &mut rng);
+    cache
+        .outputs
+        .data
+        .iter()
+        .zip(weights)
+        .map(|(o, w)| o * w)
+        .sum()
+}
+
+/// Input gradients of `layer` for the weighted sum of its outputs.
+pub fn input_grads(layer: &mut dyn Layer, inputs: &Matrix, weights: &[f64]) -> Matrix {
+    let mut cache = Cache::default();
+    let mut rng = ChaCha8Rng::seed_from_u64(42);
+    layer.forward_cached(inputs, &mut cache, Mode::Train, &mut rng);
+    let grads = Matrix {
+        rows: cache.outputs.rows,
+        cols: cache.outputs.cols,
+        data: weights.to_vec(),
+    };
+    layer.backward(&cache, &grads)
+}
+
+/// Compares the input and parameter gradients of `layer` with central
+/// differences.
+pub fn check_gradients(layer: &mut dyn Layer, inputs: &Matrix) {
+    let size: usize = layer.output_shape().iter().product();
+    let weights: Vec<f64> = (0..inputs.rows * size)
+        .map(|k| ((k * 7 % 5) as f64 - 2.0) / 3.0)
+        .collect();
+    let errors = input_grads(layer, inputs, &weights);
+
+    let epsilon = 1e-6;
+    for k in 0..inputs.data.len() {
+        let (mut plus, mut minus) = (inputs.clone(), inputs.clone());
+        plus.data[k] += epsilon;
+        minus.data[k] -= epsilon;
+        let numeric = (objective(layer, &plus, &weights) - objective(layer, &minus, &weights))
+            / (2.0 * epsilon);
+        assert!((numeric - errors.data[k]).abs() < 1e-6, "input {k}");
+    }
+
+    for group in 0..layer.parameters().len() {
+        for k in 0..layer.parameters()[group].len() {
+            let original = layer.parameters()[group][k];
+            let mut shifted = |delta: f64| {
+                layer.parameters_mut()[group].0[k] = original + delta;
+                objective(layer, inputs, &weights)
+            };
+            let numeric = (shifted(epsilon) - shifted(-epsilon)) / (2.0 * epsilon);
+            layer.parameters_mut()[group].0[k] = original;
+            let analytic = layer.gradients()[group][k];
+            assert!((numeric - analytic).abs() < 1e-6, "group {group} {k}");
+        }
+    }
+}
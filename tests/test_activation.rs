@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
 use rust_nn::activation::*;
+use rust_nn::error::Error;
 #[test]
 fn test_activation_relu_function() {
     let relu = ReLU {};
@@ -72,6 +73,8 @@ fn test_activation_gaussian_function() {
     params.insert("sigma".to_string(), 1.0);
     let gaussian = Gaussian::new(params);
     assert!((gaussian.function(&[0.0])[0] - 1.0).abs() < 1e-7);
+    assert!((gaussian.function(&[1.0])[0] - (-0.5f64).exp()).abs() < 1e-12);
+    assert!((gaussian.function(&[-1.0])[0] - (-0.5f64).exp()).abs() < 1e-12);
 }
 
 #[test]
@@ -100,3 +103,85 @@ fn test_activation_multiquadratics_derivative() {
     let multiquadratics = Multiquadratics::new(params);
     assert!((multiquadratics.derivative(&[0.0])[0] - 0.0).abs() < 1e-7);
 }
+
+#[test]
+fn test_activation_softmax_function() {
+    let softmax = get_activation("softmax", HashMap::new()).unwrap();
+    let outputs = softmax.function(&[1.0, 2.0, 3.0]);
+    assert!((outputs.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+    assert!((outputs[0] - 0.09003057317038046).abs() < 1e-12);
+    assert!((outputs[2] - 0.6652409557748219).abs() < 1e-12);
+    // large inputs must not overflow
+    let outputs = softmax.function(&[1000.0, 1000.0]);
+    assert!((outputs[0] - 0.5).abs() < 1e-12);
+}
+
+#[test]
+fn test_activation_softmax_backward() {
+    let softmax = Softmax {};
+    let x = [0.5, -1.0, 2.0];
+    let grad = [0.3, -0.2, 0.7];
+    let jacobian = softmax.jacobian(&x);
+    let backward = softmax.backward(&x, &grad);
+    for (i, b) in backward.iter().enumerate() {
+        let expected = (0..x.len()).map(|j| jacobian[j][i] * grad[j]).sum::<f64>();
+        assert!((b - expected).abs() < 1e-12);
+    }
+    for (j, row) in jacobian.iter().enumerate() {
+        assert!(
+            row.iter().sum::<f64>().abs() < 1e-12,
+            "row {} sums to zero",
+            j
+        );
+    }
+}
+
+#[test]
+fn test_activation_log_softmax_function() {
+    let log_softmax = LogSoftmax {};
+    let softmax = Softmax {};
+    let x = [1.0, 2.0, 3.0];
+    for (l, s) in log_softmax.function(&x).iter().zip(softmax.function(&x)) {
+        assert!((l - s.ln()).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn test_activation_log_softmax_backward() {
+    let log_softmax = LogSoftmax {};
+    let x = [0.5, -1.0, 2.0];
+    let grad = [0.3, -0.2, 0.7];
+    let jacobian = log_softmax.jacobian(&x);
+    let backward = log_softmax.backward(&x, &grad);
+    for (i, b) in backward.iter().enumerate() {
+        let expected = (0..x.len()).map(|j| jacobian[j][i] * grad[j]).sum::<f64>();
+        assert!((b - expected).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn test_activation_sparsemax_function() {
+    let sparsemax = Sparsemax {};
+    let outputs = sparsemax.function(&[1.0, 0.8, -1.0]);
+    assert!((outputs[0] - 0.6).abs() < 1e-12);
+    assert!((outputs[1] - 0.4).abs() < 1e-12);
+    assert_eq!(outputs[2], 0.0);
+    let outputs = sparsemax.function(&[3.0, 0.0, 0.0]);
+    assert_eq!(outputs, vec![1.0, 0.0, 0.0]);
+}
+
+#[test]
+fn test_activation_sparsemax_backward() {
+    let sparsemax = Sparsemax {};
+    let x = [1.0, 0.8, -1.0];
+    let backward = sparsemax.backward(&x, &[0.5, 0.1, 0.9]);
+    assert!((backward[0] - 0.2).abs() < 1e-12);
+    assert!((backward[1] + 0.2).abs() < 1e-12);
+    assert_eq!(backward[2], 0.0);
+}
+
+#[test]
+fn test_activation_unknown() {
+    let result = get_activation("unknown", HashMap::new());
+    assert!(matches!(result, Err(Error::UnknownActivation(name)) if name == "unknown"));
+}
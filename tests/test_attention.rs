@@ -0,0 +1,259 @@
+use rust_nn::config::{Config, ConfigLayer};
+use rust_nn::dataset::Dataset;
+use rust_nn::layer::Layer;
+use rust_nn::matrix::Matrix;
+use rust_nn::network::Network;
+
+mod common;
+
+use common::{
+    assert_close, assert_gradcheck, assert_invalid, assert_shape_mismatch, build, check_gradients,
+    initialization,
+};
+
+/// Three steps of four features.
+fn sequence(kind: &str) -> ConfigLayer {
+    ConfigLayer {
+        name: kind.to_string(),
+        kind: kind.to_string(),
+        input_size: 12,
+        output_size: 12,
+        steps: 3,
+        heads: 2,
+        hidden_size: 5,
+        activation: "tanh".to_string(),
+        ..Default::default()
+    }
+}
+
+/// Two samples of three steps of four features.
+fn sequences() -> Matrix {
+    let sample = |offset: f64| -> Vec<f64> {
+        (0..12)
+            .map(|k| ((k * 5 % 12) as f64 - 6.0) / 5.0 + offset)
+            .collect()
+    };
+    let (a, b) = (sample(0.0), sample(0.37));
+    Matrix::from_rows([&a[..], &b[..]])
+}
+
+/// Sets the projections of an attention layer over 2 features to the
+/// identity.
+fn identity(layer: &mut dyn Layer) {
+    for (group, (params, _)) in layer.parameters_mut().into_iter().enumerate() {
+        if group % 2 == 0 {
+            params.copy_from_slice(&[1.0, 0.0, 0.0, 1.0]);
+        } else {
+            params.fill(0.0);
+        }
+    }
+}
+
+#[test]
+fn test_attention() {
+    let config = ConfigLayer {
+        input_size: 4,
+        output_size: 4,
+        steps: 2,
+        heads: 1,
+        ..sequence("attention")
+    };
+    let mut layer = build(&config, &initialization());
+    assert_eq!(layer.parameters().len(), 8);
+    identity(layer.as_mut());
+
+    // scores of 1 / sqrt(2) and 0 for the first step, the reverse for the
+    // second
+    let weight = 1.0 / (1.0 + (-(0.5f64).sqrt()).exp());
+    let outputs = layer.forward(&Matrix::from_rows([&[1.0, 0.0, 0.0, 1.0][..]]));
+    assert_close(&outputs.data, &[weight, 1.0 - weight, 1.0 - weight, weight]);
+
+    let causal = ConfigLayer {
+        causal: true,
+        ..config
+    };
+    let mut layer = build(&causal, &initialization());
+    identity(layer.as_mut());
+    let outputs = layer.forward(&Matrix::from_rows([&[1.0, 0.0, 0.0, 1.0][..]]));
+    assert_close(&outputs.data[..2], &[1.0, 0.0]);
+    assert_close(&outputs.data[2..], &[1.0 - weight, weight]);
+}
+
+#[test]
+fn test_attention_gradients() {
+    for kind in ["attention", "transformer"] {
+        for heads in [1, 2] {
+            for causal in [false, true] {
+                let config = ConfigLayer {
+                    heads,
+                    causal,
+                    ..sequence(kind)
+                };
+                assert!(config.validate().is_ok());
+                let mut layer = build(&config, &initialization());
+                assert_eq!(layer.output_shape(), vec![3, 4]);
+                check_gradients(layer.as_mut(), &sequences());
+            }
+        }
+    }
+}
+
+#[test]
+fn test_causal_mask() {
+    for kind in ["attention", "transformer"] {
+        let config = ConfigLayer {
+            causal: true,
+            ..sequence(kind)
+        };
+        let layer = build(&config, &initialization());
+        let outputs = layer.forward(&sequences());
+
+        // each step only sees the steps before it
+        let mut changed = sequences();
+        changed.data[10] += 1.0;
+        let changed = layer.forward(&changed);
+        assert_eq!(&changed.row(0)[..8], &outputs.row(0)[..8], "{kind}");
+        assert_ne!(&changed.row(0)[8..], &outputs.row(0)[8..], "{kind}");
+
+        let unmasked = build(&sequence(kind), &initialization());
+        let mut changed = sequences();
+        changed.data[10] += 1.0;
+        assert_ne!(
+            &unmasked.forward(&changed).row(0)[..4],
+            &unmasked.forward(&sequences()).row(0)[..4],
+            "{kind}"
+        );
+    }
+}
+
+#[test]
+fn test_positional_encoding() {
+    let config = ConfigLayer {
+        input_size: 8,
+        output_size: 8,
+        steps: 2,
+        ..sequence("positional-encoding")
+    };
+    let mut layer = build(&config, &initialization());
+    assert!(layer.parameters().is_empty());
+    let outputs = layer.forward(&Matrix::zeros(1, 8));
+    let (one, small) = (1.0f64, 0.01f64);
+    assert_close(
+        &outputs.data,
+        &[
+            0.0,
+            1.0,
+            0.0,
+            1.0,
+            one.sin(),
+            one.cos(),
+            small.sin(),
+            small.cos(),
+        ],
+    );
+    check_gradients(layer.as_mut(), &Matrix::from_rows([&[0.5; 8][..]]));
+}
+
+/// Whether the first and last of 3 tokens match, from an embedding, position
+/// encodings and a transformer block.
+fn classifier() -> Config {
+    Config {
+        lr: 0.005,
+        epochs: 200,
+        optimizer: "adam".to_string(),
+        loss: "binary-crossentropy".to_string(),
+        metrics: vec!["accuracy".to_string()],
+        initialization: initialization(),
+        layers: vec![
+            ConfigLayer {
+                name: "embedding".to_string(),
+                kind: "embedding".to_string(),
+                input_size: 3,
+                output_size: 12,
+                vocab_size: 4,
+                embedding_size: 4,
+                ..Default::default()
+            },
+            ConfigLayer {
+                name: "positions".to_string(),
+                ..sequence("positional-encoding")
+            },
+            ConfigLayer {
+                name: "encoder".to_string(),
+                hidden_size: 8,
+                activation: "relu".to_string(),
+                ..sequence("transformer")
+            },
+            ConfigLayer {
+                name: "output".to_string(),
+                input_size: 12,
+                output_size: 1,
+                activation: "sigmoid".to_string(),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_network_transformer() {
+    let config = classifier();
+    assert_eq!(
+        config.infer_shapes().unwrap(),
+        vec![vec![3, 4], vec![3, 4], vec![3, 4], vec![1]]
+    );
+
+    let samples: Vec<(Vec<f64>, Vec<f64>)> = (0..27)
+        .map(|n: usize| {
+            let tokens = vec![
+                (n % 3 + 1) as f64,
+                (n / 3 % 3 + 1) as f64,
+                (n / 9 + 1) as f64,
+            ];
+            let label = if tokens[0] == tokens[2] { 1.0 } else { 0.0 };
+            (tokens, vec![label])
+        })
+        .collect();
+    let dataset = Dataset::from_samples(&samples);
+    let mut network = Network::new(config);
+    let before = network.evaluate(&dataset);
+    network.train(&dataset);
+    let after = network.evaluate(&dataset);
+    assert!(after.loss < before.loss);
+    assert_eq!(after.metrics["accuracy"], 1.0);
+
+    let path = std::env::temp_dir().join("rust-nn-test-transformer.json");
+    let path = path.to_str().unwrap();
+    network.save(path).unwrap();
+    let loaded = Network::load(path).unwrap();
+    std::fs::remove_file(path).unwrap();
+    assert_eq!(
+        loaded.forward(&[1.0, 2.0, 1.0]),
+        network.forward(&[1.0, 2.0, 1.0])
+    );
+}
+
+#[test]
+fn test_network_transformer_gradcheck() {
+    let mut config = classifier();
+    config.layers.remove(0);
+    config.loss = "mse".to_string();
+    config.layers[1].causal = true;
+    assert_gradcheck(&config, sequences().row(0), &[0.3], 1e-5);
+}
+
+#[test]
+fn test_attention_config() {
+    let mut invalid = classifier();
+    invalid.layers[2].heads = 3;
+    assert_invalid(&invalid);
+
+    let mut invalid = classifier();
+    invalid.layers[2].steps = 4;
+    assert_invalid(&invalid);
+
+    let mut invalid = classifier();
+    invalid.layers[2].output_size = 4;
+    assert_shape_mismatch(&invalid, 12, 4);
+}
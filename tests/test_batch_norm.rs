@@ -0,0 +1,193 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use rust_nn::config::{Config, ConfigInitialization, ConfigLayer};
+use rust_nn::dataset::Dataset;
+use rust_nn::error::Error;
+use rust_nn::gradcheck::gradcheck;
+use rust_nn::layer::{BatchNorm, Layer, Mode};
+use rust_nn::matrix::Matrix;
+use rust_nn::network::Network;
+use rust_nn::tape::Cache;
+
+mod common;
+
+use common::objective;
+
+fn batch_norm() -> ConfigLayer {
+    ConfigLayer {
+        name: "norm".to_string(),
+        kind: "batch-norm".to_string(),
+        input_size: 3,
+        output_size: 3,
+        ..Default::default()
+    }
+}
+
+fn config() -> Config {
+    Config {
+        lr: 0.1,
+        epochs: 5,
+        batch_size: 4,
+        loss: "mse".to_string(),
+        initialization: ConfigInitialization {
+            method: "xavier-uniform".to_string(),
+            seed: Some(42),
+        },
+        layers: vec![
+            ConfigLayer {
+                name: "hidden".to_string(),
+                input_size: 2,
+                output_size: 3,
+                ..Default::default()
+            },
+            batch_norm(),
+            ConfigLayer {
+                name: "output".to_string(),
+                input_size: 3,
+                output_size: 1,
+                activation: "sigmoid".to_string(),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    }
+}
+
+fn inputs() -> Matrix {
+    Matrix::from_rows([
+        &[0.5, -1.0, 2.0][..],
+        &[1.5, 0.0, -1.0][..],
+        &[-0.5, 2.0, 0.5][..],
+        &[0.0, 1.0, 1.5][..],
+    ])
+}
+
+#[test]
+fn test_batch_norm_gradients() {
+    let mut layer = BatchNorm::new(&batch_norm());
+    layer.gamma = vec![1.5, 0.5, -2.0];
+    layer.beta = vec![0.1, -0.2, 0.3];
+    let inputs = inputs();
+    let weights = Matrix::from_rows([
+        &[0.3, -0.1, 0.7][..],
+        &[-0.4, 0.2, 0.1][..],
+        &[0.9, 0.5, -0.6][..],
+        &[0.2, -0.8, 0.4][..],
+    ]);
+
+    let mut cache = Cache::default();
+    let mut rng = ChaCha8Rng::seed_from_u64(42);
+    layer.forward_cached(&inputs, &mut cache, Mode::Train, &mut rng);
+    for j in 0..3 {
+        let column: Vec<f64> = (0..4).map(|i| cache.outputs.row(i)[j]).collect();
+        let mean = column.iter().sum::<f64>() / 4.0;
+        assert!((mean - layer.beta[j]).abs() < 1e-12);
+    }
+    let errors = layer.backward(&cache, &weights);
+
+    let epsilon = 1e-6;
+    for k in 0..inputs.data.len() {
+        let (mut plus, mut minus) = (inputs.clone(), inputs.clone());
+        plus.data[k] += epsilon;
+        minus.data[k] -= epsilon;
+        let numeric = (objective(&mut layer, &plus, &weights.data)
+            - objective(&mut layer, &minus, &weights.data))
+            / (2.0 * epsilon);
+        assert!((numeric - errors.data[k]).abs() < 1e-6, "input {k}");
+    }
+
+    for group in 0..2 {
+        for k in 0..3 {
+            let original = layer.parameters()[group][k];
+            let mut shifted = |delta: f64| {
+                layer.parameters_mut()[group].0[k] = original + delta;
+                objective(&mut layer, &inputs, &weights.data)
+            };
+            let numeric = (shifted(epsilon) - shifted(-epsilon)) / (2.0 * epsilon);
+            layer.parameters_mut()[group].0[k] = original;
+            let analytic = layer.gradients()[group][k];
+            assert!((numeric - analytic).abs() < 1e-6, "group {group} {k}");
+        }
+    }
+}
+
+#[test]
+fn test_batch_norm_running_statistics() {
+    let mut layer = BatchNorm::new(&batch_norm());
+    let inputs = inputs();
+    // running statistics start as the identity, up to epsilon
+    let mut expected = inputs.clone();
+    expected
+        .data
+        .iter_mut()
+        .for_each(|x| *x /= (1.0 + 1e-5f64).sqrt());
+    assert_eq!(layer.forward(&inputs), expected);
+
+    let mut cache = Cache::default();
+    let mut rng = ChaCha8Rng::seed_from_u64(42);
+    layer.forward_cached(&inputs, &mut cache, Mode::Train, &mut rng);
+    // first feature: mean 0.375, unbiased variance 2.1875 / 3
+    assert!((layer.running_mean[0] - 0.1 * 0.375).abs() < 1e-12);
+    assert!((layer.running_var[0] - (0.9 + 0.1 * 2.1875 / 3.0)).abs() < 1e-12);
+
+    let before = (layer.running_mean.clone(), layer.running_var.clone());
+    layer.forward_cached(&inputs, &mut cache, Mode::Eval, &mut rng);
+    assert_eq!(
+        (layer.running_mean.clone(), layer.running_var.clone()),
+        before
+    );
+    assert_eq!(cache.outputs, layer.forward(&inputs));
+
+    // eval gradients treat the statistics as constants
+    let grads = Matrix::from_rows([&[1.0, 1.0, 1.0][..]; 4]);
+    let errors = layer.backward(&cache, &grads);
+    for i in 0..4 {
+        for (e, v) in errors.row(i).iter().zip(layer.running_var.iter()) {
+            assert!((e - 1.0 / (v + 1e-5).sqrt()).abs() < 1e-12);
+        }
+    }
+}
+
+#[test]
+fn test_network_batch_norm() {
+    let dataset = Dataset::from_samples(&[
+        (vec![0.0, 0.0], vec![0.0]),
+        (vec![0.0, 1.0], vec![1.0]),
+        (vec![1.0, 0.0], vec![1.0]),
+        (vec![1.0, 1.0], vec![0.0]),
+    ]);
+    let mut network = Network::new(config());
+    network.train(&dataset);
+    assert_ne!(network.layers[1].buffers()[0], &[0.0; 3]);
+
+    let path = std::env::temp_dir().join("rust-nn-test-batch-norm.json");
+    let path = path.to_str().unwrap();
+    network.save(path).unwrap();
+    let loaded = Network::load(path).unwrap();
+    std::fs::remove_file(path).unwrap();
+    assert_eq!(loaded.layers[1].buffers(), network.layers[1].buffers());
+    assert_eq!(loaded.forward(&[0.5, 0.5]), network.forward(&[0.5, 0.5]));
+
+    let reports = gradcheck(&config(), &[0.3, -0.7], &[0.2], 1e-6).unwrap();
+    for report in reports {
+        assert!(report.max_relative_error < 1e-5, "{}", report.layer);
+    }
+}
+
+#[test]
+fn test_batch_norm_config() {
+    let mut invalid = config();
+    invalid.layers[1].momentum = 1.5;
+    assert!(matches!(invalid.validate(), Err(Error::Validation(_))));
+
+    let mut invalid = config();
+    invalid.batch_size = 1;
+    assert!(matches!(invalid.validate(), Err(Error::Validation(_))));
+
+    let mut invalid = config();
+    invalid.layers[1].output_size = 2;
+    assert!(matches!(
+        invalid.validate(),
+        Err(Error::ShapeMismatch { .. })
+    ));
+}
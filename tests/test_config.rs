@@ -0,0 +1,110 @@
+use rust_nn::config::{Config, ConfigInitialization, ConfigLayer};
+use rust_nn::error::Error;
+use rust_nn::network::Network;
+
+fn config() -> Config {
+    Config {
+        lr: 0.1,
+        epochs: 10,
+        loss: "mse".to_string(),
+        initialization: ConfigInitialization {
+            method: "zero-centered".to_string(),
+            seed: Some(42),
+        },
+        layers: vec![
+            ConfigLayer {
+                input_size: 3,
+                output_size: 2,
+                name: "layer-1".to_string(),
+                activation: "sigmoid".to_string(),
+                ..Default::default()
+            },
+            ConfigLayer {
+                input_size: 2,
+                output_size: 1,
+                name: "layer-2".to_string(),
+                activation: "sigmoid".to_string(),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_config_validate() {
+    assert!(config().validate().is_ok());
+
+    let mut invalid = config();
+    invalid.lr = 0.0;
+    assert!(matches!(invalid.validate(), Err(Error::Validation(_))));
+
+    let mut invalid = config();
+    invalid.layers[1].input_size = 4;
+    assert!(matches!(
+        invalid.validate(),
+        Err(Error::ShapeMismatch {
+            expected: 2,
+            actual: 4
+        })
+    ));
+
+    let mut invalid = config();
+    invalid.initialization.method = String::new();
+    assert!(matches!(invalid.validate(), Err(Error::Validation(_))));
+
+    let mut invalid = config();
+    invalid.layers[1].kind = "unknown".to_string();
+    assert!(matches!(
+        invalid.validate(),
+        Err(Error::UnknownLayer(kind)) if kind == "unknown"
+    ));
+}
+
+#[test]
+fn test_config_new() {
+    assert!(matches!(
+        Config::new("does-not-exist.json"),
+        Err(Error::Io(_))
+    ));
+
+    let path = std::env::temp_dir().join("rust-nn-test-config-new.json");
+    let path = path.to_str().unwrap();
+    std::fs::write(path, "{\"lr\": 0.1").unwrap();
+    let result = Config::new(path);
+    assert!(matches!(result, Err(Error::Parse(_))));
+
+    std::fs::write(path, serde_json::to_string(&config()).unwrap()).unwrap();
+    let result = Config::new(path);
+    std::fs::remove_file(path).unwrap();
+    assert_eq!(result.unwrap().layers.len(), 2);
+}
+
+#[test]
+fn test_network_try_new() {
+    assert!(Network::try_new(config()).is_ok());
+
+    let mut invalid = config();
+    invalid.layers[0].activation = "unknown".to_string();
+    let result = Network::try_new(invalid);
+    assert!(matches!(result, Err(Error::UnknownActivation(name)) if name == "unknown"));
+
+    let mut invalid = config();
+    invalid.loss = "unknown".to_string();
+    assert!(matches!(
+        Network::try_new(invalid),
+        Err(Error::UnknownLoss(_))
+    ));
+
+    let mut invalid = config();
+    invalid.optimizer = "unknown".to_string();
+    assert!(matches!(
+        Network::try_new(invalid),
+        Err(Error::UnknownOptimizer(_))
+    ));
+
+    let mut invalid = config();
+    invalid.initialization.method = "unknown".to_string();
+    let error = Network::try_new(invalid).err().unwrap();
+    assert_eq!(error.to_string(), "unknown initialization method: unknown");
+}
@@ -0,0 +1,208 @@
+use rust_nn::config::{Config, ConfigLayer};
+use rust_nn::dataset::Dataset;
+use rust_nn::matrix::Matrix;
+use rust_nn::network::Network;
+
+mod common;
+
+use common::{
+    assert_close, assert_gradcheck, assert_invalid, assert_shape_mismatch, build, check_gradients,
+    initialization,
+};
+
+fn conv() -> ConfigLayer {
+    ConfigLayer {
+        name: "conv".to_string(),
+        kind: "conv1d".to_string(),
+        input_size: 12,
+        output_size: 9,
+        channels: 2,
+        filters: 3,
+        kernel_size: 2,
+        stride: 2,
+        padding: 1,
+        dilation: 2,
+        activation: "tanh".to_string(),
+        ..Default::default()
+    }
+}
+
+fn pool(kind: &str, input_size: usize, output_size: usize) -> ConfigLayer {
+    ConfigLayer {
+        name: "pool".to_string(),
+        kind: kind.to_string(),
+        input_size,
+        output_size,
+        channels: 2,
+        kernel_size: 3,
+        stride: 2,
+        ..Default::default()
+    }
+}
+
+fn inputs() -> Matrix {
+    Matrix::from_rows([
+        &[
+            0.5, -1.0, 2.0, 0.1, 1.5, -0.3, 0.0, 0.7, -2.0, 1.1, 0.4, -0.6,
+        ][..],
+        &[
+            1.2, 0.3, -0.8, 0.9, -1.4, 0.2, 0.6, -0.5, 1.8, -0.1, 0.05, 1.3,
+        ][..],
+    ])
+}
+
+#[test]
+fn test_conv1d() {
+    let config = ConfigLayer {
+        input_size: 5,
+        output_size: 3,
+        channels: 1,
+        filters: 1,
+        kernel_size: 2,
+        stride: 1,
+        padding: 0,
+        dilation: 2,
+        activation: "linear".to_string(),
+        ..conv()
+    };
+    let mut layer = build(&config, &initialization());
+    assert_eq!(layer.output_shape(), vec![1, 3]);
+    layer.parameters_mut()[0].0.copy_from_slice(&[1.0, -1.0]);
+    layer.parameters_mut()[1].0.copy_from_slice(&[0.5]);
+    let outputs = layer.forward(&Matrix::from_rows([&[1.0, 2.0, 4.0, 8.0, 16.0][..]]));
+    assert_close(&outputs.data, &[-2.5, -5.5, -11.5]);
+
+    let mut layer = build(&conv(), &initialization());
+    assert_eq!(layer.output_shape(), vec![3, 3]);
+    check_gradients(layer.as_mut(), &inputs());
+}
+
+#[test]
+fn test_pool1d() {
+    let signals = Matrix::from_rows([&[1.0, 3.0, 2.0, 5.0, 4.0, -1.0, -3.0, -2.0, 0.0, -4.0][..]]);
+    let outputs = |kind: &str, output_size: usize| {
+        build(&pool(kind, 10, output_size), &initialization())
+            .forward(&signals)
+            .data
+    };
+    assert_close(&outputs("max-pool1d", 4), &[3.0, 5.0, -1.0, 0.0]);
+    assert_close(&outputs("avg-pool1d", 4), &[2.0, 11.0 / 3.0, -2.0, -2.0]);
+    assert_close(&outputs("global-avg-pool1d", 2), &[3.0, -2.0]);
+
+    // padding positions are ignored
+    let padded = ConfigLayer {
+        padding: 1,
+        output_size: 6,
+        ..pool("avg-pool1d", 10, 6)
+    };
+    assert_close(
+        &build(&padded, &initialization()).forward(&signals).data,
+        &[2.0, 10.0 / 3.0, 4.5, -2.0, -5.0 / 3.0, -2.0],
+    );
+
+    for config in [
+        pool("max-pool1d", 12, 10),
+        pool("avg-pool1d", 12, 10),
+        padded.clone(),
+        pool("global-avg-pool1d", 12, 2),
+    ] {
+        let config = ConfigLayer {
+            input_size: 12,
+            output_size: config.infer_output_size(),
+            ..config
+        };
+        let mut layer = build(&config, &initialization());
+        assert!(layer.parameters().is_empty());
+        check_gradients(layer.as_mut(), &inputs());
+    }
+}
+
+fn config() -> Config {
+    Config {
+        lr: 0.05,
+        epochs: 30,
+        loss: "mse".to_string(),
+        initialization: initialization(),
+        layers: vec![
+            ConfigLayer {
+                name: "conv".to_string(),
+                kind: "conv1d".to_string(),
+                input_size: 8,
+                output_size: 16,
+                filters: 2,
+                kernel_size: 3,
+                padding: 1,
+                activation: "tanh".to_string(),
+                ..Default::default()
+            },
+            ConfigLayer {
+                name: "pool".to_string(),
+                kind: "max-pool1d".to_string(),
+                input_size: 16,
+                output_size: 8,
+                channels: 2,
+                kernel_size: 2,
+                stride: 2,
+                ..Default::default()
+            },
+            ConfigLayer {
+                name: "global".to_string(),
+                kind: "global-avg-pool1d".to_string(),
+                input_size: 8,
+                output_size: 2,
+                channels: 2,
+                ..Default::default()
+            },
+            ConfigLayer {
+                name: "output".to_string(),
+                input_size: 2,
+                output_size: 1,
+                activation: "sigmoid".to_string(),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_network_conv1d() {
+    // rising against falling traces
+    let samples: Vec<(Vec<f64>, Vec<f64>)> = (0..8)
+        .map(|i| {
+            let slope = if i % 2 == 0 { 1.0 } else { -1.0 };
+            let offset = i as f64 * 0.1;
+            let trace = (0..8).map(|t| offset + slope * t as f64 / 8.0).collect();
+            (trace, vec![(i % 2 == 0) as u8 as f64])
+        })
+        .collect();
+    let dataset = Dataset::from_samples(&samples);
+    let mut network = Network::new(config());
+    let before = network.evaluate(&dataset).loss;
+    network.train(&dataset);
+    assert!(network.evaluate(&dataset).loss < before);
+
+    assert_gradcheck(&config(), &samples[0].0, &samples[0].1, 1e-6);
+}
+
+#[test]
+fn test_conv1d_config() {
+    assert!(config().validate().is_ok());
+
+    let mut invalid = config();
+    invalid.layers[0].output_size = 20;
+    invalid.layers[1].input_size = 20;
+    assert_shape_mismatch(&invalid, 16, 20);
+
+    let mut invalid = config();
+    invalid.layers[1].kernel_size = 9;
+    assert_invalid(&invalid);
+
+    let mut invalid = config();
+    invalid.layers[2].channels = 3;
+    assert_invalid(&invalid);
+
+    let mut invalid = config();
+    invalid.layers[0].stride = 0;
+    assert_invalid(&invalid);
+}
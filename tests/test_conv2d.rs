@@ -0,0 +1,286 @@
+use rust_nn::config::{Config, ConfigInitialization, ConfigLayer};
+use rust_nn::dataset::Dataset;
+use rust_nn::matrix::Matrix;
+use rust_nn::network::Network;
+
+mod common;
+
+use common::{
+    assert_close, assert_gradcheck, assert_invalid, assert_shape_mismatch, build, check_gradients,
+    initialization,
+};
+
+fn he_uniform() -> ConfigInitialization {
+    ConfigInitialization {
+        method: "he-uniform".to_string(),
+        ..initialization()
+    }
+}
+
+/// Two samples of 2 channels of 4 × 5 distinct values.
+fn images() -> Matrix {
+    let sample = |offset: f64| -> Vec<f64> {
+        (0..40)
+            .map(|k| ((k * 17 % 40) as f64 - 20.0) / 10.0 + offset)
+            .collect()
+    };
+    let (a, b) = (sample(0.0), sample(0.013));
+    Matrix::from_rows([&a[..], &b[..]])
+}
+
+fn planar(kind: &str, output_size: usize) -> ConfigLayer {
+    ConfigLayer {
+        name: kind.to_string(),
+        kind: kind.to_string(),
+        input_size: 40,
+        output_size,
+        channels: 2,
+        height: 4,
+        width: 5,
+        kernel_size: 2,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_conv2d() {
+    let config = ConfigLayer {
+        input_size: 9,
+        output_size: 4,
+        channels: 1,
+        height: 3,
+        width: 3,
+        ..planar("conv2d", 4)
+    };
+    let mut layer = build(&config, &he_uniform());
+    assert_eq!(layer.output_shape(), vec![1, 2, 2]);
+    layer.parameters_mut()[0]
+        .0
+        .copy_from_slice(&[1.0, 0.0, 0.0, -1.0]);
+    layer.parameters_mut()[1].0.copy_from_slice(&[0.5]);
+    let image = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 9.0, 12.0];
+    let outputs = layer.forward(&Matrix::from_rows([&image[..]]));
+    assert_close(&outputs.data, &[-3.5, -3.5, -4.5, -6.5]);
+
+    let config = ConfigLayer {
+        filters: 3,
+        stride: 2,
+        padding: 1,
+        activation: "tanh".to_string(),
+        ..planar("conv2d", 3 * 3 * 3)
+    };
+    assert!(config.validate().is_ok());
+    let mut layer = build(&config, &he_uniform());
+    assert_eq!(layer.output_shape(), vec![3, 3, 3]);
+    check_gradients(layer.as_mut(), &images());
+
+    let dilated = ConfigLayer {
+        filters: 2,
+        dilation: 2,
+        ..planar("conv2d", 2 * 2 * 3)
+    };
+    check_gradients(build(&dilated, &he_uniform()).as_mut(), &images());
+}
+
+#[test]
+fn test_pool2d() {
+    let image = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 9.0, 12.0];
+    let pool = |kind: &str| ConfigLayer {
+        input_size: 9,
+        output_size: 4,
+        channels: 1,
+        height: 3,
+        width: 3,
+        ..planar(kind, 4)
+    };
+    let outputs = |config: &ConfigLayer| {
+        build(config, &he_uniform()).forward(&Matrix::from_rows([&image[..]]))
+    };
+    assert_close(&outputs(&pool("max-pool2d")).data, &[5.0, 6.0, 9.0, 12.0]);
+    assert_close(&outputs(&pool("avg-pool2d")).data, &[3.0, 4.0, 6.25, 8.0]);
+
+    // padding positions are ignored
+    let padded = ConfigLayer {
+        stride: 2,
+        padding: 1,
+        ..pool("avg-pool2d")
+    };
+    assert_close(&outputs(&padded).data, &[1.0, 2.5, 5.5, 8.0]);
+
+    for config in [
+        planar("max-pool2d", 2 * 3 * 4),
+        planar("avg-pool2d", 2 * 3 * 4),
+        ConfigLayer {
+            stride: 2,
+            padding: 1,
+            ..planar("max-pool2d", 2 * 3 * 3)
+        },
+        planar("flatten", 40),
+    ] {
+        assert!(config.validate().is_ok(), "{}", config.kind);
+        let mut layer = build(&config, &he_uniform());
+        assert!(layer.parameters().is_empty());
+        check_gradients(layer.as_mut(), &images());
+    }
+}
+
+/// Convolution, pooling and a dense classifier over 28 × 28 images.
+fn cnn() -> Config {
+    Config {
+        lr: 0.05,
+        epochs: 3,
+        batch_size: 4,
+        loss: "crossentropy".to_string(),
+        metrics: vec!["accuracy".to_string()],
+        initialization: he_uniform(),
+        input_shape: vec![1, 28, 28],
+        layers: vec![
+            ConfigLayer {
+                name: "conv".to_string(),
+                kind: "conv2d".to_string(),
+                input_size: 784,
+                output_size: 2 * 26 * 26,
+                filters: 2,
+                height: 28,
+                width: 28,
+                kernel_size: 3,
+                activation: "relu".to_string(),
+                ..Default::default()
+            },
+            ConfigLayer {
+                name: "pool".to_string(),
+                kind: "max-pool2d".to_string(),
+                input_size: 2 * 26 * 26,
+                output_size: 2 * 13 * 13,
+                channels: 2,
+                height: 26,
+                width: 26,
+                kernel_size: 2,
+                stride: 2,
+                ..Default::default()
+            },
+            ConfigLayer {
+                name: "flatten".to_string(),
+                kind: "flatten".to_string(),
+                input_size: 2 * 13 * 13,
+                output_size: 2 * 13 * 13,
+                ..Default::default()
+            },
+            ConfigLayer {
+                name: "output".to_string(),
+                input_size: 2 * 13 * 13,
+                output_size: 2,
+                activation: "softmax".to_string(),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    }
+}
+
+/// A vertical or horizontal bar at a varying position, labelled one-hot.
+fn bars() -> Dataset {
+    let samples: Vec<(Vec<f64>, Vec<f64>)> = (0..16)
+        .map(|i| {
+            let (vertical, position) = (i % 2 == 0, 4 + i);
+            let image = (0..784)
+                .map(|p| {
+                    let (row, col) = (p / 28, p % 28);
+                    let on = if vertical { col } else { row };
+                    if on == position || on == position + 1 {
+                        1.0
+                    } else {
+                        0.0
+                    }
+                })
+                .collect();
+            let label = if vertical {
+                vec![1.0, 0.0]
+            } else {
+                vec![0.0, 1.0]
+            };
+            (image, label)
+        })
+        .collect();
+    Dataset::from_samples(&samples)
+}
+
+#[test]
+fn test_network_cnn() {
+    let config = cnn();
+    let shapes = config.infer_shapes().unwrap();
+    assert_eq!(
+        shapes,
+        vec![vec![2, 26, 26], vec![2, 13, 13], vec![338], vec![2]]
+    );
+
+    let dataset = bars();
+    let mut network = Network::new(config);
+    assert_eq!(network.layers[0].output_shape(), vec![2, 26, 26]);
+    let before = network.evaluate(&dataset);
+    network.train(&dataset);
+    let after = network.evaluate(&dataset);
+    assert!(after.loss < before.loss);
+    assert_eq!(after.metrics["accuracy"], 1.0);
+}
+
+#[test]
+fn test_network_cnn_gradcheck() {
+    let config = Config {
+        loss: "mse".to_string(),
+        input_shape: vec![2, 4, 5],
+        layers: vec![
+            ConfigLayer {
+                filters: 2,
+                padding: 1,
+                activation: "tanh".to_string(),
+                ..planar("conv2d", 2 * 5 * 6)
+            },
+            ConfigLayer {
+                input_size: 60,
+                height: 5,
+                width: 6,
+                stride: 2,
+                ..planar("avg-pool2d", 2 * 2 * 3)
+            },
+            ConfigLayer {
+                name: "output".to_string(),
+                input_size: 12,
+                output_size: 2,
+                activation: "sigmoid".to_string(),
+                ..Default::default()
+            },
+        ],
+        initialization: he_uniform(),
+        ..Default::default()
+    };
+    assert!(config.validate().is_ok());
+    assert_gradcheck(&config, images().row(0), &[0.2, 0.7], 1e-6);
+}
+
+#[test]
+fn test_cnn_config() {
+    let mut invalid = cnn();
+    invalid.input_shape = vec![1, 28, 27];
+    assert_shape_mismatch(&invalid, 756, 784);
+
+    // same size, different shape
+    let mut invalid = cnn();
+    invalid.layers[1].channels = 1;
+    invalid.layers[1].height = 52;
+    assert_invalid(&invalid);
+
+    let mut invalid = cnn();
+    invalid.layers[0].output_size = 2 * 28 * 28;
+    assert_shape_mismatch(&invalid, 1352, 1568);
+
+    let mut invalid = cnn();
+    invalid.layers[0].height = 27;
+    assert_invalid(&invalid);
+    invalid.input_shape.clear();
+    assert_shape_mismatch(&invalid, 756, 784);
+
+    let mut invalid = cnn();
+    invalid.layers[1].kernel_size = 30;
+    assert_invalid(&invalid);
+}
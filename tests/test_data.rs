@@ -0,0 +1,158 @@
+use rust_nn::data::{read_csv, read_idx, read_mnist, Column, CsvOptions};
+use rust_nn::error::Error;
+
+fn write(name: &str, contents: &str) -> String {
+    let path = std::env::temp_dir().join(name);
+    std::fs::write(&path, contents).unwrap();
+    path.to_str().unwrap().to_string()
+}
+
+#[test]
+fn test_read_csv_by_name() {
+    let path = write(
+        "rust-nn-test-read-csv-by-name.csv",
+        "x1,x2,species,id\n0.5,1.0,setosa,1\n1.5,2.0,virginica,2\n2.5,3.0,setosa,3\n",
+    );
+    let options = CsvOptions {
+        features: vec!["x1".into(), "x2".into()],
+        targets: vec!["species".into()],
+        one_hot: vec!["species".into()],
+        ..Default::default()
+    };
+    let dataset = read_csv(&path, &options).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(dataset.len(), 3);
+    assert_eq!(
+        dataset.inputs.to_rows(),
+        vec![vec![0.5, 1.0], vec![1.5, 2.0], vec![2.5, 3.0]]
+    );
+    assert_eq!(
+        dataset.targets.to_rows(),
+        vec![vec![1.0, 0.0], vec![0.0, 1.0], vec![1.0, 0.0]]
+    );
+}
+
+#[test]
+fn test_read_csv_by_index() {
+    let path = write("rust-nn-test-read-csv-by-index.csv", "1;2;3\n4;5;6\n");
+    let options = CsvOptions {
+        targets: vec![Column::Index(1)],
+        has_headers: false,
+        delimiter: b';',
+        ..Default::default()
+    };
+    let dataset = read_csv(&path, &options).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(
+        dataset.inputs.to_rows(),
+        vec![vec![1.0, 3.0], vec![4.0, 6.0]]
+    );
+    assert_eq!(dataset.targets.to_rows(), vec![vec![2.0], vec![5.0]]);
+}
+
+#[test]
+fn test_read_csv_errors() {
+    let path = write("rust-nn-test-read-csv-errors.csv", "x,y\n1,0\n2,oops\n");
+    let options = |target: &str| CsvOptions {
+        targets: vec![target.into()],
+        ..Default::default()
+    };
+    assert!(matches!(
+        read_csv(&path, &options("y")),
+        Err(Error::Malformed { line: 3, .. })
+    ));
+    assert!(matches!(
+        read_csv(&path, &options("z")),
+        Err(Error::Validation(_))
+    ));
+    std::fs::write(&path, "x,y\n1,0\n2\n").unwrap();
+    assert!(matches!(
+        read_csv(&path, &options("y")),
+        Err(Error::Malformed { line: 3, .. })
+    ));
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(matches!(
+        read_csv("does-not-exist.csv", &options("y")),
+        Err(Error::Io(_))
+    ));
+}
+
+fn idx_path(name: &str) -> String {
+    format!("{}/tests/data/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+#[test]
+fn test_read_idx() {
+    let idx = read_idx(&idx_path("images-idx3-ubyte")).unwrap();
+    assert_eq!(idx.dims, vec![3, 2, 3]);
+    assert_eq!(idx.data[..6], [0.0, 255.0, 51.0, 102.0, 153.0, 204.0]);
+
+    let compressed = read_idx(&idx_path("images-idx3-ubyte.gz")).unwrap();
+    assert_eq!(compressed.dims, idx.dims);
+    assert_eq!(compressed.data, idx.data);
+
+    // big-endian 32-bit floats
+    let mut bytes = vec![0, 0, 0x0d, 1, 0, 0, 0, 2];
+    bytes.extend_from_slice(&1.5f32.to_be_bytes());
+    bytes.extend_from_slice(&(-2.0f32).to_be_bytes());
+    let path = std::env::temp_dir().join("rust-nn-test-read-idx.idx");
+    let path = path.to_str().unwrap();
+    std::fs::write(path, &bytes).unwrap();
+    let floats = read_idx(path).unwrap();
+    assert_eq!(floats.data, vec![1.5, -2.0]);
+
+    std::fs::write(path, [1, 0, 0x08, 1, 0, 0, 0, 0]).unwrap();
+    assert!(matches!(read_idx(path), Err(Error::Validation(_))));
+    std::fs::write(path, &bytes[..10]).unwrap();
+    assert!(matches!(read_idx(path), Err(Error::ShapeMismatch { .. })));
+    let mut huge = vec![0, 0, 0x08, 3];
+    huge.extend_from_slice(&[0xff; 12]);
+    std::fs::write(path, &huge).unwrap();
+    assert!(matches!(
+        read_idx(path),
+        Err(Error::Malformed { line: 1, .. })
+    ));
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_read_mnist() {
+    for suffix in ["", ".gz"] {
+        let dataset = read_mnist(
+            &idx_path(&format!("images-idx3-ubyte{}", suffix)),
+            &idx_path(&format!("labels-idx1-ubyte{}", suffix)),
+            3,
+        )
+        .unwrap();
+        assert_eq!(dataset.len(), 3);
+        assert_eq!(dataset.inputs.cols, 6);
+        assert_eq!(dataset.sample(0).0, [0.0, 1.0, 0.2, 0.4, 0.6, 0.8]);
+        assert_eq!(
+            dataset.targets.to_rows(),
+            vec![
+                vec![0.0, 0.0, 1.0],
+                vec![1.0, 0.0, 0.0],
+                vec![0.0, 1.0, 0.0]
+            ]
+        );
+    }
+
+    assert!(matches!(
+        read_mnist(
+            &idx_path("images-idx3-ubyte"),
+            &idx_path("labels-idx1-ubyte"),
+            2
+        ),
+        Err(Error::Validation(_))
+    ));
+    // no dimensions, a single value
+    let path = std::env::temp_dir().join("rust-nn-test-read-mnist-scalar.idx");
+    let path = path.to_str().unwrap();
+    std::fs::write(path, [0, 0, 0x08, 0, 7]).unwrap();
+    let result = read_mnist(path, &idx_path("labels-idx1-ubyte"), 3);
+    std::fs::remove_file(path).unwrap();
+    assert!(matches!(result, Err(Error::Validation(_))));
+}
@@ -0,0 +1,52 @@
+use rust_nn::dataset::Dataset;
+use rust_nn::error::Error;
+use rust_nn::matrix::Matrix;
+
+#[test]
+fn test_dataset_from_samples() {
+    let dataset = Dataset::from_samples(&[
+        (vec![0.0, 1.0], vec![1.0]),
+        (vec![2.0, 3.0], vec![0.0]),
+        (vec![4.0, 5.0], vec![1.0]),
+    ]);
+    assert_eq!(dataset.len(), 3);
+    assert_eq!(dataset.sample(1), (&[2.0, 3.0][..], &[0.0][..]));
+
+    let (inputs, targets) = dataset.batch(&[2, 0]);
+    assert_eq!(inputs.to_rows(), vec![vec![4.0, 5.0], vec![0.0, 1.0]]);
+    assert_eq!(targets.to_rows(), vec![vec![1.0], vec![1.0]]);
+}
+
+#[test]
+fn test_dataset_new_shape_mismatch() {
+    let inputs = Matrix::zeros(3, 2);
+    assert!(Dataset::new(inputs.clone(), Matrix::zeros(3, 1)).is_ok());
+    assert!(matches!(
+        Dataset::new(inputs, Matrix::zeros(2, 1)),
+        Err(Error::ShapeMismatch {
+            expected: 3,
+            actual: 2
+        })
+    ));
+}
+
+#[test]
+fn test_dataset_from_samples_ragged() {
+    let ragged_inputs = [(vec![0.0, 1.0], vec![1.0]), (vec![2.0], vec![0.0])];
+    assert!(matches!(
+        Dataset::try_from_samples(&ragged_inputs),
+        Err(Error::ShapeMismatch {
+            expected: 2,
+            actual: 1
+        })
+    ));
+    let ragged_targets = [(vec![0.0], vec![1.0]), (vec![2.0], vec![0.0, 1.0])];
+    assert!(matches!(
+        Dataset::try_from_samples(&ragged_targets),
+        Err(Error::ShapeMismatch {
+            expected: 1,
+            actual: 2
+        })
+    ));
+    assert!(Dataset::try_from_samples(&[]).unwrap().is_empty());
+}
@@ -0,0 +1,146 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use rust_nn::config::{Config, ConfigInitialization, ConfigLayer};
+use rust_nn::dataset::Dataset;
+use rust_nn::error::Error;
+use rust_nn::gradcheck::gradcheck;
+use rust_nn::layer::{Dropout, Layer, Mode};
+use rust_nn::matrix::Matrix;
+use rust_nn::network::Network;
+use rust_nn::tape::{Cache, Tape};
+
+fn dropout(rate: f64) -> ConfigLayer {
+    ConfigLayer {
+        name: "dropout".to_string(),
+        kind: "dropout".to_string(),
+        input_size: 4,
+        output_size: 4,
+        rate,
+        ..Default::default()
+    }
+}
+
+fn config() -> Config {
+    Config {
+        lr: 0.1,
+        epochs: 5,
+        loss: "mse".to_string(),
+        initialization: ConfigInitialization {
+            method: "xavier-uniform".to_string(),
+            seed: Some(42),
+        },
+        layers: vec![
+            ConfigLayer {
+                name: "hidden".to_string(),
+                input_size: 3,
+                output_size: 4,
+                activation: "tanh".to_string(),
+                ..Default::default()
+            },
+            dropout(0.5),
+            ConfigLayer {
+                name: "output".to_string(),
+                input_size: 4,
+                output_size: 2,
+                activation: "sigmoid".to_string(),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_dropout_layer() {
+    let mut layer = Dropout::new(&dropout(0.25));
+    assert_eq!(layer.rate, 0.25);
+    assert!(layer.parameters().is_empty());
+
+    let inputs = Matrix {
+        rows: 500,
+        cols: 4,
+        data: vec![1.0; 2000],
+    };
+    assert_eq!(layer.forward(&inputs), inputs);
+
+    let mut rng = ChaCha8Rng::seed_from_u64(42);
+    let mut cache = Cache::default();
+    layer.forward_cached(&inputs, &mut cache, Mode::Eval, &mut rng);
+    assert_eq!(cache.outputs, inputs);
+
+    layer.forward_cached(&inputs, &mut cache, Mode::Train, &mut rng);
+    let dropped = cache.outputs.data.iter().filter(|o| **o == 0.0).count();
+    assert!((dropped as f64 / 2000.0 - 0.25).abs() < 0.05);
+    assert!(cache
+        .outputs
+        .data
+        .iter()
+        .all(|o| *o == 0.0 || (o - 1.0 / 0.75).abs() < 1e-12));
+
+    // gradients only flow through the kept inputs, with the same scaling
+    let errors = layer.backward(&cache, &inputs);
+    assert_eq!(errors, cache.outputs);
+}
+
+#[test]
+fn test_network_dropout_mode() {
+    let inputs = Matrix::from_rows([&[0.5, -0.5, 0.25][..]]);
+    let mut network = Network::new(config());
+    assert_eq!(network.mode(), Mode::Train);
+
+    let outputs = network.forward(inputs.row(0));
+
+    // training passes drop units in train mode only
+    let mut tape = Tape::default();
+    network.train_mode();
+    network.forward_cached(&inputs, &mut tape);
+    assert_ne!(tape.outputs().unwrap().row(0), outputs);
+
+    network.eval_mode();
+    assert_eq!(network.mode(), Mode::Eval);
+    network.forward_cached(&inputs, &mut tape);
+    assert_eq!(tape.outputs().unwrap().row(0), outputs);
+
+    // inference ignores the mode
+    network.train_mode();
+    assert_eq!(network.forward(inputs.row(0)), outputs);
+    network.eval_mode();
+
+    let dataset = Dataset::from_samples(&[(vec![0.5, -0.5, 0.25], vec![1.0, 0.0])]);
+    network.train(&dataset);
+    assert_eq!(network.mode(), Mode::Eval);
+
+    // masks are drawn from the initialization seed
+    let train = || {
+        let mut network = Network::new(config());
+        network.train(&dataset);
+        network.layers[2].parameters()[0].to_vec()
+    };
+    assert_eq!(train(), train());
+}
+
+#[test]
+fn test_dropout_config() {
+    let mut invalid = config();
+    invalid.layers[1].rate = 1.0;
+    assert!(matches!(invalid.validate(), Err(Error::Validation(_))));
+
+    let mut invalid = config();
+    invalid.layers[1].output_size = 3;
+    assert!(matches!(
+        invalid.validate(),
+        Err(Error::ShapeMismatch { .. })
+    ));
+
+    let mut invalid = config();
+    invalid.layers[1].kind = "unknown".to_string();
+    assert!(matches!(
+        Network::try_new(invalid),
+        Err(Error::UnknownLayer(_))
+    ));
+
+    let reports = gradcheck(&config(), &[0.3, -0.7, 0.9], &[0.2, 0.5], 1e-6).unwrap();
+    for report in reports {
+        assert!(report.max_relative_error < 1e-5, "{}", report.layer);
+    }
+}
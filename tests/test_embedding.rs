@@ -0,0 +1,342 @@
+use std::collections::HashMap;
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use rust_nn::config::{Config, ConfigLayer};
+use rust_nn::data::read_embeddings;
+use rust_nn::dataset::Dataset;
+use rust_nn::error::Error;
+use rust_nn::layer::Mode;
+use rust_nn::matrix::Matrix;
+use rust_nn::network::Network;
+use rust_nn::optimizer::get_optimizer;
+use rust_nn::tape::Cache;
+
+mod common;
+
+use common::{
+    assert_gradcheck, assert_invalid, assert_shape_mismatch, build, initialization, try_build,
+};
+
+/// Three tokens from a vocabulary of 5, embedded in 2 dimensions.
+fn embedding() -> ConfigLayer {
+    ConfigLayer {
+        name: "embedding".to_string(),
+        kind: "embedding".to_string(),
+        input_size: 3,
+        output_size: 6,
+        vocab_size: 5,
+        embedding_size: 2,
+        ..Default::default()
+    }
+}
+
+fn table() -> [f64; 10] {
+    [0.0, 0.1, 1.0, 1.1, 2.0, 2.1, 3.0, 3.1, 4.0, 4.1]
+}
+
+/// Writes `contents` to a file of the temporary directory.
+fn write(name: &str, contents: &str) -> String {
+    let path = std::env::temp_dir().join(format!("rust-nn-test-{name}.txt"));
+    std::fs::write(&path, contents).unwrap();
+    path.to_str().unwrap().to_string()
+}
+
+#[test]
+fn test_embedding_lookup() {
+    let config = embedding();
+    assert!(config.validate().is_ok());
+    let mut layer = build(&config, &initialization());
+    assert_eq!(layer.output_shape(), vec![3, 2]);
+    layer.parameters_mut()[0].0.copy_from_slice(&table());
+
+    let tokens = Matrix::from_rows([&[4.0, 0.0, 2.0][..], &[1.0, 1.0, 3.0][..]]);
+    let outputs = layer.forward(&tokens);
+    assert_eq!(outputs.row(0), &[4.0, 4.1, 0.0, 0.1, 2.0, 2.1]);
+    assert_eq!(outputs.row(1), &[1.0, 1.1, 1.0, 1.1, 3.0, 3.1]);
+
+    // gradients accumulate into the rows looked up
+    let mut cache = Cache::default();
+    let mut rng = ChaCha8Rng::seed_from_u64(42);
+    layer.forward_cached(&tokens, &mut cache, Mode::Train, &mut rng);
+    let grads = Matrix::from_rows([&[1.0; 6][..], &[0.5; 6][..]]);
+    let input_grads = layer.backward(&cache, &grads);
+    assert!(input_grads.data.iter().all(|g| *g == 0.0));
+    assert_eq!(
+        layer.gradients()[0],
+        &[1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 0.5, 0.5, 1.0, 1.0]
+    );
+}
+
+#[test]
+fn test_embedding_unknown_token() {
+    let layer = build(&embedding(), &initialization());
+    assert!(layer
+        .check_inputs(&Matrix::from_rows([&[0.0, 4.0, 1.0][..]]))
+        .is_ok());
+    for token in [5.0, -1.0, f64::NAN] {
+        let tokens = Matrix::from_rows([&[0.0, token, 1.0][..]]);
+        assert!(matches!(
+            layer.check_inputs(&tokens),
+            Err(Error::Validation(_))
+        ));
+    }
+
+    let mut network = Network::new(classifier());
+    let unknown = [3.0, 6.0, 0.0];
+    assert!(matches!(
+        network.try_forward(&unknown),
+        Err(Error::Validation(_))
+    ));
+    let dataset = Dataset::from_samples(&[(unknown.to_vec(), vec![1.0])]);
+    assert!(matches!(
+        network.try_evaluate(&dataset),
+        Err(Error::Validation(_))
+    ));
+    assert!(matches!(
+        network.try_train(&dataset),
+        Err(Error::Validation(_))
+    ));
+}
+
+#[test]
+fn test_embedding_padding() {
+    let config = ConfigLayer {
+        padding_index: Some(0),
+        ..embedding()
+    };
+    let mut layer = build(&config, &initialization());
+    assert_eq!(&layer.parameters()[0][..2], &[0.0, 0.0]);
+
+    let tokens = Matrix::from_rows([&[0.0, 2.0, 0.0][..]]);
+    let outputs = layer.forward(&tokens);
+    assert_eq!(&outputs.data[..2], &[0.0, 0.0]);
+    assert_eq!(&outputs.data[4..], &[0.0, 0.0]);
+
+    let mut cache = Cache::default();
+    let mut rng = ChaCha8Rng::seed_from_u64(42);
+    layer.forward_cached(&tokens, &mut cache, Mode::Train, &mut rng);
+    layer.backward(&cache, &Matrix::from_rows([&[1.0; 6][..]]));
+    assert_eq!(&layer.gradients()[0][..2], &[0.0, 0.0]);
+    assert_eq!(&layer.gradients()[0][4..6], &[1.0, 1.0]);
+
+    let invalid = ConfigLayer {
+        padding_index: Some(5),
+        ..embedding()
+    };
+    assert!(matches!(invalid.validate(), Err(Error::Validation(_))));
+}
+
+#[test]
+fn test_embedding_sparse_update() {
+    for name in ["sgd", "momentum", "adam"] {
+        let config = ConfigLayer {
+            l2: 0.1,
+            weight_decay: 0.1,
+            ..embedding()
+        };
+        let mut layer = build(&config, &initialization());
+        let mut optimizer = get_optimizer(name, HashMap::new()).unwrap();
+        let before = layer.parameters()[0].to_vec();
+
+        let mut cache = Cache::default();
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        let tokens = Matrix::from_rows([&[1.0, 3.0, 1.0][..]]);
+        layer.forward_cached(&tokens, &mut cache, Mode::Train, &mut rng);
+        layer.backward(&cache, &Matrix::from_rows([&[1.0; 6][..]]));
+        layer.update(0.1, optimizer.as_mut(), 0, 1);
+
+        let after = layer.parameters()[0].to_vec();
+        for v in 0..5 {
+            let changed = (before[2 * v..2 * v + 2]) != (after[2 * v..2 * v + 2]);
+            assert_eq!(changed, v == 1 || v == 3, "{name} row {v}");
+        }
+        assert!(layer.gradients()[0].iter().all(|g| *g == 0.0));
+
+        // untouched rows stay put on later steps, even with momentum
+        layer.update(0.1, optimizer.as_mut(), 0, 1);
+        assert_eq!(layer.parameters()[0], &after[..], "{name}");
+    }
+}
+
+#[test]
+fn test_read_embeddings() {
+    let path = write(
+        "embeddings",
+        "the 0.0 0.1\nof 1.0 1.1\n\nand 2.0 2.1\nto 3.0 3.1\na 4.0 4.1\n",
+    );
+    let weights = read_embeddings(&path, true).unwrap();
+    assert_eq!((weights.rows, weights.cols), (5, 2));
+    assert_eq!(weights.data, table());
+
+    let config = ConfigLayer {
+        embeddings: Some(path.clone()),
+        padding_index: Some(4),
+        ..embedding()
+    };
+    let layer = build(&config, &initialization());
+    assert_eq!(&layer.parameters()[0][..8], &table()[..8]);
+    assert_eq!(&layer.parameters()[0][8..], &[0.0, 0.0]);
+
+    let config = ConfigLayer {
+        vocab_size: 4,
+        output_size: 6,
+        embeddings: Some(path.clone()),
+        ..embedding()
+    };
+    assert!(matches!(
+        try_build(&config, &initialization()),
+        Err(Error::ShapeMismatch {
+            expected: 8,
+            actual: 10
+        })
+    ));
+    std::fs::remove_file(&path).unwrap();
+
+    // words that read as numbers are still words
+    let path = write(
+        "embeddings-numeric",
+        "nan 0.0 0.1\ninf 1.0 1.1\n2010 2.0 2.1\n",
+    );
+    let weights = read_embeddings(&path, true).unwrap();
+    assert_eq!(weights.data, table()[..6]);
+    std::fs::remove_file(&path).unwrap();
+
+    let path = write("embeddings-bare", "0.5 -0.5\n1.5 -1.5\n");
+    assert_eq!(
+        read_embeddings(&path, false).unwrap().data,
+        [0.5, -0.5, 1.5, -1.5]
+    );
+    let config = ConfigLayer {
+        vocab_size: 2,
+        output_size: 6,
+        embeddings: Some(path.clone()),
+        embedding_words: false,
+        ..embedding()
+    };
+    assert_eq!(
+        build(&config, &initialization()).parameters()[0],
+        [0.5, -0.5, 1.5, -1.5]
+    );
+    // a word on some lines only is malformed either way
+    std::fs::write(&path, "0.5 -0.5\nthe 1.5 -1.5\n").unwrap();
+    assert!(matches!(
+        read_embeddings(&path, false),
+        Err(Error::Malformed { line: 2, .. })
+    ));
+    assert!(matches!(
+        read_embeddings(&path, true),
+        Err(Error::Malformed { line: 2, .. })
+    ));
+    std::fs::remove_file(&path).unwrap();
+
+    let path = write("embeddings-malformed", "the 0.0 0.1\nof 1.0 x\n");
+    assert!(matches!(
+        read_embeddings(&path, true),
+        Err(Error::Malformed { line: 2, .. })
+    ));
+    std::fs::remove_file(&path).unwrap();
+
+    let path = write("embeddings-ragged", "the 0.0 0.1\nof 1.0\n");
+    assert!(matches!(
+        read_embeddings(&path, true),
+        Err(Error::Malformed { line: 2, .. })
+    ));
+    std::fs::remove_file(&path).unwrap();
+}
+
+/// Whether a sequence of 3 tokens from a vocabulary of 6 contains token 5,
+/// padded with token 0.
+fn classifier() -> Config {
+    Config {
+        lr: 0.05,
+        epochs: 200,
+        optimizer: "adam".to_string(),
+        loss: "binary-crossentropy".to_string(),
+        metrics: vec!["accuracy".to_string()],
+        initialization: initialization(),
+        layers: vec![
+            ConfigLayer {
+                vocab_size: 6,
+                embedding_size: 4,
+                output_size: 12,
+                padding_index: Some(0),
+                ..embedding()
+            },
+            ConfigLayer {
+                name: "lstm".to_string(),
+                kind: "lstm".to_string(),
+                input_size: 12,
+                output_size: 6,
+                steps: 3,
+                hidden_size: 6,
+                ..Default::default()
+            },
+            ConfigLayer {
+                name: "output".to_string(),
+                input_size: 6,
+                output_size: 1,
+                activation: "sigmoid".to_string(),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_network_embedding() {
+    let config = classifier();
+    assert_eq!(
+        config.infer_shapes().unwrap(),
+        vec![vec![3, 4], vec![6], vec![1]]
+    );
+
+    let samples: Vec<(Vec<f64>, Vec<f64>)> = (0..36)
+        .map(|n: usize| {
+            let tokens = vec![(n % 6) as f64, (n / 6) as f64, 0.0];
+            let label = if tokens.contains(&5.0) { 1.0 } else { 0.0 };
+            (tokens, vec![label])
+        })
+        .collect();
+    let dataset = Dataset::from_samples(&samples);
+    let mut network = Network::new(config);
+    network.train(&dataset);
+    assert_eq!(network.evaluate(&dataset).metrics["accuracy"], 1.0);
+
+    assert_gradcheck(&classifier(), &[3.0, 5.0, 0.0], &[1.0], 1e-5);
+}
+
+#[test]
+fn test_network_embedding_load() {
+    let rows: Vec<String> = (0..6)
+        .map(|v| format!("w{v} {v}.0 {v}.1 {v}.2 {v}.3"))
+        .collect();
+    let embeddings = write("embeddings-load", &rows.join("\n"));
+    let mut config = classifier();
+    config.layers[0].embeddings = Some(embeddings.clone());
+    let network = Network::new(config);
+
+    let path = std::env::temp_dir().join("rust-nn-test-embedding-load.json");
+    let path = path.to_str().unwrap();
+    network.save(path).unwrap();
+    // the saved weights are enough, the embeddings file may be gone
+    std::fs::remove_file(&embeddings).unwrap();
+    let loaded = Network::load(path).unwrap();
+    std::fs::remove_file(path).unwrap();
+
+    assert_eq!(loaded.config.layers[0].embeddings, Some(embeddings));
+    let tokens = [3.0, 5.0, 0.0];
+    assert_eq!(loaded.forward(&tokens), network.forward(&tokens));
+}
+
+#[test]
+fn test_embedding_config() {
+    let mut invalid = classifier();
+    invalid.layers[0].output_size = 6;
+    assert_shape_mismatch(&invalid, 12, 6);
+
+    let mut invalid = classifier();
+    invalid.layers[0].vocab_size = 0;
+    assert_invalid(&invalid);
+}
@@ -0,0 +1,89 @@
+use rust_nn::activation::ACTIVATIONS;
+use rust_nn::config::{Config, ConfigInitialization, ConfigLayer};
+use rust_nn::gradcheck::gradcheck;
+use rust_nn::loss::LOSSES;
+
+fn config(activation: &str, output: &str, loss: &str) -> Config {
+    Config {
+        lr: 0.1,
+        epochs: 1,
+        loss: loss.to_string(),
+        initialization: ConfigInitialization {
+            method: "uniform-distribution".to_string(),
+            seed: Some(42),
+        },
+        layers: vec![
+            ConfigLayer {
+                input_size: 3,
+                output_size: 4,
+                name: "hidden".to_string(),
+                activation: activation.to_string(),
+                ..Default::default()
+            },
+            ConfigLayer {
+                input_size: 4,
+                output_size: 3,
+                name: "output".to_string(),
+                activation: output.to_string(),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_gradcheck_activations() {
+    let inputs = [0.3, -0.7, 0.9];
+    let targets = [0.2, 0.5, 0.3];
+    for activation in ACTIVATIONS {
+        // heaviside uses a straight-through derivative, its true derivative is zero
+        if activation == "heaviside" {
+            continue;
+        }
+        let config = config(activation, activation, "mse");
+        for report in gradcheck(&config, &inputs, &targets, 1e-6).unwrap() {
+            assert!(
+                report.max_relative_error < 1e-4,
+                "{} layer {}: relative error {}",
+                activation,
+                report.layer,
+                report.max_relative_error
+            );
+        }
+    }
+}
+
+#[test]
+fn test_gradcheck_losses() {
+    let inputs = [0.3, -0.7, 0.9];
+    for loss in LOSSES {
+        let (output, targets) = match loss {
+            "crossentropy" => ("softmax", [0.0, 1.0, 0.0]),
+            _ => ("sigmoid", [0.0, 1.0, 1.0]),
+        };
+        let config = config("tanh", output, loss);
+        for report in gradcheck(&config, &inputs, &targets, 1e-6).unwrap() {
+            assert!(
+                report.max_relative_error < 1e-4,
+                "{} layer {}: relative error {}",
+                loss,
+                report.layer,
+                report.max_relative_error
+            );
+        }
+    }
+}
+
+#[test]
+fn test_gradcheck_report() {
+    let config = config("tanh", "sigmoid", "mse");
+    let reports = gradcheck(&config, &[0.3, -0.7, 0.9], &[0.0, 1.0, 1.0], 1e-6).unwrap();
+    assert_eq!(reports.len(), 2);
+    assert_eq!(reports[0].layer, "hidden");
+    assert_eq!(reports[1].layer, "output");
+
+    let mut invalid = config.clone();
+    invalid.loss = "unknown".to_string();
+    assert!(gradcheck(&invalid, &[0.3, -0.7, 0.9], &[0.0, 1.0, 1.0], 1e-6).is_err());
+}
@@ -0,0 +1,143 @@
+use rust_nn::config::{ConfigInitialization, ConfigLayer};
+use rust_nn::error::Error;
+use rust_nn::initialization::get_initialization;
+use rust_nn::layer::get_layer;
+
+fn samples(method: &str, seed: u64, fan_in: usize, fan_out: usize) -> Vec<f64> {
+    let config = ConfigInitialization {
+        method: method.to_string(),
+        seed: Some(seed),
+    };
+    let mut initialization = get_initialization(&config).unwrap();
+    (0..20000)
+        .map(|_| initialization.init(fan_in, fan_out))
+        .collect()
+}
+
+fn std(samples: &[f64]) -> f64 {
+    let n = samples.len() as f64;
+    let mean = samples.iter().sum::<f64>() / n;
+    (samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n).sqrt()
+}
+
+#[test]
+fn test_initialization_uniform_limits() {
+    let cases: [(&str, f64); 2] = [
+        ("xavier-uniform", 6.0 / (100.0 + 50.0)),
+        ("he-uniform", 6.0 / 100.0),
+    ];
+    for (method, limit) in cases {
+        let samples = samples(method, 42, 100, 50);
+        let limit = limit.sqrt();
+        assert!(samples.iter().all(|x| x.abs() <= limit), "{}", method);
+        // the standard deviation of U(-a, a) is a / sqrt(3)
+        assert!(
+            (std(&samples) - limit / 3f64.sqrt()).abs() < 0.01 * limit,
+            "{}",
+            method
+        );
+    }
+}
+
+#[test]
+fn test_initialization_normal_std() {
+    let cases: [(&str, f64); 3] = [
+        ("xavier-normal", 2.0 / (100.0 + 50.0)),
+        ("he-normal", 2.0 / 100.0),
+        ("lecun-normal", 1.0 / 100.0),
+    ];
+    for (method, variance) in cases {
+        let samples = samples(method, 42, 100, 50);
+        let expected = variance.sqrt();
+        assert!(
+            (std(&samples) - expected).abs() < 0.02 * expected,
+            "{}: expected {}, got {}",
+            method,
+            expected,
+            std(&samples)
+        );
+    }
+}
+
+#[test]
+fn test_initialization_ranges() {
+    for method in ["uniform-distribution", "zero-centered"] {
+        let samples = samples(method, 42, 10, 10);
+        assert!(samples.iter().all(|x| (-1.0..1.0).contains(x)), "{method}");
+        assert!(samples.iter().any(|x| *x < 0.0), "{method}");
+    }
+}
+
+#[test]
+fn test_initialization_biases() {
+    let kinds = [
+        ConfigLayer {
+            input_size: 4,
+            output_size: 3,
+            ..Default::default()
+        },
+        ConfigLayer {
+            kind: "conv1d".to_string(),
+            input_size: 8,
+            output_size: 6,
+            channels: 2,
+            filters: 2,
+            kernel_size: 2,
+            ..Default::default()
+        },
+        ConfigLayer {
+            kind: "rnn".to_string(),
+            input_size: 6,
+            output_size: 4,
+            steps: 3,
+            hidden_size: 4,
+            ..Default::default()
+        },
+    ];
+    for method in ["xavier-uniform", "he-normal", "lecun-normal"] {
+        let config = ConfigInitialization {
+            method: method.to_string(),
+            seed: Some(42),
+        };
+        let mut initialization = get_initialization(&config).unwrap();
+        for kind in kinds.iter() {
+            let layer = get_layer(kind, &mut initialization).unwrap();
+            let parameters = layer.parameters();
+            assert!(
+                parameters[0].iter().any(|w| *w != 0.0),
+                "{method} {}",
+                kind.kind
+            );
+            assert!(
+                parameters[1].iter().all(|b| *b == 0.0),
+                "{method} {}",
+                kind.kind
+            );
+        }
+    }
+}
+
+#[test]
+fn test_initialization_seeded() {
+    for method in [
+        "xavier-uniform",
+        "he-normal",
+        "lecun-normal",
+        "zero-centered",
+    ] {
+        assert_eq!(samples(method, 7, 10, 10), samples(method, 7, 10, 10));
+        assert_ne!(samples(method, 7, 10, 10), samples(method, 8, 10, 10));
+    }
+}
+
+#[test]
+fn test_initialization_unknown() {
+    let config = ConfigInitialization {
+        method: "unknown".to_string(),
+        seed: None,
+    };
+    assert!(matches!(
+        get_initialization(&config),
+        Err(Error::UnknownInitialization(_))
+    ));
+}
@@ -1,6 +1,16 @@
-use rust_nn::config::{ConfigInitialization, ConfigLayer};
+use rust_nn::config::{Config, ConfigInitialization, ConfigLayer};
+use rust_nn::error::Error;
+use rust_nn::gradcheck::gradcheck;
 use rust_nn::initialization::get_initialization;
-use rust_nn::layer::Layer;
+use rust_nn::layer::{get_layer, Dense, Layer, Mode, LAYERS};
+use rust_nn::matrix::Matrix;
+use rust_nn::network::Network;
+use rust_nn::optimizer::Sgd;
+use rust_nn::tape::Cache;
+
+fn forward(layer: &Dense, inputs: &[f64]) -> Vec<f64> {
+    layer.forward(&Matrix::from_rows([inputs])).data
+}
 
 #[test]
 fn test_layer_new() {
@@ -9,13 +19,14 @@ fn test_layer_new() {
         output_size: 2,
         name: "test".to_string(),
         activation: "sigmoid".to_string(),
+        ..Default::default()
     };
     let initialization = ConfigInitialization {
         method: "uniform-distribution".to_string(),
         seed: Some(42),
     };
-    let mut initialization = get_initialization(&initialization);
-    let layer = Layer::new(&config, &mut initialization);
+    let mut initialization = get_initialization(&initialization).unwrap();
+    let layer = Dense::new(&config, &mut initialization).unwrap();
     let weights = [
         0.900550815344968,
         -0.1449671942869606,
@@ -26,9 +37,9 @@ fn test_layer_new() {
     ];
 
     let mut i = 0;
-    layer.neurons.iter().for_each(|neuron| {
+    layer.neurons().iter().for_each(|neuron| {
         assert_eq!(neuron.weights.len(), 3);
-        // assert!(neuron.bias >= 0.0 && neuron.bias < 1.0);
+        assert!((-1.0..1.0).contains(&neuron.bias));
         neuron.weights.iter().for_each(|&w| {
             assert!((w - weights[i]).abs() < 1e-7);
             i += 1;
@@ -43,18 +54,19 @@ fn test_layer_forward() {
         output_size: 2,
         name: "test".to_string(),
         activation: "sigmoid".to_string(),
+        ..Default::default()
     };
     let initialization = ConfigInitialization {
         method: "uniform-distribution".to_string(),
         seed: Some(42),
     };
-    let mut initialization = get_initialization(&initialization);
-    let layer = Layer::new(&config, &mut initialization);
+    let mut initialization = get_initialization(&initialization).unwrap();
+    let layer = Dense::new(&config, &mut initialization).unwrap();
     let inputs = vec![0.5, 0.5, 0.5];
-    let outputs = layer.forward(&inputs);
+    let outputs = forward(&layer, &inputs);
     assert_eq!(outputs.len(), 2);
     outputs.iter().for_each(|&output| {
-        assert!(output >= 0.0 && output < 1.0); // Since Sigmoid activation function output is in range [0.0, 1.0)
+        assert!((0.0..1.0).contains(&output)); // Since Sigmoid activation function output is in range [0.0, 1.0)
     });
 }
 
@@ -69,31 +81,317 @@ fn test_layer_backward() {
         output_size: 2,
         name: "test".to_string(),
         activation: "sigmoid".to_string(),
+        ..Default::default()
     };
-    let mut initialization = get_initialization(&initialization);
-    let mut layer = Layer::new(&config, &mut initialization);
+    let mut initialization = get_initialization(&initialization).unwrap();
+    let mut layer = Dense::new(&config, &mut initialization).unwrap();
     let inputs = vec![0.5, 0.5, 0.5];
     let output_grads = vec![0.1, 0.1];
     let lr = 0.01;
 
-    let input_grads = layer.backward(lr, &inputs, &output_grads);
+    let mut cache = Cache::default();
+    layer.forward_cached(
+        &Matrix::from_rows([inputs.as_slice()]),
+        &mut cache,
+        Mode::Eval,
+        &mut rand::thread_rng(),
+    );
+    let input_grads = layer.backward(&cache, &Matrix::from_rows([output_grads.as_slice()]));
+    layer.update(lr, &mut Sgd {}, 0, 1);
 
     // Check that the input gradients are calculated correctly
-    assert_eq!(input_grads.len(), inputs.len());
+    assert_eq!(input_grads.cols, inputs.len());
 
     let weights = [
-        0.9004333134888671,
-        -0.14508469614306138,
-        0.2546035405385798,
-        -0.7001997612754509,
-        -0.3840363826602815,
-        0.6076280324951526,
+        0.9004467308012107,
+        -0.14507127883071783,
+        0.25461695785092336,
+        -0.7001945438357362,
+        -0.3840311652205668,
+        0.6076332499348673,
     ];
     let mut i = 0;
-    layer.neurons.iter().for_each(|neuron| {
+    layer.neurons().iter().for_each(|neuron| {
         neuron.weights.iter().for_each(|&w| {
             assert!((w - weights[i]).abs() < 1e-7);
             i += 1;
         });
     });
 }
+
+#[test]
+fn test_layer_forward_cached() {
+    let config = ConfigLayer {
+        input_size: 3,
+        output_size: 2,
+        name: "test".to_string(),
+        activation: "tanh".to_string(),
+        ..Default::default()
+    };
+    let initialization = ConfigInitialization {
+        method: "uniform-distribution".to_string(),
+        seed: Some(42),
+    };
+    let mut initialization = get_initialization(&initialization).unwrap();
+    let mut layer = Dense::new(&config, &mut initialization).unwrap();
+    let inputs = vec![0.5, -0.5, 0.25];
+    let mut cache = Cache::default();
+    layer.forward_cached(
+        &Matrix::from_rows([inputs.as_slice()]),
+        &mut cache,
+        Mode::Eval,
+        &mut rand::thread_rng(),
+    );
+
+    assert_eq!(cache.inputs.row(0), inputs);
+    assert_eq!(
+        cache.pre_activations,
+        layer.pre_activations(&Matrix::from_rows([inputs.as_slice()]))
+    );
+    assert_eq!(cache.outputs.row(0), forward(&layer, &inputs));
+    for (z, a) in cache
+        .pre_activations
+        .data
+        .iter()
+        .zip(cache.outputs.data.iter())
+    {
+        assert_eq!(z.tanh(), *a);
+    }
+}
+
+#[test]
+fn test_layer_backward_input_gradient() {
+    // d(sum(grads * outputs)) / d(inputs) against central finite differences
+    let activations = [
+        "relu",
+        "tanh",
+        "linear",
+        "sigmoid",
+        "softmax",
+        "sparsemax",
+        "log-softmax",
+        "multiquadratics",
+    ];
+    let initialization = ConfigInitialization {
+        method: "uniform-distribution".to_string(),
+        seed: Some(42),
+    };
+    let inputs = vec![0.3, -0.7, 0.9];
+    let output_grads = vec![0.4, -0.2, 0.1, 0.8];
+    let h = 1e-6;
+
+    for activation in activations {
+        let config = ConfigLayer {
+            input_size: 3,
+            output_size: 4,
+            name: "test".to_string(),
+            activation: activation.to_string(),
+            ..Default::default()
+        };
+        let mut initialization = get_initialization(&initialization).unwrap();
+        let mut layer = Dense::new(&config, &mut initialization).unwrap();
+        let objective = |layer: &Dense, inputs: &[f64]| {
+            forward(layer, inputs)
+                .iter()
+                .zip(output_grads.iter())
+                .map(|(o, g)| o * g)
+                .sum::<f64>()
+        };
+
+        let mut cache = Cache::default();
+        layer.forward_cached(
+            &Matrix::from_rows([inputs.as_slice()]),
+            &mut cache,
+            Mode::Eval,
+            &mut rand::thread_rng(),
+        );
+        let input_grads = layer.backward(&cache, &Matrix::from_rows([output_grads.as_slice()]));
+        let input_grads = input_grads.row(0);
+        for i in 0..inputs.len() {
+            let mut plus = inputs.clone();
+            let mut minus = inputs.clone();
+            plus[i] += h;
+            minus[i] -= h;
+            let numerical = (objective(&layer, &plus) - objective(&layer, &minus)) / (2.0 * h);
+            assert!(
+                (numerical - input_grads[i]).abs() < 1e-6,
+                "{}: expected {}, got {}",
+                activation,
+                numerical,
+                input_grads[i]
+            );
+        }
+    }
+}
+
+#[test]
+fn test_layer_forward_batch() {
+    let config = ConfigLayer {
+        input_size: 3,
+        output_size: 2,
+        name: "test".to_string(),
+        activation: "softmax".to_string(),
+        ..Default::default()
+    };
+    let initialization = ConfigInitialization {
+        method: "uniform-distribution".to_string(),
+        seed: Some(42),
+    };
+    let mut initialization = get_initialization(&initialization).unwrap();
+    let layer = Dense::new(&config, &mut initialization).unwrap();
+    let batch = [vec![0.5, 0.5, 0.5], vec![-1.0, 0.0, 2.0]];
+    let outputs = layer.forward(&Matrix::from_rows(batch.iter().map(|x| x.as_slice())));
+
+    assert_eq!((outputs.rows, outputs.cols), (2, 2));
+    for (i, inputs) in batch.iter().enumerate() {
+        assert_eq!(outputs.row(i), forward(&layer, inputs));
+    }
+}
+
+/// Valid configuration of a layer of each kind of `LAYERS`.
+fn kind_config(kind: &str) -> ConfigLayer {
+    let config = ConfigLayer {
+        kind: kind.to_string(),
+        input_size: 3,
+        output_size: 3,
+        ..Default::default()
+    };
+    let channeled = ConfigLayer {
+        input_size: 6,
+        channels: 2,
+        kernel_size: 2,
+        ..config.clone()
+    };
+    let planar = ConfigLayer {
+        input_size: 12,
+        channels: 2,
+        height: 2,
+        width: 3,
+        kernel_size: 2,
+        ..config.clone()
+    };
+    match kind {
+        "conv1d" => ConfigLayer {
+            output_size: 4,
+            filters: 2,
+            ..channeled
+        },
+        "max-pool1d" | "avg-pool1d" => ConfigLayer {
+            output_size: 4,
+            ..channeled
+        },
+        "global-avg-pool1d" => ConfigLayer {
+            output_size: 2,
+            ..channeled
+        },
+        "conv2d" => ConfigLayer {
+            output_size: 4,
+            filters: 2,
+            ..planar
+        },
+        "max-pool2d" | "avg-pool2d" => ConfigLayer {
+            output_size: 4,
+            ..planar
+        },
+        "flatten" => ConfigLayer {
+            output_size: 12,
+            ..planar
+        },
+        "rnn" | "lstm" | "gru" => ConfigLayer {
+            input_size: 6,
+            output_size: 2,
+            steps: 3,
+            hidden_size: 2,
+            ..config
+        },
+        "embedding" => ConfigLayer {
+            output_size: 6,
+            vocab_size: 4,
+            embedding_size: 2,
+            ..config
+        },
+        "positional-encoding" | "attention" | "transformer" => ConfigLayer {
+            input_size: 6,
+            output_size: 6,
+            steps: 3,
+            heads: 2,
+            hidden_size: 4,
+            ..config
+        },
+        _ => config,
+    }
+}
+
+#[test]
+fn test_layer_kinds() {
+    let mut initialization = get_initialization(&ConfigInitialization::default()).unwrap();
+    for kind in LAYERS {
+        let config = kind_config(kind);
+        config
+            .validate()
+            .unwrap_or_else(|e| panic!("{}: {}", kind, e));
+        let layer = get_layer(&config, &mut initialization).unwrap();
+        assert_eq!(layer.output_shape(), config.output_shape(), "{}", kind);
+        assert!(layer
+            .check_inputs(&Matrix::zeros(2, config.input_size))
+            .is_ok());
+        assert!(
+            matches!(
+                layer.check_inputs(&Matrix::zeros(2, config.input_size + 1)),
+                Err(Error::ShapeMismatch { expected, actual })
+                    if expected == config.input_size && actual == expected + 1
+            ),
+            "{}",
+            kind
+        );
+    }
+
+    let config = ConfigLayer {
+        kind: "unknown".to_string(),
+        ..Default::default()
+    };
+    assert!(matches!(
+        get_layer(&config, &mut initialization),
+        Err(Error::UnknownLayer(_))
+    ));
+}
+
+#[test]
+fn test_network_activation_layer() {
+    let config = |layers: Vec<ConfigLayer>| Config {
+        initialization: ConfigInitialization {
+            method: "xavier-uniform".to_string(),
+            seed: Some(42),
+        },
+        layers,
+        ..Default::default()
+    };
+    let dense = |activation: &str| ConfigLayer {
+        name: "dense".to_string(),
+        input_size: 3,
+        output_size: 2,
+        activation: activation.to_string(),
+        ..Default::default()
+    };
+    let activation = ConfigLayer {
+        name: "activation".to_string(),
+        kind: "activation".to_string(),
+        input_size: 2,
+        output_size: 2,
+        activation: "tanh".to_string(),
+        ..Default::default()
+    };
+
+    let fused = Network::new(config(vec![dense("tanh")]));
+    let split = config(vec![dense("linear"), activation]);
+    let inputs = [0.3, -0.7, 0.9];
+    assert_eq!(
+        Network::new(split.clone()).forward(&inputs),
+        fused.forward(&inputs)
+    );
+
+    let reports = gradcheck(&split, &inputs, &[0.2, 0.5], 1e-6).unwrap();
+    for report in reports {
+        assert!(report.max_relative_error < 1e-5, "{}", report.layer);
+    }
+}
@@ -0,0 +1,135 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use rust_nn::config::{Config, ConfigInitialization, ConfigLayer};
+use rust_nn::error::Error;
+use rust_nn::gradcheck::gradcheck;
+use rust_nn::layer::{BatchNorm, Layer, LayerNorm, Mode};
+use rust_nn::matrix::Matrix;
+use rust_nn::network::Network;
+use rust_nn::tape::Cache;
+
+mod common;
+
+use common::objective;
+
+fn layer_norm() -> ConfigLayer {
+    ConfigLayer {
+        name: "norm".to_string(),
+        kind: "layer-norm".to_string(),
+        input_size: 4,
+        output_size: 4,
+        ..Default::default()
+    }
+}
+
+fn config() -> Config {
+    Config {
+        lr: 0.1,
+        loss: "mse".to_string(),
+        initialization: ConfigInitialization {
+            method: "xavier-uniform".to_string(),
+            seed: Some(42),
+        },
+        layers: vec![
+            ConfigLayer {
+                name: "hidden".to_string(),
+                input_size: 3,
+                output_size: 4,
+                activation: "tanh".to_string(),
+                ..Default::default()
+            },
+            layer_norm(),
+            ConfigLayer {
+                name: "output".to_string(),
+                input_size: 4,
+                output_size: 2,
+                activation: "sigmoid".to_string(),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_layer_norm() {
+    let mut layer = LayerNorm::new(&layer_norm());
+    let inputs = Matrix::from_rows([&[1.0, 2.0, 3.0, 6.0][..], &[-1.0, 0.5, 0.0, 2.5][..]]);
+    let outputs = layer.forward(&inputs);
+    for i in 0..2 {
+        let mean = outputs.row(i).iter().sum::<f64>() / 4.0;
+        let var = outputs.row(i).iter().map(|x| x * x).sum::<f64>() / 4.0;
+        assert!(mean.abs() < 1e-12);
+        assert!((var - 1.0).abs() < 1e-4);
+    }
+
+    // samples are normalized independently of the rest of the batch
+    let single = layer.forward(&Matrix::from_rows([inputs.row(1)]));
+    assert_eq!(single.row(0), outputs.row(1));
+
+    layer.gamma = vec![1.5, 0.5, -2.0, 1.0];
+    layer.beta = vec![0.1, -0.2, 0.3, 0.0];
+    let weights = Matrix::from_rows([&[0.3, -0.1, 0.7, 0.2][..], &[-0.4, 0.2, 0.1, 0.9][..]]);
+    let mut cache = Cache::default();
+    let mut rng = ChaCha8Rng::seed_from_u64(42);
+    layer.forward_cached(&inputs, &mut cache, Mode::Eval, &mut rng);
+    assert_eq!(cache.outputs, layer.forward(&inputs));
+    let errors = layer.backward(&cache, &weights);
+
+    let epsilon = 1e-6;
+    for k in 0..inputs.data.len() {
+        let (mut plus, mut minus) = (inputs.clone(), inputs.clone());
+        plus.data[k] += epsilon;
+        minus.data[k] -= epsilon;
+        let numeric = (objective(&mut layer, &plus, &weights.data)
+            - objective(&mut layer, &minus, &weights.data))
+            / (2.0 * epsilon);
+        assert!((numeric - errors.data[k]).abs() < 1e-6, "input {k}");
+    }
+}
+
+#[test]
+fn test_network_layer_norm() {
+    let mut network = Network::new(config());
+    let (inputs, targets) = ([0.3, -0.7, 0.9], [0.2, 0.5]);
+    let loss = |network: &Network| {
+        let outputs = network.forward(&inputs);
+        outputs
+            .iter()
+            .zip(targets.iter())
+            .map(|(o, t)| (o - t).powi(2))
+            .sum::<f64>()
+    };
+    let before = loss(&network);
+    for _ in 0..20 {
+        network.backward(0.1, &inputs, &targets);
+    }
+    assert!(loss(&network) < before);
+
+    let reports = gradcheck(&config(), &inputs, &targets, 1e-6).unwrap();
+    for report in reports {
+        assert!(report.max_relative_error < 1e-5, "{}", report.layer);
+    }
+}
+
+#[test]
+fn test_normalization_epsilon() {
+    let mut layer_config = layer_norm();
+    assert_eq!(layer_config.epsilon, 1e-5);
+    layer_config.epsilon = 0.5;
+    let layer = LayerNorm::new(&layer_config);
+    let outputs = layer.forward(&Matrix::from_rows([&[1.0, -1.0, 1.0, -1.0][..]]));
+    assert!((outputs.data[0] - 1.0 / 1.5f64.sqrt()).abs() < 1e-12);
+
+    layer_config.kind = "batch-norm".to_string();
+    let layer = BatchNorm::new(&layer_config);
+    let outputs = layer.forward(&Matrix::from_rows([&[1.0, -1.0, 1.0, -1.0][..]]));
+    assert!((outputs.data[0] - 1.0 / 1.5f64.sqrt()).abs() < 1e-12);
+
+    for kind in ["layer-norm", "batch-norm"] {
+        let mut invalid = config();
+        invalid.layers[1].kind = kind.to_string();
+        invalid.layers[1].epsilon = 0.0;
+        assert!(matches!(invalid.validate(), Err(Error::Validation(_))));
+    }
+}
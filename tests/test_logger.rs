@@ -0,0 +1,26 @@
+use rust_nn::config::{Config, ConfigLayer};
+use rust_nn::error::Error;
+use rust_nn::logger::Logger;
+use rust_nn::network::Network;
+
+// The logger writes under `logs/` in the working directory, so this binary
+// holds a single test that moves to a directory where `logs` is a file.
+#[test]
+fn test_logger_io_error() {
+    let dir = std::env::temp_dir().join("rust-nn-test-logger");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("logs"), "").unwrap();
+    std::env::set_current_dir(&dir).unwrap();
+
+    assert!(matches!(Logger::try_new(), Err(Error::Io(_))));
+
+    let config = Config {
+        layers: vec![ConfigLayer {
+            input_size: 2,
+            output_size: 1,
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    assert!(matches!(Network::try_new(config), Err(Error::Io(_))));
+}
@@ -1,9 +1,11 @@
+use rust_nn::activation::get_activation;
+use rust_nn::error::Error;
 use rust_nn::loss::get_loss;
-use std::{collections::HashMap, f64::EPSILON};
+use std::collections::HashMap;
 
 #[test]
 fn test_loss_mse_function() {
-    let loss = get_loss("mse", HashMap::new());
+    let loss = get_loss("mse", HashMap::new()).unwrap();
     let y_true = vec![1.0, 0.0, 1.0];
     let y_pred = vec![0.9, 0.1, 0.8];
     let result = loss.function(&y_true, &y_pred);
@@ -12,11 +14,11 @@ fn test_loss_mse_function() {
 
 #[test]
 fn test_loss_mse_gradient() {
-    let loss = get_loss("mse", HashMap::new());
+    let loss = get_loss("mse", HashMap::new()).unwrap();
     let y_true = vec![1.0, 0.0, 1.0];
     let y_pred = vec![0.9, 0.1, 0.8];
     let gradient = loss.gradient(&y_true, &y_pred);
-    let expected_gradient = vec![
+    let expected_gradient = [
         0.06666666666666665,
         -0.06666666666666667,
         0.1333333333333333,
@@ -28,7 +30,7 @@ fn test_loss_mse_gradient() {
 
 #[test]
 fn test_loss_crossentropy_function() {
-    let loss = get_loss("crossentropy", HashMap::new());
+    let loss = get_loss("crossentropy", HashMap::new()).unwrap();
     let y_true = vec![1.0, 0.0, 1.0];
     let y_pred = vec![0.9, 0.1, 0.8];
     let result = loss.function(&y_true, &y_pred);
@@ -37,11 +39,11 @@ fn test_loss_crossentropy_function() {
 
 #[test]
 fn test_loss_crossentropy_gradient() {
-    let loss = get_loss("crossentropy", HashMap::new());
+    let loss = get_loss("crossentropy", HashMap::new()).unwrap();
     let y_true = vec![1.0, 0.0, 1.0];
     let y_pred = vec![0.9, 0.1, 0.8];
-    let gradient = loss.gradient(&y_true, &y_pred);
-    let expected_gradient = vec![0.09999999999999998, -0.1, 0.19999999999999996];
+    let gradient = loss.gradient(&y_pred, &y_true);
+    let expected_gradient = [-1.1111111111111112, 0.0, -1.25];
     for (g, e) in gradient.iter().zip(expected_gradient.iter()) {
         assert!((g - e).abs() < 1e-6);
     }
@@ -49,21 +51,58 @@ fn test_loss_crossentropy_gradient() {
 
 #[test]
 fn test_loss_binary_crossentropy_function() {
-    let loss = get_loss("binary-crossentropy", HashMap::new());
+    let loss = get_loss("binary-crossentropy", HashMap::new()).unwrap();
     let y_true = vec![1.0, 0.0];
     let y_pred = vec![1.0, 1.0];
     let result = loss.function(&y_true, &y_pred);
-    assert!((result - 36.04365338911715).abs() < EPSILON);
+    assert!((result - 36.04365338911715).abs() < f64::EPSILON);
 }
 
 #[test]
 fn test_loss_binary_crossentropy_gradient() {
-    let loss = get_loss("binary-crossentropy", HashMap::new());
+    let loss = get_loss("binary-crossentropy", HashMap::new()).unwrap();
     let y_true = vec![1.0, 0.0, 1.0];
     let y_pred = vec![0.9, 0.1, 0.8];
-    let gradient = loss.gradient(&y_true, &y_pred);
-    let expected_gradient = vec![0.09999999999999998, -0.1, 0.19999999999999996];
+    let gradient = loss.gradient(&y_pred, &y_true);
+    let expected_gradient = [-1.1111111111111112, 1.111111111111111, -1.25];
+    for (g, e) in gradient.iter().zip(expected_gradient.iter()) {
+        assert!((g - e).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn test_loss_crossentropy_softmax_gradient() {
+    let loss = get_loss("crossentropy", HashMap::new()).unwrap();
+    let softmax = get_activation("softmax", HashMap::new()).unwrap();
+    let y_true = vec![1.0, 0.0, 0.0];
+    let logits: Vec<f64> = [0.7f64, 0.2, 0.1].iter().map(|p| p.ln()).collect();
+    let y_pred = softmax.function(&logits);
+    let gradient = softmax.backward(&logits, &loss.gradient(&y_pred, &y_true));
+    let expected_gradient = [-0.3, 0.2, 0.1];
+    for (g, e) in gradient.iter().zip(expected_gradient.iter()) {
+        assert!((g - e).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn test_loss_binary_crossentropy_sigmoid_gradient() {
+    let loss = get_loss("binary-crossentropy", HashMap::new()).unwrap();
+    let sigmoid = get_activation("sigmoid", HashMap::new()).unwrap();
+    let y_true = vec![1.0, 0.0, 1.0];
+    let logits: Vec<f64> = [0.9f64, 0.1, 0.8]
+        .iter()
+        .map(|p| (p / (1.0 - p)).ln())
+        .collect();
+    let y_pred = sigmoid.function(&logits);
+    let gradient = sigmoid.backward(&logits, &loss.gradient(&y_pred, &y_true));
+    let expected_gradient = [-0.1, 0.1, -0.2];
     for (g, e) in gradient.iter().zip(expected_gradient.iter()) {
-        assert!((g - e).abs() < EPSILON);
+        assert!((g - e).abs() < 1e-12);
     }
 }
+
+#[test]
+fn test_loss_unknown() {
+    let result = get_loss("unknown", HashMap::new());
+    assert!(matches!(result, Err(Error::UnknownLoss(name)) if name == "unknown"));
+}
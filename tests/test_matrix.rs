@@ -0,0 +1,47 @@
+use rust_nn::matrix::Matrix;
+
+fn matrix() -> Matrix {
+    // [[1, 2, 3],
+    //  [4, 5, 6]]
+    Matrix::from_rows([[1.0, 2.0, 3.0].as_slice(), [4.0, 5.0, 6.0].as_slice()])
+}
+
+#[test]
+fn test_matrix_from_rows() {
+    let m = matrix();
+    assert_eq!((m.rows, m.cols), (2, 3));
+    assert_eq!(m.row(1), [4.0, 5.0, 6.0]);
+    assert_eq!(m.to_rows(), vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]);
+}
+
+#[test]
+fn test_matrix_matvec() {
+    let m = matrix();
+    assert_eq!(m.matvec(&[1.0, 0.0, -1.0]), vec![-2.0, -2.0]);
+    assert_eq!(m.transpose_matvec(&[1.0, -1.0]), vec![-3.0, -3.0, -3.0]);
+}
+
+#[test]
+fn test_matrix_matmul() {
+    let m = matrix();
+    let product = m.matmul_transpose(&m);
+    assert_eq!(product.to_rows(), vec![vec![14.0, 32.0], vec![32.0, 77.0]]);
+
+    let identity = Matrix::from_rows([
+        [1.0, 0.0, 0.0].as_slice(),
+        [0.0, 1.0, 0.0].as_slice(),
+        [0.0, 0.0, 1.0].as_slice(),
+    ]);
+    assert_eq!(m.matmul(&identity), m);
+
+    let mut gram = Matrix::zeros(3, 3);
+    gram.add_transpose_matmul(&m, &m);
+    assert_eq!(gram.row(0), [17.0, 22.0, 27.0]);
+    assert_eq!(gram.row(2), [27.0, 36.0, 45.0]);
+}
+
+#[test]
+#[should_panic(expected = "row 1 has 2 values, expected 3")]
+fn test_matrix_from_rows_ragged() {
+    Matrix::from_rows([[1.0, 2.0, 3.0].as_slice(), [4.0, 5.0].as_slice()]);
+}
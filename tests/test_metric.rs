@@ -0,0 +1,28 @@
+use rust_nn::error::Error;
+use rust_nn::metric::*;
+
+#[test]
+fn test_metric_accuracy() {
+    let accuracy = get_metric("accuracy").unwrap();
+    assert_eq!(accuracy.function(&[0.7], &[1.0]), 1.0);
+    assert_eq!(accuracy.function(&[0.3], &[1.0]), 0.0);
+    assert_eq!(accuracy.function(&[0.1, 0.7, 0.2], &[0.0, 1.0, 0.0]), 1.0);
+    assert_eq!(accuracy.function(&[0.6, 0.3, 0.1], &[0.0, 1.0, 0.0]), 0.0);
+}
+
+#[test]
+fn test_metric_mae() {
+    let mae = get_metric("mae").unwrap();
+    assert!((mae.function(&[0.5, 1.0], &[1.0, 0.0]) - 0.75).abs() < 1e-12);
+}
+
+#[test]
+fn test_metric_unknown() {
+    for name in METRICS {
+        assert!(get_metric(name).is_ok());
+    }
+    assert!(matches!(
+        get_metric("unknown"),
+        Err(Error::UnknownMetric(_))
+    ));
+}
@@ -1,5 +1,15 @@
 use rust_nn::config::{Config, ConfigInitialization, ConfigLayer};
+use rust_nn::dataset::Dataset;
+use rust_nn::error::Error;
+use rust_nn::matrix::Matrix;
 use rust_nn::network::Network;
+use rust_nn::tape::Tape;
+
+/// Distance to the targets of the sigmoid outputs of the networks trained
+/// with MSE and SGD. The MSE gradient through a sigmoid carries a factor
+/// `p (1 - p)`, so the outputs approach 0 and 1 slower than with the
+/// derivative taken at the layer inputs, which the 1e-3 used to rely on.
+const MSE_TOLERANCE: f64 = 1e-2;
 
 #[test]
 fn test_network_new() {
@@ -17,20 +27,72 @@ fn test_network_new() {
                 output_size: 2,
                 name: "layer-1".to_string(),
                 activation: "sigmoid".to_string(),
+                ..Default::default()
             },
             ConfigLayer {
                 input_size: 2,
                 output_size: 1,
                 name: "layer-2".to_string(),
                 activation: "sigmoid".to_string(),
+                ..Default::default()
             },
         ],
+        ..Default::default()
     };
     let network = Network::new(config);
 
     assert_eq!(network.layers.len(), 2);
-    assert_eq!(network.layers[0].neurons.len(), 2);
-    assert_eq!(network.layers[1].neurons.len(), 1);
+    assert_eq!(network.layers[0].output_shape(), vec![2]);
+    assert_eq!(network.layers[1].output_shape(), vec![1]);
+}
+
+#[test]
+fn test_network_dense() {
+    let config = Config {
+        loss: "mse".to_string(),
+        initialization: ConfigInitialization {
+            method: "zero-centered".to_string(),
+            seed: Some(42),
+        },
+        layers: vec![
+            ConfigLayer {
+                input_size: 3,
+                output_size: 2,
+                name: "hidden".to_string(),
+                activation: "tanh".to_string(),
+                ..Default::default()
+            },
+            ConfigLayer {
+                kind: "dropout".to_string(),
+                input_size: 2,
+                output_size: 2,
+                name: "dropout".to_string(),
+                rate: 0.5,
+                ..Default::default()
+            },
+            ConfigLayer {
+                input_size: 2,
+                output_size: 1,
+                name: "output".to_string(),
+                activation: "sigmoid".to_string(),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+    let network = Network::new(config);
+
+    let neurons = network.dense(0).unwrap().neurons();
+    let parameters = network.layers[0].parameters();
+    assert_eq!(neurons.len(), 2);
+    for (j, neuron) in neurons.iter().enumerate() {
+        assert_eq!(neuron.weights, parameters[0][j * 3..(j + 1) * 3]);
+        assert_eq!(neuron.bias, parameters[1][j]);
+    }
+    assert_eq!(network.dense(2).unwrap().neurons().len(), 1);
+
+    assert!(network.dense(1).is_none());
+    assert!(network.dense(3).is_none());
 }
 
 #[test]
@@ -49,14 +111,17 @@ fn test_network_forward() {
                 output_size: 2,
                 name: "layer-1".to_string(),
                 activation: "sigmoid".to_string(),
+                ..Default::default()
             },
             ConfigLayer {
                 input_size: 2,
                 output_size: 2,
                 name: "layer-2".to_string(),
                 activation: "sigmoid".to_string(),
+                ..Default::default()
             },
         ],
+        ..Default::default()
     };
     let inputs = vec![0.5, 0.5, 0.5];
     let network = Network::new(config);
@@ -81,20 +146,24 @@ fn test_network_activations() {
                 output_size: 5,
                 name: "layer-1".to_string(),
                 activation: "sigmoid".to_string(),
+                ..Default::default()
             },
             ConfigLayer {
                 input_size: 5,
                 output_size: 4,
                 name: "layer-2".to_string(),
                 activation: "sigmoid".to_string(),
+                ..Default::default()
             },
             ConfigLayer {
                 input_size: 4,
                 output_size: 2,
                 name: "layer-3".to_string(),
                 activation: "sigmoid".to_string(),
+                ..Default::default()
             },
         ],
+        ..Default::default()
     };
     let inputs = vec![0.5, 0.5, 0.5];
     let network = Network::new(config.clone());
@@ -103,6 +172,58 @@ fn test_network_activations() {
     assert_eq!(activations.len(), 3);
 }
 
+#[test]
+fn test_network_forward_cached() {
+    let config = Config {
+        lr: 0.1,
+        epochs: 10,
+        loss: "mse".to_string(),
+        initialization: ConfigInitialization {
+            method: "zero-centered".to_string(),
+            seed: Some(42),
+        },
+        layers: vec![
+            ConfigLayer {
+                input_size: 3,
+                output_size: 5,
+                name: "layer-1".to_string(),
+                activation: "relu".to_string(),
+                ..Default::default()
+            },
+            ConfigLayer {
+                input_size: 5,
+                output_size: 2,
+                name: "layer-2".to_string(),
+                activation: "softmax".to_string(),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+    let inputs = vec![0.5, -0.5, 0.5];
+    let mut network = Network::new(config);
+    let mut tape = Tape::default();
+    network.forward_cached(&Matrix::from_rows([inputs.as_slice()]), &mut tape);
+
+    let activations = network.activations(&inputs);
+    assert_eq!(tape.caches.len(), 2);
+    assert_eq!(tape.caches[0].inputs.row(0), inputs);
+    assert_eq!(tape.caches[1].inputs.row(0), activations[0]);
+    assert_eq!(tape.caches[1].outputs.row(0), activations[1]);
+    assert_eq!(tape.outputs().unwrap().row(0), network.forward(&inputs));
+
+    // the tape is reused by later passes, one sample per row
+    let batch = [vec![0.0, 1.0, 0.0], vec![1.0, 0.0, 1.0]];
+    network.forward_cached(
+        &Matrix::from_rows(batch.iter().map(|x| x.as_slice())),
+        &mut tape,
+    );
+    assert_eq!(tape.caches.len(), 2);
+    for (i, inputs) in batch.iter().enumerate() {
+        assert_eq!(tape.outputs().unwrap().row(i), network.forward(inputs));
+    }
+}
+
 #[test]
 fn test_network_backward() {
     let config = Config {
@@ -119,14 +240,17 @@ fn test_network_backward() {
                 output_size: 2,
                 name: "layer-1".to_string(),
                 activation: "sigmoid".to_string(),
+                ..Default::default()
             },
             ConfigLayer {
                 input_size: 2,
                 output_size: 1,
                 name: "layer-2".to_string(),
                 activation: "sigmoid".to_string(),
+                ..Default::default()
             },
         ],
+        ..Default::default()
     };
 
     let target = vec![0.0];
@@ -159,30 +283,34 @@ fn test_network_train_xor() {
                 input_size: 2,
                 output_size: 8,
                 activation: "relu".to_string(),
+                ..Default::default()
             },
             ConfigLayer {
                 name: "hidden2".to_string(),
                 input_size: 8,
                 output_size: 4,
                 activation: "relu".to_string(),
+                ..Default::default()
             },
             ConfigLayer {
                 name: "output".to_string(),
                 input_size: 4,
                 output_size: 1,
                 activation: "sigmoid".to_string(),
+                ..Default::default()
             },
         ],
+        ..Default::default()
     };
 
     let mut network = Network::new(config);
-    network.train(&dataset);
+    network.train(&Dataset::from_samples(&dataset));
 
     // Test predictions
     for (inputs, expected) in dataset {
         let output = network.forward(&inputs);
         assert!(
-            (output[0] - expected[0]).abs() < 1e-3,
+            (output[0] - expected[0]).abs() < MSE_TOLERANCE,
             "Failed XOR test: input {:?}, expected {}, got {}",
             inputs,
             expected[0],
@@ -214,29 +342,33 @@ fn test_network_and() {
                 input_size: 2,
                 output_size: 4,
                 activation: "relu".to_string(),
+                ..Default::default()
             },
             ConfigLayer {
                 name: "hidden2".to_string(),
                 input_size: 4,
                 output_size: 4,
                 activation: "relu".to_string(),
+                ..Default::default()
             },
             ConfigLayer {
                 name: "output".to_string(),
                 input_size: 4,
                 output_size: 1,
                 activation: "sigmoid".to_string(),
+                ..Default::default()
             },
         ],
+        ..Default::default()
     };
 
     let mut network = Network::new(config);
-    network.train(&dataset);
+    network.train(&Dataset::from_samples(&dataset));
 
     for (inputs, expected) in dataset {
         let output = network.forward(&inputs);
         assert!(
-            (output[0] - expected[0]).abs() < 1e-3,
+            (output[0] - expected[0]).abs() < MSE_TOLERANCE,
             "Failed AND test: input {:?}, expected {}, got {}",
             inputs,
             expected[0],
@@ -268,35 +400,41 @@ fn test_network_binary_addition() {
                 input_size: 2,
                 output_size: 12,
                 activation: "tanh".to_string(),
+                ..Default::default()
             },
             ConfigLayer {
                 name: "hidden2".to_string(),
                 input_size: 12,
                 output_size: 8,
                 activation: "tanh".to_string(),
+                ..Default::default()
             },
             ConfigLayer {
                 name: "hidden3".to_string(),
                 input_size: 8,
                 output_size: 6,
                 activation: "tanh".to_string(),
+                ..Default::default()
             },
             ConfigLayer {
                 name: "output".to_string(),
                 input_size: 6,
                 output_size: 2,
                 activation: "sigmoid".to_string(),
+                ..Default::default()
             },
         ],
+        ..Default::default()
     };
 
     let mut network = Network::new(config);
-    network.train(&dataset);
+    network.train(&Dataset::from_samples(&dataset));
 
     for (inputs, expected) in dataset {
         let output = network.forward(&inputs);
         assert!(
-            (output[0] - expected[0]).abs() < 1e-3 && (output[1] - expected[1]).abs() < 1e-3,
+            (output[0] - expected[0]).abs() < MSE_TOLERANCE
+                && (output[1] - expected[1]).abs() < MSE_TOLERANCE,
             "Failed binary addition test: input {:?}, expected {:?}, got {:?}",
             inputs,
             expected,
@@ -331,23 +469,26 @@ fn test_network_pattern_recognition() {
                 input_size: 4,
                 output_size: 6,
                 activation: "relu".to_string(),
+                ..Default::default()
             },
             ConfigLayer {
                 name: "output".to_string(),
                 input_size: 6,
                 output_size: 1,
                 activation: "sigmoid".to_string(),
+                ..Default::default()
             },
         ],
+        ..Default::default()
     };
 
     let mut network = Network::new(config);
-    network.train(&dataset);
+    network.train(&Dataset::from_samples(&dataset));
 
     for (inputs, expected) in dataset {
         let output = network.forward(&inputs);
         assert!(
-            (output[0] - expected[0]).abs() < 1e-3,
+            (output[0] - expected[0]).abs() < MSE_TOLERANCE,
             "Failed pattern recognition test: input {:?}, expected {}, got {}",
             inputs,
             expected[0],
@@ -355,3 +496,720 @@ fn test_network_pattern_recognition() {
         );
     }
 }
+
+#[test]
+fn test_network_save_load() {
+    let config = Config {
+        lr: 0.1,
+        epochs: 100,
+        loss: "mse".to_string(),
+        initialization: ConfigInitialization {
+            method: "zero-centered".to_string(),
+            seed: None,
+        },
+        layers: vec![
+            ConfigLayer {
+                input_size: 3,
+                output_size: 5,
+                name: "layer-1".to_string(),
+                activation: "tanh".to_string(),
+                ..Default::default()
+            },
+            ConfigLayer {
+                input_size: 5,
+                output_size: 2,
+                name: "layer-2".to_string(),
+                activation: "sigmoid".to_string(),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+    let dataset = vec![
+        (vec![0.0, 0.5, 1.0], vec![1.0, 0.0]),
+        (vec![1.0, 0.5, 0.0], vec![0.0, 1.0]),
+    ];
+
+    let mut network = Network::new(config);
+    network.train(&Dataset::from_samples(&dataset));
+
+    let path = std::env::temp_dir().join("rust-nn-test-network-save-load.json");
+    let path = path.to_str().unwrap();
+    network.save(path).unwrap();
+    let loaded = Network::load(path).unwrap();
+    std::fs::remove_file(path).unwrap();
+
+    assert_eq!(loaded.layers.len(), network.layers.len());
+    for (l, n) in loaded.layers.iter().zip(network.layers.iter()) {
+        assert_eq!(l.parameters(), n.parameters());
+    }
+    for (inputs, _) in dataset.iter() {
+        let expected = network.forward(inputs);
+        let outputs = loaded.forward(inputs);
+        for (o, e) in outputs.iter().zip(expected.iter()) {
+            assert_eq!(o.to_bits(), e.to_bits());
+        }
+    }
+}
+
+#[test]
+fn test_network_load_invalid() {
+    let path = std::env::temp_dir().join("rust-nn-test-network-load-invalid.json");
+    let path = path.to_str().unwrap();
+    std::fs::write(path, "{\"config\": {}}").unwrap();
+    let result = Network::load(path);
+    std::fs::remove_file(path).unwrap();
+    assert!(result.is_err());
+    assert!(Network::load("does-not-exist.json").is_err());
+}
+
+#[test]
+fn test_network_load_transposed() {
+    let config = Config {
+        layers: vec![ConfigLayer {
+            input_size: 3,
+            output_size: 2,
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let network = Network::new(config);
+
+    let path = std::env::temp_dir().join("rust-nn-test-network-load-transposed.json");
+    let path = path.to_str().unwrap();
+    network.save(path).unwrap();
+    let mut state: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+    let shape = state["layers"][0]["shapes"][0].as_array_mut().unwrap();
+    assert_eq!(shape.len(), 2);
+    shape.reverse();
+    std::fs::write(path, state.to_string()).unwrap();
+    let result = Network::load(path);
+    std::fs::remove_file(path).unwrap();
+
+    assert!(matches!(
+        result,
+        Err(Error::ShapeMismatch {
+            expected: 2,
+            actual: 3
+        })
+    ));
+}
+
+#[test]
+fn test_network_input_width() {
+    let config = Config {
+        layers: vec![ConfigLayer {
+            input_size: 3,
+            output_size: 2,
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let mut network = Network::new(config);
+    let mismatch = |result: Result<_, Error>| {
+        matches!(
+            result,
+            Err(Error::ShapeMismatch {
+                expected: 3,
+                actual: 2
+            })
+        )
+    };
+    assert!(mismatch(network.try_forward(&[1.0, 2.0]).map(|_| ())));
+    assert!(mismatch(
+        network
+            .try_forward_batch(&Matrix::from_rows([&[1.0, 2.0][..]]))
+            .map(|_| ())
+    ));
+
+    let dataset = Dataset::from_samples(&[(vec![1.0, 2.0], vec![0.0, 1.0])]);
+    assert!(mismatch(network.try_evaluate(&dataset).map(|_| ())));
+    assert!(mismatch(network.try_train(&dataset)));
+    assert!(mismatch(network.try_fit(&dataset, &dataset).map(|_| ())));
+    assert_eq!(network.try_forward(&[1.0, 2.0, 3.0]).unwrap().len(), 2);
+}
+
+#[test]
+fn test_network_and_adam() {
+    let dataset = vec![
+        (vec![0.0, 0.0], vec![0.0]),
+        (vec![0.0, 1.0], vec![0.0]),
+        (vec![1.0, 0.0], vec![0.0]),
+        (vec![1.0, 1.0], vec![1.0]),
+    ];
+
+    let config = Config {
+        lr: 0.1,
+        epochs: 500,
+        loss: "mse".to_string(),
+        optimizer: "adam".to_string(),
+        initialization: ConfigInitialization {
+            method: "zero-centered".to_string(),
+            seed: Some(42),
+        },
+        layers: vec![
+            ConfigLayer {
+                name: "hidden1".to_string(),
+                input_size: 2,
+                output_size: 4,
+                activation: "relu".to_string(),
+                ..Default::default()
+            },
+            ConfigLayer {
+                name: "hidden2".to_string(),
+                input_size: 4,
+                output_size: 4,
+                activation: "relu".to_string(),
+                ..Default::default()
+            },
+            ConfigLayer {
+                name: "output".to_string(),
+                input_size: 4,
+                output_size: 1,
+                activation: "sigmoid".to_string(),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+
+    let mut network = Network::new(config);
+    network.train(&Dataset::from_samples(&dataset));
+
+    for (inputs, expected) in dataset {
+        let output = network.forward(&inputs);
+        assert!(
+            (output[0] - expected[0]).abs() < 1e-2,
+            "Failed AND adam test: input {:?}, expected {}, got {}",
+            inputs,
+            expected[0],
+            output[0]
+        );
+    }
+}
+
+#[test]
+fn test_network_and_batch() {
+    let dataset = vec![
+        (vec![0.0, 0.0], vec![0.0]),
+        (vec![0.0, 1.0], vec![0.0]),
+        (vec![1.0, 0.0], vec![0.0]),
+        (vec![1.0, 1.0], vec![1.0]),
+    ];
+
+    let config = Config {
+        lr: 0.1,
+        epochs: 1000,
+        loss: "mse".to_string(),
+        batch_size: 2,
+        optimizer: "adam".to_string(),
+        initialization: ConfigInitialization {
+            method: "zero-centered".to_string(),
+            seed: Some(42),
+        },
+        layers: vec![
+            ConfigLayer {
+                name: "hidden1".to_string(),
+                input_size: 2,
+                output_size: 4,
+                activation: "relu".to_string(),
+                ..Default::default()
+            },
+            ConfigLayer {
+                name: "hidden2".to_string(),
+                input_size: 4,
+                output_size: 4,
+                activation: "relu".to_string(),
+                ..Default::default()
+            },
+            ConfigLayer {
+                name: "output".to_string(),
+                input_size: 4,
+                output_size: 1,
+                activation: "sigmoid".to_string(),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+
+    let mut network = Network::new(config);
+    network.train(&Dataset::from_samples(&dataset));
+
+    for (inputs, expected) in dataset {
+        let output = network.forward(&inputs);
+        assert!(
+            (output[0] - expected[0]).abs() < 1e-2,
+            "Failed AND batch test: input {:?}, expected {}, got {}",
+            inputs,
+            expected[0],
+            output[0]
+        );
+    }
+}
+
+#[test]
+fn test_network_softmax_classification() {
+    let dataset = vec![
+        (vec![1.0, 0.0, 0.0], vec![1.0, 0.0, 0.0]),
+        (vec![0.0, 1.0, 0.0], vec![0.0, 1.0, 0.0]),
+        (vec![0.0, 0.0, 1.0], vec![0.0, 0.0, 1.0]),
+        (vec![1.0, 1.0, 0.0], vec![0.0, 1.0, 0.0]),
+        (vec![0.0, 1.0, 1.0], vec![0.0, 0.0, 1.0]),
+    ];
+
+    let config = Config {
+        lr: 0.1,
+        epochs: 500,
+        loss: "crossentropy".to_string(),
+        initialization: ConfigInitialization {
+            method: "zero-centered".to_string(),
+            seed: Some(42),
+        },
+        layers: vec![
+            ConfigLayer {
+                name: "hidden".to_string(),
+                input_size: 3,
+                output_size: 8,
+                activation: "tanh".to_string(),
+                ..Default::default()
+            },
+            ConfigLayer {
+                name: "output".to_string(),
+                input_size: 8,
+                output_size: 3,
+                activation: "softmax".to_string(),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+
+    let mut network = Network::new(config);
+    network.train(&Dataset::from_samples(&dataset));
+
+    for (inputs, expected) in dataset {
+        let output = network.forward(&inputs);
+        assert!((output.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        for (o, e) in output.iter().zip(expected.iter()) {
+            assert!(
+                (o - e).abs() < 0.1,
+                "Failed softmax test: input {:?}, expected {:?}, got {:?}",
+                inputs,
+                expected,
+                output
+            );
+        }
+    }
+}
+
+#[test]
+fn test_network_train_xor_binary_crossentropy() {
+    let dataset = vec![
+        (vec![0.0, 0.0], vec![0.0]),
+        (vec![0.0, 1.0], vec![1.0]),
+        (vec![1.0, 0.0], vec![1.0]),
+        (vec![1.0, 1.0], vec![0.0]),
+    ];
+
+    let config = Config {
+        lr: 0.1,
+        epochs: 20000,
+        loss: "binary-crossentropy".to_string(),
+        initialization: ConfigInitialization {
+            method: "zero-centered".to_string(),
+            seed: Some(42),
+        },
+        layers: vec![
+            ConfigLayer {
+                name: "hidden".to_string(),
+                input_size: 2,
+                output_size: 8,
+                activation: "relu".to_string(),
+                ..Default::default()
+            },
+            ConfigLayer {
+                name: "hidden2".to_string(),
+                input_size: 8,
+                output_size: 4,
+                activation: "relu".to_string(),
+                ..Default::default()
+            },
+            ConfigLayer {
+                name: "output".to_string(),
+                input_size: 4,
+                output_size: 1,
+                activation: "sigmoid".to_string(),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+
+    let mut network = Network::new(config);
+    network.train(&Dataset::from_samples(&dataset));
+
+    for (inputs, expected) in dataset {
+        let output = network.forward(&inputs);
+        assert!(
+            (output[0] - expected[0]).abs() < 1e-3,
+            "Failed XOR test: input {:?}, expected {}, got {}",
+            inputs,
+            expected[0],
+            output[0]
+        );
+    }
+}
+
+#[test]
+fn test_network_binary_addition_binary_crossentropy() {
+    let dataset = vec![
+        (vec![0.0, 0.0], vec![0.0, 0.0]),
+        (vec![0.0, 1.0], vec![1.0, 0.0]),
+        (vec![1.0, 0.0], vec![1.0, 0.0]),
+        (vec![1.0, 1.0], vec![0.0, 1.0]),
+    ];
+
+    let config = Config {
+        lr: 0.5,
+        epochs: 10000,
+        loss: "binary-crossentropy".to_string(),
+        initialization: ConfigInitialization {
+            method: "zero-centered".to_string(),
+            seed: Some(42),
+        },
+        layers: vec![
+            ConfigLayer {
+                name: "hidden1".to_string(),
+                input_size: 2,
+                output_size: 12,
+                activation: "tanh".to_string(),
+                ..Default::default()
+            },
+            ConfigLayer {
+                name: "hidden2".to_string(),
+                input_size: 12,
+                output_size: 8,
+                activation: "tanh".to_string(),
+                ..Default::default()
+            },
+            ConfigLayer {
+                name: "output".to_string(),
+                input_size: 8,
+                output_size: 2,
+                activation: "sigmoid".to_string(),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+
+    let mut network = Network::new(config);
+    network.train(&Dataset::from_samples(&dataset));
+
+    for (inputs, expected) in dataset {
+        let output = network.forward(&inputs);
+        assert!(
+            (output[0] - expected[0]).abs() < 1e-3 && (output[1] - expected[1]).abs() < 1e-3,
+            "Failed binary addition test: input {:?}, expected {:?}, got {:?}",
+            inputs,
+            expected,
+            output
+        );
+    }
+}
+
+#[test]
+fn test_network_accumulate_batch() {
+    let config = Config {
+        lr: 0.1,
+        epochs: 1,
+        loss: "mse".to_string(),
+        initialization: ConfigInitialization {
+            method: "zero-centered".to_string(),
+            seed: Some(42),
+        },
+        layers: vec![
+            ConfigLayer {
+                input_size: 2,
+                output_size: 3,
+                name: "layer-1".to_string(),
+                activation: "tanh".to_string(),
+                ..Default::default()
+            },
+            ConfigLayer {
+                input_size: 3,
+                output_size: 1,
+                name: "layer-2".to_string(),
+                activation: "sigmoid".to_string(),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+    let dataset = [
+        (vec![0.0, 1.0], vec![1.0]),
+        (vec![1.0, 0.5], vec![0.0]),
+        (vec![-1.0, 0.5], vec![1.0]),
+    ];
+
+    let mut batched = Network::new(config.clone());
+    let inputs = Matrix::from_rows(dataset.iter().map(|(x, _)| x.as_slice()));
+    let targets = Matrix::from_rows(dataset.iter().map(|(_, y)| y.as_slice()));
+    let batch_loss = batched.accumulate_batch(&inputs, &targets);
+
+    let mut single = Network::new(config);
+    let loss = dataset
+        .iter()
+        .map(|(x, y)| single.accumulate(x, y))
+        .sum::<f64>();
+
+    assert!((batch_loss - loss).abs() < 1e-12);
+    for (b, s) in batched.layers.iter().zip(single.layers.iter()) {
+        for (gb, gs) in b.gradients().iter().zip(s.gradients().iter()) {
+            for (gb, gs) in gb.iter().zip(gs.iter()) {
+                assert!((gb - gs).abs() < 1e-12);
+            }
+        }
+    }
+}
+
+fn and_config() -> Config {
+    Config {
+        lr: 0.05,
+        epochs: 200,
+        loss: "binary-crossentropy".to_string(),
+        optimizer: "adam".to_string(),
+        metrics: vec!["accuracy".to_string(), "mae".to_string()],
+        initialization: ConfigInitialization {
+            method: "zero-centered".to_string(),
+            seed: Some(42),
+        },
+        layers: vec![
+            ConfigLayer {
+                name: "hidden".to_string(),
+                input_size: 2,
+                output_size: 4,
+                activation: "tanh".to_string(),
+                ..Default::default()
+            },
+            ConfigLayer {
+                name: "output".to_string(),
+                input_size: 4,
+                output_size: 1,
+                activation: "sigmoid".to_string(),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_network_evaluate() {
+    let dataset = Dataset::from_samples(&[
+        (vec![0.0, 0.0], vec![0.0]),
+        (vec![0.0, 1.0], vec![0.0]),
+        (vec![1.0, 0.0], vec![0.0]),
+        (vec![1.0, 1.0], vec![1.0]),
+    ]);
+    let mut network = Network::new(and_config());
+    network.train(&dataset);
+
+    let report = network.evaluate(&dataset);
+    let loss = dataset
+        .samples()
+        .map(|(x, y)| network.loss.function(&network.forward(x), y))
+        .sum::<f64>()
+        / 4.0;
+    assert!((report.loss - loss).abs() < 1e-12);
+    assert_eq!(report.metrics["accuracy"], 1.0);
+    assert!(report.metrics["mae"] < 0.1);
+}
+
+#[test]
+fn test_network_fit_early_stopping() {
+    let train = vec![
+        (vec![0.0, 0.0], vec![0.0]),
+        (vec![0.0, 1.0], vec![0.0]),
+        (vec![1.0, 0.0], vec![0.0]),
+        (vec![1.0, 1.0], vec![1.0]),
+    ];
+    // labels flipped, so the validation loss gets worse as training goes on
+    let validation: Vec<(Vec<f64>, Vec<f64>)> = train
+        .iter()
+        .map(|(x, y)| (x.clone(), vec![1.0 - y[0]]))
+        .collect();
+    let (train, validation) = (
+        Dataset::from_samples(&train),
+        Dataset::from_samples(&validation),
+    );
+
+    let mut config = and_config();
+    config.patience = Some(5);
+    let mut network = Network::new(config);
+    let reports = network.fit(&train, &validation);
+
+    assert!(reports.len() < 200);
+    let best = reports.iter().map(|r| r.loss).fold(f64::INFINITY, f64::min);
+    assert_eq!(
+        reports.len(),
+        6 + reports.iter().position(|r| r.loss == best).unwrap()
+    );
+    assert!((network.evaluate(&validation).loss - best).abs() < 1e-12);
+}
+
+#[test]
+fn test_network_fit_diverging() {
+    let dataset = Dataset::from_samples(&[
+        (vec![1.0], vec![2.0]),
+        (vec![2.0], vec![4.0]),
+        (vec![3.0], vec![6.0]),
+    ]);
+    // far too large a rate for a linear layer: the loss overflows to NaN
+    // well before the patience runs out
+    let config = Config {
+        lr: 5.0,
+        epochs: 1000,
+        patience: Some(100),
+        layers: vec![ConfigLayer {
+            input_size: 1,
+            output_size: 1,
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let mut network = Network::new(config);
+    let reports = network.fit(&dataset, &dataset);
+
+    assert_eq!(reports.len(), 101);
+    assert!(reports.last().unwrap().loss.is_nan());
+    let best = reports.iter().map(|r| r.loss).fold(f64::INFINITY, f64::min);
+    assert!(best.is_finite());
+    assert_eq!(network.evaluate(&dataset).loss, best);
+}
+
+#[test]
+fn test_network_fit_without_patience() {
+    let train = Dataset::from_samples(&[
+        (vec![0.0, 0.0], vec![0.0]),
+        (vec![0.0, 1.0], vec![0.0]),
+        (vec![1.0, 0.0], vec![0.0]),
+        (vec![1.0, 1.0], vec![1.0]),
+    ]);
+    let mut network = Network::new(and_config());
+    let reports = network.fit(&train, &train);
+    assert_eq!(reports.len(), 200);
+    assert_eq!(reports.last().unwrap().metrics["accuracy"], 1.0);
+}
+
+#[test]
+fn test_network_shuffle_seeded() {
+    let dataset = Dataset::from_samples(&[
+        (vec![0.0, 0.0], vec![0.0]),
+        (vec![0.0, 1.0], vec![0.0]),
+        (vec![1.0, 0.0], vec![0.0]),
+        (vec![1.0, 1.0], vec![1.0]),
+    ]);
+    let train = |shuffle: bool| {
+        let mut config = and_config();
+        config.epochs = 5;
+        config.shuffle = shuffle;
+        let mut network = Network::new(config);
+        network.train(&dataset);
+        network.layers[0].parameters()[0].to_vec()
+    };
+
+    assert_eq!(train(true), train(true));
+    assert_eq!(train(false), train(false));
+    assert_ne!(train(true), train(false));
+}
+
+/// Epochs of `optimizer` until the XOR loss falls below `threshold`, at most
+/// `limit`.
+fn xor_epochs(optimizer: &str, lr: f64, threshold: f64, limit: usize) -> usize {
+    let dataset = Dataset::from_samples(&[
+        (vec![0.0, 0.0], vec![0.0]),
+        (vec![0.0, 1.0], vec![1.0]),
+        (vec![1.0, 0.0], vec![1.0]),
+        (vec![1.0, 1.0], vec![0.0]),
+    ]);
+    let config = Config {
+        lr,
+        epochs: 1,
+        loss: "mse".to_string(),
+        optimizer: optimizer.to_string(),
+        initialization: ConfigInitialization {
+            method: "xavier-uniform".to_string(),
+            seed: Some(42),
+        },
+        layers: vec![
+            ConfigLayer {
+                name: "hidden".to_string(),
+                input_size: 2,
+                output_size: 8,
+                activation: "tanh".to_string(),
+                ..Default::default()
+            },
+            ConfigLayer {
+                name: "output".to_string(),
+                input_size: 8,
+                output_size: 1,
+                activation: "sigmoid".to_string(),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+
+    let mut network = Network::new(config);
+    (1..=limit)
+        .find(|_| {
+            network.train(&dataset);
+            network.evaluate(&dataset).loss < threshold
+        })
+        .unwrap_or(limit)
+}
+
+#[test]
+fn test_network_xor_adam_converges_faster() {
+    // each with a learning rate that suits it
+    let sgd = xor_epochs("sgd", 0.5, 1e-3, 20000);
+    let adam = xor_epochs("adam", 0.1, 1e-3, 20000);
+    assert!(adam * 3 < sgd, "adam took {adam} epochs, sgd {sgd}");
+}
+
+#[test]
+fn test_network_optimizer_params() {
+    let config = |optimizer: &str, params: &[(&str, f64)]| Config {
+        lr: 0.1,
+        epochs: 20,
+        optimizer: optimizer.to_string(),
+        optimizer_params: params.iter().map(|(k, v)| (k.to_string(), *v)).collect(),
+        initialization: ConfigInitialization {
+            method: "xavier-uniform".to_string(),
+            seed: Some(42),
+        },
+        layers: vec![ConfigLayer {
+            name: "output".to_string(),
+            input_size: 2,
+            output_size: 1,
+            activation: "sigmoid".to_string(),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let dataset =
+        Dataset::from_samples(&[(vec![0.0, 1.0], vec![1.0]), (vec![1.0, 0.0], vec![0.0])]);
+    let train = |config: Config| {
+        let mut network = Network::new(config);
+        network.train(&dataset);
+        network.layers[0].parameters()[0].to_vec()
+    };
+
+    // momentum without momentum is plain gradient descent
+    let sgd = train(config("sgd", &[]));
+    assert_eq!(train(config("momentum", &[("momentum", 0.0)])), sgd);
+    assert_ne!(train(config("momentum", &[])), sgd);
+}
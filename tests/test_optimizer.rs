@@ -0,0 +1,76 @@
+use std::collections::HashMap;
+
+use rust_nn::optimizer::*;
+
+fn minimize(optimizer: &mut dyn Optimizer, lr: f64, steps: usize) -> Vec<f64> {
+    // f(x) = sum((x - target)^2), minimum at target
+    let target = [1.0, -2.0, 0.5];
+    let mut params = vec![0.0, 0.0, 0.0];
+    for _ in 0..steps {
+        let grads: Vec<f64> = params
+            .iter()
+            .zip(target.iter())
+            .map(|(p, t)| 2.0 * (p - t))
+            .collect();
+        optimizer.update((0, 0), &mut params, &grads, lr);
+    }
+    params
+        .iter()
+        .zip(target.iter())
+        .map(|(p, t)| (p - t).abs())
+        .collect()
+}
+
+#[test]
+fn test_optimizer_sgd_update() {
+    let mut sgd = Sgd {};
+    let mut params = vec![1.0, 2.0];
+    sgd.update((0, 0), &mut params, &[0.5, -1.0], 0.1);
+    assert!((params[0] - 0.95).abs() < 1e-12);
+    assert!((params[1] - 2.1).abs() < 1e-12);
+}
+
+#[test]
+fn test_optimizer_state_per_parameter() {
+    let mut momentum = Momentum::new(HashMap::new(), false);
+    let mut a = vec![0.0];
+    let mut b = vec![0.0];
+    momentum.update((0, 0), &mut a, &[1.0], 1.0);
+    momentum.update((0, 0), &mut a, &[1.0], 1.0);
+    momentum.update((0, 1), &mut b, &[1.0], 1.0);
+    assert!((a[0] + 2.9).abs() < 1e-12);
+    assert!((b[0] + 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_optimizer_convergence() {
+    let cases = [
+        ("sgd", 0.1),
+        ("momentum", 0.05),
+        ("nesterov", 0.05),
+        ("rmsprop", 0.01),
+        ("adagrad", 0.5),
+        ("adam", 0.05),
+        ("adamw", 0.05),
+    ];
+    for (name, lr) in cases {
+        let mut optimizer = get_optimizer(name, HashMap::new()).unwrap();
+        let errors = minimize(optimizer.as_mut(), lr, 1000);
+        let tolerance = if name == "adamw" { 5e-2 } else { 1e-2 };
+        assert!(
+            errors.iter().all(|e| *e < tolerance),
+            "{} did not converge: {:?}",
+            name,
+            errors
+        );
+    }
+}
+
+#[test]
+fn test_optimizer_adam_bias_correction() {
+    let mut adam = Adam::new(HashMap::new());
+    let mut params = vec![0.0];
+    adam.update((0, 0), &mut params, &[3.0], 0.1);
+    // first bias-corrected step is lr * sign(grad)
+    assert!((params[0] + 0.1).abs() < 1e-6);
+}
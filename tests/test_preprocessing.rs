@@ -0,0 +1,246 @@
+use rust_nn::config::{Config, ConfigInitialization, ConfigLayer};
+use rust_nn::dataset::Dataset;
+use rust_nn::error::Error;
+use rust_nn::matrix::Matrix;
+use rust_nn::network::Network;
+use rust_nn::preprocessing::{
+    get_preprocessor, LabelEncoder, MinMaxScaler, OneHotEncoder, Pipeline, Preprocessor,
+    StandardScaler, PREPROCESSORS,
+};
+
+fn data() -> Matrix {
+    Matrix::from_rows([&[1.0, 10.0, 2.0][..], &[3.0, 10.0, 0.0], &[5.0, 10.0, 2.0]])
+}
+
+#[test]
+fn test_standard_scaler() {
+    let mut scaler = StandardScaler::new(vec![]);
+    scaler.fit(&data());
+    let outputs = scaler.transform(&data()).unwrap();
+    let std = (8.0f64 / 3.0).sqrt();
+    assert_eq!(outputs.row(0)[0], -2.0 / std);
+    assert_eq!(outputs.row(2)[0], 2.0 / std);
+    // constant columns are centered only
+    assert_eq!(outputs.row(1)[1], 0.0);
+
+    let mut scaler = StandardScaler::new(vec![1]);
+    scaler.fit(&data());
+    let outputs = scaler.transform(&data()).unwrap();
+    assert_eq!(outputs.row(0), [1.0, 0.0, 2.0]);
+}
+
+#[test]
+fn test_min_max_scaler() {
+    let mut scaler = MinMaxScaler::new(vec![0, 2]);
+    scaler.fit(&data());
+    let outputs = scaler.transform(&data()).unwrap();
+    assert_eq!(
+        outputs.to_rows(),
+        vec![
+            vec![0.0, 10.0, 1.0],
+            vec![0.5, 10.0, 0.0],
+            vec![1.0, 10.0, 1.0]
+        ]
+    );
+    assert!(matches!(
+        scaler.transform(&Matrix::zeros(1, 2)),
+        Err(Error::ShapeMismatch {
+            expected: 3,
+            actual: 2
+        })
+    ));
+}
+
+#[test]
+fn test_encoders() {
+    let mut encoder = OneHotEncoder::new(vec![2]);
+    encoder.fit(&data());
+    let outputs = encoder.transform(&data()).unwrap();
+    assert_eq!(outputs.row(0), [1.0, 10.0, 0.0, 1.0]);
+    assert_eq!(outputs.row(1), [3.0, 10.0, 1.0, 0.0]);
+    let unseen = encoder
+        .transform(&Matrix::from_rows([&[0.0, 0.0, 7.0][..]]))
+        .unwrap();
+    assert_eq!(unseen.row(0), [0.0, 0.0, 0.0, 0.0]);
+
+    let mut encoder = LabelEncoder::new(vec![0]);
+    encoder.fit(&data());
+    let outputs = encoder.transform(&data()).unwrap();
+    assert_eq!(
+        (0..3).map(|i| outputs.row(i)[0]).collect::<Vec<f64>>(),
+        vec![0.0, 1.0, 2.0]
+    );
+    assert_eq!(encoder.inverse(0, 1), Some(3.0));
+    assert!(matches!(
+        encoder.transform(&Matrix::from_rows([&[4.0, 0.0, 0.0][..]])),
+        Err(Error::Validation(_))
+    ));
+}
+
+#[test]
+fn test_pipeline() {
+    for name in PREPROCESSORS {
+        assert!(get_preprocessor(name, vec![]).is_ok());
+    }
+    assert!(matches!(
+        get_preprocessor("unknown", vec![]),
+        Err(Error::UnknownPreprocessor(_))
+    ));
+
+    let dataset = Dataset::new(data(), Matrix::from_rows([&[7.0][..], &[9.0], &[7.0]])).unwrap();
+    let mut pipeline = Pipeline::new(
+        vec![
+            get_preprocessor("one-hot-encoder", vec![2]).unwrap(),
+            get_preprocessor("min-max-scaler", vec![0]).unwrap(),
+        ],
+        vec![
+            get_preprocessor("label-encoder", vec![]).unwrap(),
+            get_preprocessor("one-hot-encoder", vec![]).unwrap(),
+        ],
+    );
+    assert!(matches!(
+        pipeline.transform(&dataset),
+        Err(Error::Validation(_))
+    ));
+
+    pipeline.fit(&dataset).unwrap();
+    let transformed = pipeline.transform(&dataset).unwrap();
+    assert_eq!(transformed.sample(1).0, [0.5, 10.0, 1.0, 0.0]);
+    assert_eq!(transformed.sample(1).1, [0.0, 1.0]);
+
+    let json = serde_json::to_string(&pipeline).unwrap();
+    assert!(json.contains("\"kind\":\"min-max-scaler\""));
+    let loaded: Pipeline = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded, pipeline);
+}
+
+#[test]
+fn test_network_pipeline() {
+    let config = Config {
+        lr: 0.05,
+        epochs: 200,
+        loss: "binary-crossentropy".to_string(),
+        optimizer: "adam".to_string(),
+        initialization: ConfigInitialization {
+            method: "xavier-uniform".to_string(),
+            seed: Some(42),
+        },
+        layers: vec![ConfigLayer {
+            name: "output".to_string(),
+            input_size: 1,
+            output_size: 1,
+            activation: "sigmoid".to_string(),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    // raw inputs far from zero, separable around 1000
+    let dataset = Dataset::from_samples(&[
+        (vec![900.0], vec![0.0]),
+        (vec![950.0], vec![0.0]),
+        (vec![1050.0], vec![1.0]),
+        (vec![1100.0], vec![1.0]),
+    ]);
+
+    let mut network = Network::new(config);
+    network.pipeline = Pipeline::new(
+        vec![get_preprocessor("standard-scaler", vec![]).unwrap()],
+        vec![],
+    );
+    network.train(&dataset);
+    assert!(network.pipeline.fitted);
+    assert!(network.forward(&[920.0])[0] < 0.5);
+    assert!(network.forward(&[1080.0])[0] > 0.5);
+
+    let path = std::env::temp_dir().join("rust-nn-test-network-pipeline.json");
+    let path = path.to_str().unwrap();
+    network.save(path).unwrap();
+    let loaded = Network::load(path).unwrap();
+    std::fs::remove_file(path).unwrap();
+
+    assert_eq!(loaded.pipeline, network.pipeline);
+    assert_eq!(loaded.forward(&[1080.0]), network.forward(&[1080.0]));
+    assert_eq!(
+        loaded.evaluate(&dataset).loss,
+        network.evaluate(&dataset).loss
+    );
+}
+
+#[test]
+fn test_network_target_scaler() {
+    let config = Config {
+        lr: 0.1,
+        epochs: 300,
+        loss: "mse".to_string(),
+        optimizer: "adam".to_string(),
+        initialization: ConfigInitialization {
+            method: "xavier-uniform".to_string(),
+            seed: Some(42),
+        },
+        layers: vec![ConfigLayer {
+            name: "output".to_string(),
+            input_size: 1,
+            output_size: 1,
+            activation: "linear".to_string(),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    // targets far from zero, y = 1000 + 100 x
+    let samples: Vec<(Vec<f64>, Vec<f64>)> = (0..5)
+        .map(|x| (vec![x as f64], vec![1000.0 + 100.0 * x as f64]))
+        .collect();
+    let dataset = Dataset::from_samples(&samples);
+
+    let mut network = Network::new(config);
+    network.pipeline = Pipeline::new(
+        vec![get_preprocessor("standard-scaler", vec![]).unwrap()],
+        vec![get_preprocessor("standard-scaler", vec![]).unwrap()],
+    );
+    network.train(&dataset);
+    // outputs are in the scale of the targets
+    assert!((network.forward(&[2.5])[0] - 1250.0).abs() < 1.0);
+    assert!(network.evaluate(&dataset).loss < 1e-4);
+}
+
+#[test]
+fn test_network_pipeline_errors() {
+    let config = Config {
+        layers: vec![ConfigLayer {
+            input_size: 3,
+            output_size: 1,
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let mut network = Network::new(config);
+    network.pipeline = Pipeline::new(
+        vec![get_preprocessor("standard-scaler", vec![]).unwrap()],
+        vec![get_preprocessor("label-encoder", vec![]).unwrap()],
+    );
+    // not fitted yet
+    assert!(matches!(
+        network.try_forward(&[1.0, 10.0, 2.0]),
+        Err(Error::Validation(_))
+    ));
+
+    let dataset = Dataset::new(data(), Matrix::from_rows([&[7.0][..], &[9.0], &[7.0]])).unwrap();
+    network.try_train(&dataset).unwrap();
+    assert!(matches!(
+        network.try_forward(&[1.0, 10.0]),
+        Err(Error::ShapeMismatch {
+            expected: 3,
+            actual: 2
+        })
+    ));
+    // the label encoder has not seen 8
+    let unseen = Dataset::new(data(), Matrix::from_rows([&[7.0][..], &[8.0], &[7.0]])).unwrap();
+    assert!(matches!(
+        network.try_evaluate(&unseen),
+        Err(Error::Validation(_))
+    ));
+    assert!(matches!(
+        network.try_fit(&dataset, &unseen),
+        Err(Error::Validation(_))
+    ));
+}
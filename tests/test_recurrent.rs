@@ -0,0 +1,182 @@
+use rust_nn::config::{Config, ConfigLayer};
+use rust_nn::dataset::{Dataset, Sequence};
+use rust_nn::matrix::Matrix;
+use rust_nn::network::Network;
+
+mod common;
+
+use common::{
+    assert_gradcheck, assert_invalid, assert_shape_mismatch, build, check_gradients,
+    initialization, input_grads,
+};
+
+fn recurrent(kind: &str, return_sequences: bool) -> ConfigLayer {
+    ConfigLayer {
+        name: kind.to_string(),
+        kind: kind.to_string(),
+        input_size: 8,
+        output_size: if return_sequences { 12 } else { 3 },
+        steps: 4,
+        hidden_size: 3,
+        return_sequences,
+        activation: "tanh".to_string(),
+        ..Default::default()
+    }
+}
+
+/// Two sequences of 4 steps of 2 features.
+fn sequences() -> Matrix {
+    Matrix::from_rows([
+        &[0.5, -1.0, 0.2, 0.1, 1.5, -0.3, 0.0, 0.7][..],
+        &[-0.8, 0.9, -1.4, 0.2, 0.6, -0.5, 1.8, -0.1][..],
+    ])
+}
+
+#[test]
+fn test_recurrent_gradients() {
+    for kind in ["rnn", "lstm", "gru"] {
+        for return_sequences in [false, true] {
+            let config = recurrent(kind, return_sequences);
+            assert!(config.validate().is_ok());
+            let mut layer = build(&config, &initialization());
+            assert_eq!(
+                layer.output_shape().iter().product::<usize>(),
+                config.output_size
+            );
+            check_gradients(layer.as_mut(), &sequences());
+        }
+    }
+}
+
+#[test]
+fn test_recurrent_state() {
+    for kind in ["rnn", "lstm", "gru"] {
+        let sequence = build(&recurrent(kind, true), &initialization()).forward(&sequences());
+        let last = build(&recurrent(kind, false), &initialization()).forward(&sequences());
+        for i in 0..2 {
+            assert_eq!(&sequence.row(i)[9..], last.row(i), "{kind}");
+        }
+
+        // each step only sees the steps before it
+        let mut changed = sequences();
+        changed.data[6] += 1.0;
+        let outputs = build(&recurrent(kind, true), &initialization()).forward(&changed);
+        assert_eq!(&outputs.row(0)[..9], &sequence.row(0)[..9], "{kind}");
+        assert_ne!(&outputs.row(0)[9..], &sequence.row(0)[9..], "{kind}");
+    }
+}
+
+#[test]
+fn test_truncated_bptt() {
+    let weights = vec![1.0; 6];
+    for kind in ["rnn", "lstm", "gru"] {
+        let full = input_grads(
+            build(&recurrent(kind, false), &initialization()).as_mut(),
+            &sequences(),
+            &weights,
+        );
+        assert!(full.row(0)[..4].iter().all(|g| *g != 0.0), "{kind}");
+
+        // gradients of the last state stop at the chunk of steps 2 and 3
+        let config = ConfigLayer {
+            truncation: 2,
+            ..recurrent(kind, false)
+        };
+        let truncated = input_grads(
+            build(&config, &initialization()).as_mut(),
+            &sequences(),
+            &weights,
+        );
+        for i in 0..2 {
+            assert!(truncated.row(i)[..4].iter().all(|g| *g == 0.0), "{kind}");
+            assert_eq!(truncated.row(i)[4..], full.row(i)[4..], "{kind}");
+        }
+    }
+}
+
+/// Parity of sequences of 3 bits, from the last state of a recurrent layer.
+fn parity(kind: &str) -> Config {
+    Config {
+        lr: 0.02,
+        epochs: 1000,
+        optimizer: "adam".to_string(),
+        loss: "binary-crossentropy".to_string(),
+        metrics: vec!["accuracy".to_string()],
+        initialization: initialization(),
+        input_shape: vec![3, 1],
+        layers: vec![
+            ConfigLayer {
+                name: "recurrent".to_string(),
+                kind: kind.to_string(),
+                input_size: 3,
+                output_size: 8,
+                steps: 3,
+                hidden_size: 8,
+                activation: "tanh".to_string(),
+                ..Default::default()
+            },
+            ConfigLayer {
+                name: "output".to_string(),
+                input_size: 8,
+                output_size: 1,
+                activation: "sigmoid".to_string(),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_network_parity() {
+    let sequences: Vec<Sequence> = (0..8)
+        .map(|n: usize| {
+            let bits: Vec<Vec<f64>> = (0..3).map(|b| vec![(n >> b & 1) as f64]).collect();
+            (bits, vec![vec![(n.count_ones() % 2) as f64]])
+        })
+        .collect();
+    let dataset = Dataset::from_sequences(&sequences);
+    assert_eq!(dataset.sample(6), (&[0.0, 1.0, 1.0][..], &[0.0][..]));
+
+    for kind in ["rnn", "lstm", "gru"] {
+        let config = parity(kind);
+        assert_eq!(config.infer_shapes().unwrap(), vec![vec![8], vec![1]]);
+        let mut network = Network::new(config);
+        network.train_sequences(&sequences);
+        let report = network.evaluate(&dataset);
+        assert_eq!(report.metrics["accuracy"], 1.0, "{kind}");
+        assert_eq!(network.forward_sequence(&sequences[0].0).len(), 1);
+    }
+}
+
+#[test]
+fn test_network_recurrent_gradcheck() {
+    for kind in ["rnn", "lstm", "gru"] {
+        let mut config = parity(kind);
+        config.loss = "mse".to_string();
+        config.layers[0].return_sequences = true;
+        config.layers[0].output_size = 24;
+        config.layers[1].input_size = 24;
+        assert_gradcheck(&config, &[0.9, -0.4, 0.6], &[0.3], 1e-5);
+
+        let network = Network::new(config);
+        let outputs = network.forward_sequence(&[vec![1.0], vec![0.0], vec![1.0]]);
+        assert_eq!(outputs.len(), 1);
+    }
+}
+
+#[test]
+fn test_recurrent_config() {
+    let mut invalid = parity("lstm");
+    invalid.layers[0].output_size = 24;
+    invalid.layers[1].input_size = 24;
+    assert_shape_mismatch(&invalid, 8, 24);
+
+    let mut invalid = parity("gru");
+    invalid.layers[0].steps = 2;
+    assert_invalid(&invalid);
+
+    let mut invalid = parity("rnn");
+    invalid.input_shape = vec![1, 3];
+    assert_invalid(&invalid);
+}
@@ -0,0 +1,201 @@
+use rust_nn::config::{Config, ConfigInitialization, ConfigLayer};
+use rust_nn::dataset::Dataset;
+use rust_nn::error::Error;
+use rust_nn::initialization::get_initialization;
+use rust_nn::layer::{Dense, Layer};
+use rust_nn::network::Network;
+use rust_nn::optimizer::Sgd;
+use rust_nn::regularization::Regularization;
+
+#[test]
+fn test_regularization() {
+    let regularization = Regularization {
+        l1: 0.1,
+        l2: 0.2,
+        ..Default::default()
+    };
+    let params = [1.0, -2.0, 0.0];
+    assert!((regularization.penalty(&params) - (0.3 + 0.5)).abs() < 1e-12);
+
+    let mut grads = vec![0.0; 3];
+    regularization.gradient(&params, &mut grads);
+    assert_eq!(grads, vec![0.1 + 0.2, -0.1 - 0.4, 0.0]);
+
+    let decay = Regularization {
+        weight_decay: 0.5,
+        ..Default::default()
+    };
+    let mut params = vec![1.0, -2.0];
+    decay.decay(0.1, &mut params);
+    assert_eq!(params, vec![0.95, -1.9]);
+    assert_eq!(decay.penalty(&params), 0.0);
+}
+
+fn layer(l2: f64, weight_decay: f64, regularize_biases: bool) -> Dense {
+    let config = ConfigLayer {
+        name: "test".to_string(),
+        input_size: 3,
+        output_size: 2,
+        l2,
+        weight_decay,
+        regularize_biases,
+        ..Default::default()
+    };
+    let initialization = ConfigInitialization {
+        method: "uniform-distribution".to_string(),
+        seed: Some(42),
+    };
+    Dense::new(&config, &mut get_initialization(&initialization).unwrap()).unwrap()
+}
+
+#[test]
+fn test_layer_update_regularization() {
+    // without data gradients, l2 and weight decay only shrink the weights
+    for (l2, weight_decay) in [(0.5, 0.0), (0.0, 0.5)] {
+        let mut layer = layer(l2, weight_decay, false);
+        let (weights, biases) = (layer.weights.clone(), layer.biases.clone());
+        layer.update(0.1, &mut Sgd {}, 0, 1);
+        for (w, before) in layer.weights.data.iter().zip(weights.data.iter()) {
+            assert!((w - 0.95 * before).abs() < 1e-12);
+        }
+        assert_eq!(layer.biases, biases);
+    }
+
+    let mut layer = layer(0.5, 0.0, true);
+    let biases = layer.biases.clone();
+    layer.update(0.1, &mut Sgd {}, 0, 1);
+    for (b, before) in layer.biases.iter().zip(biases.iter()) {
+        assert!((b - 0.95 * before).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn test_network_penalty() {
+    let config = Config {
+        initialization: ConfigInitialization {
+            method: "uniform-distribution".to_string(),
+            seed: Some(42),
+        },
+        layers: vec![
+            ConfigLayer {
+                name: "hidden".to_string(),
+                input_size: 3,
+                output_size: 2,
+                l1: 0.1,
+                ..Default::default()
+            },
+            ConfigLayer {
+                name: "output".to_string(),
+                input_size: 2,
+                output_size: 1,
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+    let network = Network::new(config.clone());
+    let l1 = network.layers[0].parameters()[0]
+        .iter()
+        .map(|w| 0.1 * w.abs())
+        .sum::<f64>();
+    assert!((network.penalty() - l1).abs() < 1e-12);
+
+    let mut invalid = config;
+    invalid.layers[1].l2 = -1.0;
+    assert!(matches!(invalid.validate(), Err(Error::Validation(_))));
+}
+
+#[test]
+fn test_adamw_weight_decay_config() {
+    let config = Config {
+        optimizer: "adamw".to_string(),
+        layers: vec![ConfigLayer {
+            input_size: 3,
+            output_size: 1,
+            weight_decay: 0.01,
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    // adamw decays by 0.01 unless told otherwise
+    assert!(matches!(config.validate(), Err(Error::Validation(_))));
+
+    let mut explicit = config.clone();
+    explicit
+        .optimizer_params
+        .insert("weight_decay".to_string(), 0.05);
+    assert!(matches!(explicit.validate(), Err(Error::Validation(_))));
+
+    let mut per_layer = config.clone();
+    per_layer
+        .optimizer_params
+        .insert("weight_decay".to_string(), 0.0);
+    assert!(per_layer.validate().is_ok());
+
+    let mut optimizer_only = config;
+    optimizer_only.layers[0].weight_decay = 0.0;
+    assert!(optimizer_only.validate().is_ok());
+}
+
+#[test]
+fn test_regularization_config() {
+    for kind in ["dense", "batch-norm", "layer-norm", "dropout"] {
+        let layer = ConfigLayer {
+            kind: kind.to_string(),
+            input_size: 3,
+            output_size: 3,
+            ..Default::default()
+        };
+        assert!(layer.validate().is_ok(), "{kind}");
+        let regularized = ConfigLayer { l2: 0.1, ..layer };
+        assert_eq!(regularized.validate().is_ok(), kind == "dense", "{kind}");
+    }
+}
+
+/// Loss logged for the first batch of a network trained with `l1` and `l2`
+/// on its hidden layer, computed with the seeded initial weights.
+fn logged_loss(l1: f64, l2: f64) -> f64 {
+    let config = Config {
+        epochs: 1,
+        initialization: ConfigInitialization {
+            method: "uniform-distribution".to_string(),
+            seed: Some(42),
+        },
+        layers: vec![
+            ConfigLayer {
+                name: "hidden".to_string(),
+                input_size: 2,
+                output_size: 2,
+                l1,
+                l2,
+                ..Default::default()
+            },
+            ConfigLayer {
+                name: "output".to_string(),
+                input_size: 2,
+                output_size: 1,
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+    let mut network = Network::new(config);
+    let dataset = Dataset::from_samples(&[(vec![1.0, 0.0], vec![1.0])]);
+    network.train(&dataset);
+
+    let path = format!("{}/loss.jsonl", network.logger.path());
+    let log = std::fs::read_to_string(path).unwrap();
+    let entry: serde_json::Value = serde_json::from_str(log.lines().next().unwrap()).unwrap();
+    entry["value"].as_f64().unwrap()
+}
+
+#[test]
+fn test_network_logged_penalty() {
+    let plain = logged_loss(0.0, 0.0);
+    let l1 = logged_loss(0.1, 0.0);
+    let l2 = logged_loss(0.0, 0.1);
+    assert!(l1 > plain);
+    assert!(l2 > plain);
+    assert!(logged_loss(0.2, 0.0) > l1);
+    assert!(logged_loss(0.1, 0.1) > l1.max(l2));
+}
@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+
+use rust_nn::config::{Config, ConfigInitialization, ConfigLayer, ConfigSchedule};
+use rust_nn::dataset::Dataset;
+use rust_nn::error::Error;
+use rust_nn::network::Network;
+use rust_nn::schedule::*;
+
+fn params(values: &[(&str, f64)]) -> HashMap<String, f64> {
+    values.iter().map(|(k, v)| (k.to_string(), *v)).collect()
+}
+
+#[test]
+fn test_schedule_decay() {
+    let step = StepDecay::new(params(&[("step_size", 2.0), ("gamma", 0.5)]));
+    let lrs: Vec<f64> = (0..5).map(|e| step.lr(1.0, e, 0)).collect();
+    assert_eq!(lrs, vec![1.0, 1.0, 0.5, 0.5, 0.25]);
+
+    let exponential = Exponential::new(params(&[("gamma", 0.5)]));
+    assert_eq!(exponential.lr(1.0, 3, 0), 0.125);
+}
+
+#[test]
+fn test_schedule_cosine_restarts() {
+    let cosine = CosineRestarts::new(params(&[("period", 2.0), ("mult", 2.0), ("min_lr", 0.1)]));
+    assert_eq!(cosine.lr(1.0, 0, 0), 1.0);
+    assert!((cosine.lr(1.0, 1, 0) - 0.55).abs() < 1e-12);
+    // restart after 2 epochs, then a period of 4 epochs
+    assert_eq!(cosine.lr(1.0, 2, 0), 1.0);
+    assert!((cosine.lr(1.0, 4, 0) - 0.55).abs() < 1e-12);
+    assert_eq!(cosine.lr(1.0, 6, 0), 1.0);
+}
+
+#[test]
+fn test_schedule_linear_warmup() {
+    let warmup = LinearWarmup::new(params(&[("steps", 4.0)]));
+    let lrs: Vec<f64> = (0..6).map(|s| warmup.lr(1.0, 0, s)).collect();
+    assert_eq!(lrs, vec![0.25, 0.5, 0.75, 1.0, 1.0, 1.0]);
+}
+
+#[test]
+fn test_schedule_reduce_on_plateau() {
+    let mut plateau = ReduceOnPlateau::new(params(&[("patience", 1.0), ("factor", 0.5)]));
+    plateau.observe(1.0);
+    plateau.observe(0.5);
+    assert_eq!(plateau.lr(1.0, 0, 0), 1.0);
+    plateau.observe(0.5);
+    assert_eq!(plateau.lr(1.0, 0, 0), 1.0);
+    plateau.observe(0.6);
+    assert_eq!(plateau.lr(1.0, 0, 0), 0.5);
+}
+
+#[test]
+fn test_get_schedule() {
+    for name in SCHEDULES {
+        assert!(get_schedule(name, HashMap::new()).is_ok());
+    }
+    assert!(matches!(
+        get_schedule("unknown", HashMap::new()),
+        Err(Error::UnknownSchedule(_))
+    ));
+}
+
+#[test]
+fn test_network_schedule() {
+    let config = |epochs: usize, schedule: Option<ConfigSchedule>| Config {
+        lr: 0.1,
+        epochs,
+        schedule,
+        shuffle: false,
+        loss: "mse".to_string(),
+        initialization: ConfigInitialization {
+            method: "xavier-uniform".to_string(),
+            seed: Some(42),
+        },
+        layers: vec![ConfigLayer {
+            name: "output".to_string(),
+            input_size: 2,
+            output_size: 1,
+            activation: "linear".to_string(),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let dataset =
+        Dataset::from_samples(&[(vec![1.0, 0.0], vec![1.0]), (vec![0.0, 1.0], vec![0.0])]);
+
+    // the learning rate drops to zero after the first epoch
+    let schedule = ConfigSchedule {
+        method: "step".to_string(),
+        params: params(&[("step_size", 1.0), ("gamma", 0.0)]),
+    };
+    let mut scheduled = Network::new(config(5, Some(schedule)));
+    scheduled.train(&dataset);
+    let mut constant = Network::new(config(1, None));
+    constant.train(&dataset);
+    assert_eq!(
+        scheduled.layers[0].parameters(),
+        constant.layers[0].parameters()
+    );
+
+    // only fit reports the validation loss that reduce-on-plateau follows
+    let plateau = ConfigSchedule {
+        method: "reduce-on-plateau".to_string(),
+        params: HashMap::new(),
+    };
+    let mut network = Network::new(config(2, Some(plateau)));
+    assert!(matches!(
+        network.try_train(&dataset),
+        Err(Error::Validation(_))
+    ));
+    assert_eq!(network.try_fit(&dataset, &dataset).unwrap().len(), 2);
+
+    let mut unknown = config(1, None);
+    unknown.schedule = Some(ConfigSchedule {
+        method: "unknown".to_string(),
+        params: HashMap::new(),
+    });
+    assert!(matches!(
+        Network::try_new(unknown),
+        Err(Error::UnknownSchedule(_))
+    ));
+}